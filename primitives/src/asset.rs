@@ -26,6 +26,49 @@ pub enum Asset<MI> {
     CombinatorialOutcome,
     PoolShare(SerdeWrapper<PoolId>),
     Ztg,
+    /// An outcome share of a categorical market that is conditioned on an outcome of another
+    /// categorical market. \[parent_market_id, parent_outcome, child_market_id, child_outcome\]
+    CombinatorialShare(MI, CategoryIndex, MI, CategoryIndex),
+}
+
+impl<MI: Copy> Asset<MI> {
+    /// Returns the market id and the category index of a categorical outcome asset or `None` if
+    /// the asset is anything else.
+    pub fn decode_outcome_asset(&self) -> Option<(MI, CategoryIndex)> {
+        if let Asset::CategoricalOutcome(market_id, index) = *self {
+            Some((market_id, index))
+        } else {
+            None
+        }
+    }
+}
+
+/// Stable, human-readable labels for clients, e.g. `ztg`, `pm-1-0` for the first outcome of
+/// market 1, `pm-1-long` for the long position of scalar market 1 or `pm-2-1-if-pm-1-0` for the
+/// second outcome of market 2 conditioned on the first outcome of market 1.
+#[cfg(feature = "std")]
+impl<MI: std::fmt::Display> std::fmt::Display for Asset<MI> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Asset::CategoricalOutcome(market_id, index) => write!(f, "pm-{}-{}", market_id, index),
+            Asset::ScalarOutcome(market_id, ScalarPosition::Long) => {
+                write!(f, "pm-{}-long", market_id)
+            }
+            Asset::ScalarOutcome(market_id, ScalarPosition::Short) => {
+                write!(f, "pm-{}-short", market_id)
+            }
+            Asset::CombinatorialOutcome => write!(f, "combinatorial"),
+            Asset::PoolShare(pool_id) => write!(f, "pool-{}", pool_id.0),
+            Asset::Ztg => write!(f, "ztg"),
+            Asset::CombinatorialShare(parent_market_id, parent_outcome, market_id, index) => {
+                write!(
+                    f,
+                    "pm-{}-{}-if-pm-{}-{}",
+                    market_id, index, parent_market_id, parent_outcome
+                )
+            }
+        }
+    }
 }
 
 /// In a scalar market, users can either choose a `Long` position,
@@ -50,3 +93,29 @@ pub enum ScalarPosition {
     Long,
     Short,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_outcome_asset_round_trips_categorical_outcome_assets() {
+        for market_id in [0u128, 1, 42] {
+            for index in [0, 1, 7] {
+                let asset = Asset::CategoricalOutcome(market_id, index);
+                assert_eq!(asset.decode_outcome_asset(), Some((market_id, index)));
+            }
+        }
+        assert_eq!(Asset::<u128>::Ztg.decode_outcome_asset(), None);
+        assert_eq!(Asset::ScalarOutcome(0, ScalarPosition::Long).decode_outcome_asset(), None);
+    }
+
+    #[test]
+    fn assets_are_displayed_as_stable_labels() {
+        assert_eq!(Asset::<u128>::Ztg.to_string(), "ztg");
+        assert_eq!(Asset::CategoricalOutcome(3u128, 1).to_string(), "pm-3-1");
+        assert_eq!(Asset::ScalarOutcome(3u128, ScalarPosition::Short).to_string(), "pm-3-short");
+        assert_eq!(Asset::<u128>::PoolShare(SerdeWrapper(7)).to_string(), "pool-7");
+        assert_eq!(Asset::CombinatorialShare(1u128, 0, 2, 1).to_string(), "pm-2-1-if-pm-1-0");
+    }
+}
@@ -8,11 +8,14 @@ pub mod ztg;
 
 use crate::{
     asset::Asset,
-    types::{AccountId, AccountIdTest, Balance, BlockNumber, CurrencyId, Moment},
+    types::{
+        AccountId, AccountIdTest, Balance, BlockNumber, CurrencyId, DisputeResolutionRule,
+        MarketEndKinds, Moment, ScalarDisputeStep, ScalarResolutionPolicy, ValidityBondPolicy,
+    },
 };
 use frame_support::{parameter_types, PalletId};
 use orml_traits::parameter_type_with_key;
-use sp_runtime::{traits::AccountIdConversion, Permill};
+use sp_runtime::{traits::AccountIdConversion, Perbill, Permill};
 
 // Definitions for time
 pub const BLOCKS_PER_DAY: BlockNumber = BLOCKS_PER_HOUR * 24;
@@ -89,20 +92,52 @@ parameter_types! {
 // Prediction Market parameters
 parameter_types! {
     pub const AdvisoryBond: Balance = 25 * CENT;
+    pub const AdvisoryReviewPeriod: BlockNumber = 7 * BLOCKS_PER_DAY;
+    pub const AllowOracleSelfDispute: bool = false;
+    pub const AllowPublicReporting: bool = true;
+    pub const AllowedMarketEndKinds: MarketEndKinds = MarketEndKinds::Any;
+    pub const CancellationPenalty: Perbill = Perbill::zero();
+    pub const CompleteSetMintFee: Perbill = Perbill::zero();
     pub const DisputeBond: Balance = 5 * BASE;
     pub const DisputeFactor: Balance = 2 * BASE;
     pub const DisputePeriod: BlockNumber = BLOCKS_PER_DAY;
+    pub const DisputeResolution: DisputeResolutionRule = DisputeResolutionRule::LastDispute;
+    pub const GlobalDisputePeriod: BlockNumber = 3 * BLOCKS_PER_DAY;
+    pub const MaxAdditionalMetadata: u32 = 16;
     pub const MaxCategories: u16 = 10;
+    pub const MaxConcurrentDisputedMarkets: u32 = 1_000;
+    pub const MaxDestroyedShareAccounts: u32 = 4_500;
     pub const MaxDisputes: u16 = 6;
+    pub const MaxDisputesPerAccount: u16 = 2;
+    pub const MaxExpiredProposalsPerBlock: u32 = 16;
+    pub const MaxMetadataLength: u32 = 1024;
+    pub const MaxOutcomeLabelLength: u32 = 64;
+    pub const MaxPoolDeploymentsPerBatch: u32 = 16;
+    pub const MaxResolutionFailures: u32 = 10;
+    pub const MaxTotalMetadataBytes: u32 = 1024;
+    pub const MillisecondsPerBlock: u32 = MILLISECS_PER_BLOCK;
     pub const MinCategories: u16 = 2;
+    pub const MinEffectiveDisputeBond: Balance = 5 * BASE;
+    pub const MinGlobalDisputeInitialStake: Balance = 10 * BASE;
+    pub const MinGlobalDisputeStake: Balance = BASE;
     // 60_000 = 1 minute. Should be raised to something more reasonable in the future.
     pub const MinSubsidyPeriod: Moment = 60_000;
     // 2_678_400_000 = 31 days.
     pub const MaxSubsidyPeriod: Moment = 2_678_400_000;
+    pub const MonotonicScalarDisputes: ScalarDisputeStep = ScalarDisputeStep::Unrestricted;
     pub const OracleBond: Balance = 50 * CENT;
+    pub const OracleGracePeriod: u32 = BLOCKS_PER_DAY as _;
     pub const PmPalletId: PalletId = PalletId(*b"zge/pred");
+    pub const PoolWeightsSum: Option<u128> = None;
+    pub const ProRataRedemption: bool = true;
+    pub const RedemptionTreasuryFee: Perbill = Perbill::zero();
     pub const ReportingPeriod: u32 = BLOCKS_PER_DAY as _;
+    pub const ResolutionReward: Balance = 5 * CENT;
+    pub const ScalarResolution: ScalarResolutionPolicy = ScalarResolutionPolicy::LastDispute;
+    pub const SlashSplit: Perbill = Perbill::zero();
     pub const ValidityBond: Balance = 50 * CENT;
+    pub const ValidityBondSettlement: ValidityBondPolicy = ValidityBondPolicy::ReturnIfValid;
+    pub const ZeroSupplyRefund: bool = true;
 }
 
 // Simple disputes parameters
@@ -6,9 +6,11 @@ mod asset;
 pub mod constants;
 mod market;
 mod max_runtime_usize;
+mod outcome_descriptor;
 mod outcome_report;
 mod pool;
 mod pool_status;
+mod scalar_payout;
 mod serde_wrapper;
 pub mod traits;
 pub mod types;
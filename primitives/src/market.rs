@@ -1,6 +1,16 @@
-use crate::{pool::ScoringRule, types::OutcomeReport};
+use crate::{
+    constants::MaxMetadataLength,
+    pool::ScoringRule,
+    types::{OutcomeDescriptor, OutcomeReport},
+};
 use alloc::vec::Vec;
 use core::ops::{Range, RangeInclusive};
+use frame_support::BoundedVec;
+use sp_runtime::traits::Saturating;
+
+/// Variable length metadata of at most `MaxMetadataLength` bytes, e.g. the content address of a
+/// market's description or the label of an outcome.
+pub type BoundedMetadata = BoundedVec<u8, MaxMetadataLength>;
 
 /// Types
 ///
@@ -20,13 +30,13 @@ pub struct Market<AI, BN, M> {
     pub creator: AI,
     /// Creation type.
     pub creation: MarketCreation,
-    /// The fee the creator gets from each winning share.
+    /// The fee the creator gets from each winning share, in percent.
     pub creator_fee: u8,
     /// Oracle that reports the outcome of this market.
     pub oracle: AI,
     /// Metadata for the market, usually a content address of IPFS
     /// hosted JSON.
-    pub metadata: Vec<u8>,
+    pub metadata: BoundedMetadata,
     /// The type of the market.
     pub market_type: MarketType,
     /// Market start and end
@@ -41,15 +51,83 @@ pub struct Market<AI, BN, M> {
     pub resolved_outcome: Option<OutcomeReport>,
     /// See [`MarketDisputeMechanism`].
     pub mdm: MarketDisputeMechanism<AI>,
+    /// The maximum number of disputes allowed on this market. Falls back to the global
+    /// `MaxDisputes` if `None`.
+    pub max_disputes: Option<u32>,
+    /// The block in which the market was created.
+    pub created_at: BN,
+    /// The timestamp of the block in which the market was created.
+    pub created_at_timestamp: M,
+    /// See [`MarketKind`].
+    pub kind: MarketKind,
 }
 
 impl<AI, BN, M> Market<AI, BN, M> {
-    // Returns the number of outcomes for a market.
+    // Returns the number of tradeable outcomes for a market, i.e. the number of outcome shares.
     pub fn outcomes(&self) -> u16 {
-        match self.market_type {
-            MarketType::Categorical(categories) => categories,
-            MarketType::Scalar(_) => 2,
-        }
+        self.market_type.outcome_count()
+    }
+
+    // Returns the number of outcomes a market can resolve to. This includes the implicit
+    // `Invalid` outcome, for which no share exists.
+    pub fn resolvable_outcomes(&self) -> u16 {
+        self.outcomes().saturating_add(1)
+    }
+}
+
+/// A market together with the position of a single account in it.
+///
+/// The balances are indexed by outcome. For scalar markets, the long position comes first.
+///
+/// Types
+///
+/// * `AI`: Account Id
+/// * `BN`: Block Number
+/// * `M`: Moment (Time moment)
+/// * `B`: Balance
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub struct MarketView<AI, BN, M, B> {
+    /// The market.
+    pub market: Market<AI, BN, M>,
+    /// The free balance of each outcome asset.
+    pub free: Vec<B>,
+    /// The reserved balance of each outcome asset.
+    pub reserved: Vec<B>,
+    /// The collateral that can be redeemed. Only `Some` if the market is resolved.
+    pub winnings: Option<B>,
+}
+
+/// The bonds of a market that are still reserved from its creator.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Default,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub struct BondBreakdown<B> {
+    /// Reserved until an advised market is approved or rejected.
+    pub advisory: B,
+    /// Reserved until a permissionless market is resolved.
+    pub validity: B,
+    /// Reserved until the market is resolved.
+    pub oracle: B,
+}
+
+impl<B: Copy + Saturating> BondBreakdown<B> {
+    /// Returns the sum of all bonds.
+    pub fn total(&self) -> B {
+        self.advisory.saturating_add(self.validity).saturating_add(self.oracle)
     }
 }
 
@@ -72,6 +150,37 @@ pub enum MarketCreation {
     Advised,
 }
 
+/// The optional parameters of a new market. Every parameter that is `None` falls back to its
+/// default.
+///
+/// * `oracle_bond`: A higher bond than `OracleBond` to reserve from the creator.
+/// * `validity_bond`: A higher bond than `ValidityBond` to reserve from the creator of a
+///     permissionless market. Ignored for advised markets.
+/// * `outcome_descriptors`: An [`OutcomeDescriptor`] for each category. Categorical markets
+///     only.
+/// * `max_disputes`: A lower number of disputes than `MaxDisputes` to allow.
+/// * `auto_deploy_pool`: The weights of a pool that is deployed as soon as the market is active.
+/// * `oracle_grace_period`: The number of blocks the oracle can report exclusively, instead of
+///     `OracleGracePeriod`.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Default,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Encode,
+    parity_scale_codec::Decode,
+    sp_runtime::RuntimeDebug,
+)]
+pub struct MarketCreationOptions<B> {
+    pub oracle_bond: Option<B>,
+    pub validity_bond: Option<B>,
+    pub outcome_descriptors: Option<Vec<OutcomeDescriptor>>,
+    pub max_disputes: Option<u32>,
+    pub auto_deploy_pool: Option<Vec<u128>>,
+    pub oracle_grace_period: Option<u32>,
+}
+
 #[derive(
     scale_info::TypeInfo,
     Clone,
@@ -127,6 +236,54 @@ pub enum MarketPeriod<BN, M> {
     Timestamp(Range<M>),
 }
 
+/// A point in time given in the same unit as the `MarketPeriod` of a market.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum MarketEnd<BN, M> {
+    Block(BN),
+    Timestamp(M),
+}
+
+/// Restricts the kinds of `MarketEnd` (and therefore `MarketPeriod`) markets may be created with.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum MarketEndKinds {
+    /// Markets may end at a block or at a timestamp.
+    Any,
+    /// Markets may only end at a block.
+    BlocksOnly,
+    /// Markets may only end at a timestamp.
+    TimestampsOnly,
+}
+
+impl MarketEndKinds {
+    /// Returns `true` if a market with the given `period` may be created.
+    pub fn allows<BN, M>(&self, period: &MarketPeriod<BN, M>) -> bool {
+        matches!(
+            (self, period),
+            (MarketEndKinds::Any, _)
+                | (MarketEndKinds::BlocksOnly, MarketPeriod::Block(_))
+                | (MarketEndKinds::TimestampsOnly, MarketPeriod::Timestamp(_))
+        )
+    }
+}
+
 /// Defines the state of the market.
 #[derive(
     scale_info::TypeInfo,
@@ -159,6 +316,9 @@ pub enum MarketStatus {
     /// The market outcome has been resolved and can be cleaned up
     /// after the `MarketWipeDelay`.
     Resolved,
+    /// The automatic resolution of the market failed too often. The market waits for an admin
+    /// to resolve or destroy it.
+    ResolutionStalled,
 }
 
 /// Defines the type of market.
@@ -179,6 +339,48 @@ pub enum MarketType {
     Scalar(RangeInclusive<u128>),
 }
 
+impl MarketType {
+    /// Returns the number of outcome assets of a market of this type: the number of categories
+    /// for categorical markets and two (long and short) for scalar markets.
+    pub fn outcome_count(&self) -> u16 {
+        match self {
+            MarketType::Categorical(categories) => *categories,
+            MarketType::Scalar(_) => 2,
+        }
+    }
+
+    /// Returns the kind of a market of this type. Categorical markets with exactly two
+    /// categories are binary.
+    pub fn kind(&self) -> MarketKind {
+        match self {
+            MarketType::Categorical(2) => MarketKind::Binary,
+            MarketType::Categorical(_) => MarketKind::Categorical,
+            MarketType::Scalar(_) => MarketKind::Scalar,
+        }
+    }
+}
+
+/// The kind of a market, set on creation. Allows to tell binary yes/no markets apart from other
+/// categorical markets without inspecting the market type.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum MarketKind {
+    /// A categorical market with the two outcomes yes and no.
+    Binary,
+    /// A categorical market with any other number of outcomes.
+    Categorical,
+    /// A scalar market.
+    Scalar,
+}
+
 #[derive(
     scale_info::TypeInfo,
     Clone,
@@ -191,6 +393,69 @@ pub struct Report<AccountId, BlockNumber> {
     pub at: BlockNumber,
     pub by: AccountId,
     pub outcome: OutcomeReport,
+    /// Whether `by` was the oracle of the market at the time of the report.
+    pub by_oracle: bool,
+}
+
+/// Defines how the outcome of a disputed categorical market is determined.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum DisputeResolutionRule {
+    /// The outcome chosen by the dispute mechanism is used as is.
+    LastDispute,
+    /// The disputed outcome with the highest total dispute bond is used. Ties are broken in favor
+    /// of the outcome that was disputed most recently.
+    HighestBondedOutcome,
+}
+
+/// Defines how the outcome of a disputed scalar market is determined.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum ScalarResolutionPolicy {
+    /// The outcome chosen by the dispute mechanism is used as is.
+    LastDispute,
+    /// The median of the reported and all disputed values is used.
+    Median,
+}
+
+/// Restricts how far each dispute of a scalar market may move its value, starting from the
+/// reported value.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum ScalarDisputeStep {
+    /// Disputes may propose any value in the range of the market.
+    Unrestricted,
+    /// Disputes may only raise the value of the report or the previous dispute.
+    Increasing,
+    /// Disputes may only lower the value of the report or the previous dispute.
+    Decreasing,
+    /// Disputes may move the value of the report or the previous dispute by at most the given
+    /// distance in either direction.
+    MaxDelta(u128),
 }
 
 /// Contains a market id and the market period.
@@ -213,3 +478,24 @@ pub struct SubsidyUntil<BN, MO, MI> {
     /// Market start and end.
     pub period: MarketPeriod<BN, MO>,
 }
+
+/// Defines what happens to the validity bond of a permissionless market once it's resolved.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    sp_runtime::RuntimeDebug,
+)]
+pub enum ValidityBondPolicy {
+    /// The bond is returned unless the market resolves to `OutcomeReport::Invalid`, in which
+    /// case it's slashed.
+    ReturnIfValid,
+    /// The bond is always slashed.
+    AlwaysBurn,
+    /// The bond is always returned.
+    AlwaysReturn,
+}
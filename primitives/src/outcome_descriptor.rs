@@ -0,0 +1,63 @@
+use crate::types::BoundedMetadata;
+
+/// Describes an outcome of a categorical market in a format that clients can rely on.
+#[derive(
+    scale_info::TypeInfo,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+)]
+pub enum OutcomeDescriptor {
+    /// The binary representation of an IPFS CID (v0 or v1) of the outcome's description.
+    Cid(BoundedMetadata),
+    /// A short UTF-8 encoded label.
+    Label(BoundedMetadata),
+    /// A number, e.g. the lower bound of a bucket.
+    Numeric(u128),
+}
+
+impl OutcomeDescriptor {
+    /// Returns the bytes that are stored for the descriptor. Empty for numeric descriptors.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Cid(bytes) | Self::Label(bytes) => bytes.as_slice(),
+            Self::Numeric(_) => &[],
+        }
+    }
+
+    /// Returns whether the descriptor is well-formed. Labels must be non-empty, valid UTF-8 and
+    /// at most `max_label_len` bytes long.
+    pub fn is_valid(&self, max_label_len: usize) -> bool {
+        match self {
+            Self::Cid(cid) => is_cid_v0(cid) || is_cid_v1(cid),
+            Self::Label(label) => {
+                !label.is_empty()
+                    && label.len() <= max_label_len
+                    && core::str::from_utf8(label).is_ok()
+            }
+            Self::Numeric(_) => true,
+        }
+    }
+}
+
+// A CIDv0 is a bare sha2-256 multihash.
+fn is_cid_v0(cid: &[u8]) -> bool {
+    cid.len() == 34 && cid.starts_with(&[0x12, 0x20])
+}
+
+// A CIDv1 is the version, the content codec and a multihash. Only single byte codecs and hash
+// functions are supported.
+fn is_cid_v1(cid: &[u8]) -> bool {
+    match cid {
+        [0x01, codec, hash_fn, digest_len, digest @ ..] => {
+            *codec < 0x80
+                && *hash_fn < 0x80
+                && *digest_len > 0
+                && digest.len() == usize::from(*digest_len)
+        }
+        _ => false,
+    }
+}
@@ -15,4 +15,7 @@ use crate::types::CategoryIndex;
 pub enum OutcomeReport {
     Categorical(CategoryIndex),
     Scalar(u128),
+    /// The market can't be resolved. Every outcome share is redeemed for an equal fraction of
+    /// a unit of collateral, which refunds all complete sets.
+    Invalid,
 }
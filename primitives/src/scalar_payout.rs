@@ -0,0 +1,49 @@
+use core::ops::RangeInclusive;
+use sp_runtime::Perbill;
+
+/// Returns the share of the collateral that is paid out for each `Long` and `Short` share if a
+/// scalar market with the bounds `bounds` resolves to `value`.
+pub fn scalar_payout_percents(value: u128, bounds: &RangeInclusive<u128>) -> (Perbill, Perbill) {
+    let (low, high) = (*bounds.start(), *bounds.end());
+    if value <= low {
+        return (Perbill::zero(), Perbill::one());
+    }
+    if value >= high {
+        return (Perbill::one(), Perbill::zero());
+    }
+
+    let payout_long: Perbill =
+        Perbill::from_rational(value.saturating_sub(low), high.saturating_sub(low));
+    let payout_short: Perbill =
+        Perbill::from_parts(Perbill::one().deconstruct().saturating_sub(payout_long.deconstruct()));
+    (payout_long, payout_short)
+}
+
+/// Returns the collateral that is paid out for `long_amount` `Long` shares and `short_amount`
+/// `Short` shares if a scalar market with the bounds `bounds` resolves to `value`. Both payouts
+/// are rounded down.
+pub fn scalar_payout(
+    value: u128,
+    bounds: &RangeInclusive<u128>,
+    long_amount: u128,
+    short_amount: u128,
+) -> (u128, u128) {
+    let (long_percent, short_percent) = scalar_payout_percents(value, bounds);
+    (long_percent.mul_floor(long_amount), short_percent.mul_floor(short_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_payout_rounds_down_between_the_bounds() {
+        let bounds = 10..=40;
+        assert_eq!(scalar_payout(5, &bounds, 100, 100), (0, 100));
+        assert_eq!(scalar_payout(10, &bounds, 100, 100), (0, 100));
+        assert_eq!(scalar_payout(25, &bounds, 100, 60), (50, 30));
+        assert_eq!(scalar_payout(20, &bounds, 100, 100), (33, 66));
+        assert_eq!(scalar_payout(40, &bounds, 100, 100), (100, 0));
+        assert_eq!(scalar_payout(50, &bounds, u128::MAX, 100), (u128::MAX, 0));
+    }
+}
@@ -93,6 +93,12 @@ pub trait Swaps<AccountId> {
     /// Returns the pool instance of a corresponding `pool_id`.
     fn pool(pool_id: PoolId) -> Result<Pool<Self::Balance, Self::MarketId>, DispatchError>;
 
+    /// Returns `true` if `pool_id` exists and `asset` is one of its assets.
+    fn pool_contains_asset(pool_id: PoolId, asset: &Asset<Self::MarketId>) -> bool;
+
+    /// Returns `true` if `pool_id` exists.
+    fn pool_exists(pool_id: PoolId) -> bool;
+
     /// Pool will be marked as `PoolStatus::Stale`. If market is categorical, removes everything
     /// that is not ZTG or winning assets from the selected pool. Additionally, it distributes
     /// the rewards to all pool share holders.
@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 use orml_tokens::{AccountData, Accounts, TotalIssuance};
 use orml_traits::currency::MultiReservableCurrency;
+use sp_runtime::traits::{Saturating, Zero};
 
 /// Custom `MultiReservableCurrency` trait.
 pub trait ZeitgeistMultiReservableCurrency<AccountId>: MultiReservableCurrency<AccountId> {
@@ -13,6 +14,16 @@ pub trait ZeitgeistMultiReservableCurrency<AccountId>: MultiReservableCurrency<A
     fn destroy_all<I>(currency_id: Self::CurrencyId, accounts: I)
     where
         I: Iterator<Item = (AccountId, AccountData<Self::Balance>)>;
+
+    /// Returns the sum of the total balances (free and reserved) of `who` in `currency_ids`.
+    fn total_balance_of_assets(
+        currency_ids: &[Self::CurrencyId],
+        who: &AccountId,
+    ) -> Self::Balance {
+        currency_ids.iter().fold(Zero::zero(), |acc: Self::Balance, currency_id| {
+            acc.saturating_add(Self::total_balance(*currency_id, who))
+        })
+    }
 }
 
 impl<T> ZeitgeistMultiReservableCurrency<T::AccountId> for orml_tokens::Pallet<T>
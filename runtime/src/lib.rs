@@ -606,29 +606,60 @@ impl zrml_market_commons::Config for Runtime {
 
 impl zrml_prediction_markets::Config for Runtime {
     type AdvisoryBond = AdvisoryBond;
+    type AdvisoryReviewPeriod = AdvisoryReviewPeriod;
+    type AllowOracleSelfDispute = AllowOracleSelfDispute;
+    type AllowPublicReporting = AllowPublicReporting;
+    type AllowedMarketEndKinds = AllowedMarketEndKinds;
     type ApprovalOrigin = EnsureRootOrMoreThanHalfOfAdvisoryCommittee;
     type Authorized = Authorized;
+    type CancellationPenalty = CancellationPenalty;
+    type CompleteSetMintFee = CompleteSetMintFee;
     type Court = Court;
     type DisputeBond = DisputeBond;
     type DisputeFactor = DisputeFactor;
     type DisputePeriod = DisputePeriod;
+    type DisputeResolutionRule = DisputeResolution;
     type Event = Event;
+    type GlobalDisputePeriod = GlobalDisputePeriod;
     type LiquidityMining = LiquidityMining;
     type MarketCommons = MarketCommons;
+    type MaxAdditionalMetadata = MaxAdditionalMetadata;
     type MaxCategories = MaxCategories;
+    type MaxConcurrentDisputedMarkets = MaxConcurrentDisputedMarkets;
+    type MaxDestroyedShareAccounts = MaxDestroyedShareAccounts;
     type MaxDisputes = MaxDisputes;
+    type MaxDisputesPerAccount = MaxDisputesPerAccount;
+    type MaxExpiredProposalsPerBlock = MaxExpiredProposalsPerBlock;
+    type MaxOutcomeLabelLength = MaxOutcomeLabelLength;
+    type MaxPoolDeploymentsPerBatch = MaxPoolDeploymentsPerBatch;
+    type MaxResolutionFailures = MaxResolutionFailures;
     type MaxSubsidyPeriod = MaxSubsidyPeriod;
+    type MaxTotalMetadataBytes = MaxTotalMetadataBytes;
+    type MillisecondsPerBlock = MillisecondsPerBlock;
     type MinCategories = MinCategories;
+    type MinEffectiveDisputeBond = MinEffectiveDisputeBond;
+    type MinGlobalDisputeInitialStake = MinGlobalDisputeInitialStake;
+    type MinGlobalDisputeStake = MinGlobalDisputeStake;
     type MinSubsidyPeriod = MinSubsidyPeriod;
+    type MonotonicScalarDisputes = MonotonicScalarDisputes;
     type OracleBond = OracleBond;
+    type OracleGracePeriod = OracleGracePeriod;
     type PalletId = PmPalletId;
+    type PoolWeightsSum = PoolWeightsSum;
+    type ProRataRedemption = ProRataRedemption;
+    type RedemptionTreasuryFee = RedemptionTreasuryFee;
     type ReportingPeriod = ReportingPeriod;
+    type ResolutionReward = ResolutionReward;
+    type ScalarResolutionPolicy = ScalarResolution;
     type Shares = Tokens;
     type SimpleDisputes = SimpleDisputes;
     type Slash = ();
+    type SlashSplit = SlashSplit;
     type Swaps = Swaps;
     type ValidityBond = ValidityBond;
+    type ValidityBondPolicy = ValidityBondSettlement;
     type WeightInfo = zrml_prediction_markets::weights::WeightInfo<Runtime>;
+    type ZeroSupplyRefund = ZeroSupplyRefund;
 }
 
 impl zrml_rikiddo::Config<RikiddoSigmoidFeeMarketVolumeEma> for Runtime {
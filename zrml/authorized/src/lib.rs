@@ -192,7 +192,11 @@ where
         creation: zeitgeist_primitives::types::MarketCreation::Permissionless,
         creator_fee: 0,
         creator: T::AccountId::default(),
+        created_at: Default::default(),
+        created_at_timestamp: Default::default(),
+        kind: zeitgeist_primitives::types::MarketKind::Scalar,
         market_type: zeitgeist_primitives::types::MarketType::Scalar(0..=100),
+        max_disputes: None,
         mdm: zeitgeist_primitives::types::MarketDisputeMechanism::Authorized(ai),
         metadata: Default::default(),
         oracle: T::AccountId::default(),
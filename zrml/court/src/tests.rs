@@ -17,25 +17,31 @@ use zeitgeist_primitives::{
     constants::BASE,
     traits::DisputeApi,
     types::{
-        Market, MarketCreation, MarketDisputeMechanism, MarketPeriod, MarketStatus, MarketType,
-        OutcomeReport, ScoringRule,
+        Market, MarketCreation, MarketDisputeMechanism, MarketKind, MarketPeriod, MarketStatus,
+        MarketType, OutcomeReport, ScoringRule,
     },
 };
 
-const DEFAULT_MARKET: Market<u128, u64, u64> = Market {
-    creation: MarketCreation::Permissionless,
-    creator_fee: 0,
-    creator: 0,
-    market_type: MarketType::Scalar(0..=100),
-    mdm: MarketDisputeMechanism::Court,
-    metadata: vec![],
-    oracle: 0,
-    period: MarketPeriod::Block(0..100),
-    report: None,
-    resolved_outcome: None,
-    status: MarketStatus::Closed,
-    scoring_rule: ScoringRule::CPMM,
-};
+fn default_market() -> Market<u128, u64, u64> {
+    Market {
+        creation: MarketCreation::Permissionless,
+        creator_fee: 0,
+        creator: 0,
+        created_at: 0,
+        created_at_timestamp: 0,
+        kind: MarketKind::Scalar,
+        market_type: MarketType::Scalar(0..=100),
+        max_disputes: None,
+        mdm: MarketDisputeMechanism::Court,
+        metadata: Default::default(),
+        oracle: 0,
+        period: MarketPeriod::Block(0..100),
+        report: None,
+        resolved_outcome: None,
+        status: MarketStatus::Closed,
+        scoring_rule: ScoringRule::CPMM,
+    }
+}
 const DEFAULT_SET_OF_JURORS: &[(u128, Juror)] = &[
     (7, Juror { status: JurorStatus::Ok }),
     (6, Juror { status: JurorStatus::Tardy }),
@@ -110,7 +116,7 @@ fn join_court_will_not_insert_an_already_stored_juror() {
 #[test]
 fn on_dispute_denies_non_court_markets() {
     ExtBuilder::default().build().execute_with(|| {
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.mdm = MarketDisputeMechanism::SimpleDisputes;
         assert_noop!(
             Court::on_dispute(&[], &0, &market),
@@ -122,7 +128,7 @@ fn on_dispute_denies_non_court_markets() {
 #[test]
 fn on_resolution_denies_non_court_markets() {
     ExtBuilder::default().build().execute_with(|| {
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.mdm = MarketDisputeMechanism::SimpleDisputes;
         assert_noop!(
             Court::on_resolution(&[], &0, &market),
@@ -137,7 +143,7 @@ fn on_dispute_stores_jurors_that_should_vote() {
         setup_blocks(1..123);
         let _ = Court::join_court(Origin::signed(ALICE));
         let _ = Court::join_court(Origin::signed(BOB));
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
         assert_noop!(
             Court::join_court(Origin::signed(ALICE)),
             Error::<Runtime>::JurorAlreadyExists
@@ -154,11 +160,11 @@ fn on_resolution_awards_winners_and_slashes_losers() {
         Court::join_court(Origin::signed(ALICE)).unwrap();
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::join_court(Origin::signed(CHARLIE)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(BOB), 0, OutcomeReport::Scalar(2)).unwrap();
         Court::vote(Origin::signed(CHARLIE), 0, OutcomeReport::Scalar(3)).unwrap();
-        let _ = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        let _ = Court::on_resolution(&[], &0, &default_market()).unwrap();
         assert_eq!(Balances::free_balance(ALICE), 998 * BASE + 3 * BASE);
         assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &ALICE), 2 * BASE);
         assert_eq!(Balances::free_balance(BOB), 996 * BASE);
@@ -175,11 +181,11 @@ fn on_resolution_decides_market_outcome_based_on_the_majority() {
         Court::join_court(Origin::signed(ALICE)).unwrap();
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::join_court(Origin::signed(CHARLIE)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(BOB), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(CHARLIE), 0, OutcomeReport::Scalar(2)).unwrap();
-        let outcome = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        let outcome = Court::on_resolution(&[], &0, &default_market()).unwrap();
         assert_eq!(outcome, OutcomeReport::Scalar(1))
     });
 }
@@ -191,8 +197,8 @@ fn on_resolution_sets_late_jurors_as_tardy() {
         Court::join_court(Origin::signed(ALICE)).unwrap();
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
-        let _ = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
+        let _ = Court::on_resolution(&[], &0, &default_market()).unwrap();
         assert_eq!(Jurors::<Runtime>::get(ALICE).unwrap().status, JurorStatus::Ok);
         assert_eq!(Jurors::<Runtime>::get(BOB).unwrap().status, JurorStatus::Tardy);
     });
@@ -205,11 +211,11 @@ fn on_resolution_sets_jurors_that_voted_on_the_second_most_voted_outcome_as_tard
         Court::join_court(Origin::signed(ALICE)).unwrap();
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::join_court(Origin::signed(CHARLIE)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(BOB), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(CHARLIE), 0, OutcomeReport::Scalar(2)).unwrap();
-        let _ = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        let _ = Court::on_resolution(&[], &0, &default_market()).unwrap();
         assert_eq!(Jurors::<Runtime>::get(CHARLIE).unwrap().status, JurorStatus::Tardy);
     });
 }
@@ -222,8 +228,8 @@ fn on_resolution_punishes_tardy_jurors_that_failed_to_vote_a_second_time() {
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::set_stored_juror_as_tardy(&BOB).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
-        let _ = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
+        let _ = Court::on_resolution(&[], &0, &default_market()).unwrap();
         let join_court_stake = 40000000000;
         let slash = join_court_stake / 5;
         assert_eq!(Balances::free_balance(Court::treasury_account_id()), INITIAL_BALANCE + slash);
@@ -239,11 +245,11 @@ fn on_resolution_removes_requested_jurors_and_votes() {
         Court::join_court(Origin::signed(ALICE)).unwrap();
         Court::join_court(Origin::signed(BOB)).unwrap();
         Court::join_court(Origin::signed(CHARLIE)).unwrap();
-        Court::on_dispute(&[], &0, &DEFAULT_MARKET).unwrap();
+        Court::on_dispute(&[], &0, &default_market()).unwrap();
         Court::vote(Origin::signed(ALICE), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(BOB), 0, OutcomeReport::Scalar(1)).unwrap();
         Court::vote(Origin::signed(CHARLIE), 0, OutcomeReport::Scalar(2)).unwrap();
-        let _ = Court::on_resolution(&[], &0, &DEFAULT_MARKET).unwrap();
+        let _ = Court::on_resolution(&[], &0, &default_market()).unwrap();
         assert_eq!(RequestedJurors::<Runtime>::iter().count(), 0);
         assert_eq!(Votes::<Runtime>::iter().count(), 0);
     });
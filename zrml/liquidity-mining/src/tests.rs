@@ -14,8 +14,8 @@ use frame_support::{
 };
 use frame_system::RawOrigin;
 use zeitgeist_primitives::types::{
-    Market, MarketCreation, MarketDisputeMechanism, MarketPeriod, MarketStatus, MarketType,
-    ScoringRule,
+    Market, MarketCreation, MarketDisputeMechanism, MarketKind, MarketPeriod, MarketStatus,
+    MarketType, ScoringRule,
 };
 use zrml_market_commons::Markets;
 
@@ -188,9 +188,13 @@ fn create_default_market(market_id: u128, period: Range<u64>) {
             creation: MarketCreation::Permissionless,
             creator_fee: 0,
             creator: 0,
+            created_at: 0,
+            created_at_timestamp: 0,
+            kind: MarketKind::Categorical,
             market_type: MarketType::Categorical(0),
+            max_disputes: None,
             mdm: MarketDisputeMechanism::SimpleDisputes,
-            metadata: vec![],
+            metadata: Default::default(),
             oracle: 0,
             period: MarketPeriod::Block(period),
             report: None,
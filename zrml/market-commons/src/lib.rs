@@ -8,6 +8,7 @@
 extern crate alloc;
 
 mod market_commons_pallet_api;
+mod migrations;
 
 pub use market_commons_pallet_api::MarketCommonsPalletApi;
 pub use pallet::*;
@@ -20,7 +21,8 @@ mod pallet {
     use frame_support::{
         dispatch::DispatchResult,
         pallet_prelude::{StorageMap, StorageValue, ValueQuery},
-        traits::{Hooks, NamedReservableCurrency, Time},
+        traits::{Hooks, NamedReservableCurrency, StorageVersion, Time},
+        weights::Weight,
         Blake2_128Concat, Parameter,
     };
     use sp_runtime::{
@@ -29,7 +31,9 @@ mod pallet {
     };
     use zeitgeist_primitives::types::{Market, PoolId, Report};
 
-    type MomentOf<T> = <<T as Config>::Timestamp as frame_support::traits::Time>::Moment;
+    pub(crate) type MomentOf<T> = <<T as Config>::Timestamp as frame_support::traits::Time>::Moment;
+
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {}
@@ -67,9 +71,16 @@ mod pallet {
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::add_creation_to_markets::<T>()
+                .saturating_add(crate::migrations::add_by_oracle_to_reports::<T>())
+                .saturating_add(crate::migrations::add_kind_to_markets::<T>())
+        }
+    }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(PhantomData<T>);
 
     impl<T> Pallet<T>
@@ -178,6 +189,10 @@ mod pallet {
                 .map_err(|_err| Error::<T>::MarketPoolDoesNotExist.into())
         }
 
+        fn market_pools() -> Vec<(Self::MarketId, PoolId)> {
+            <MarketPool<T>>::iter().collect()
+        }
+
         // Etc
 
         fn now() -> Self::Moment {
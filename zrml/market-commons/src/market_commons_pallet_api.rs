@@ -60,6 +60,9 @@ pub trait MarketCommonsPalletApi {
     /// Fetches the pool id associated with a given `market_id`.
     fn market_pool(market_id: &Self::MarketId) -> Result<PoolId, DispatchError>;
 
+    /// All stored market pools
+    fn market_pools() -> Vec<(Self::MarketId, PoolId)>;
+
     // Etc
 
     /// Returns the current UTC time (milliseconds)
@@ -0,0 +1,198 @@
+use crate::{Config, Markets, MomentOf, Pallet, STORAGE_VERSION};
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+use zeitgeist_primitives::types::{
+    BoundedMetadata, Market, MarketCreation, MarketDisputeMechanism, MarketPeriod, MarketStatus,
+    MarketType, OutcomeReport, Report, ScoringRule,
+};
+
+// The layout of `Report` before `by_oracle` was added.
+#[derive(parity_scale_codec::Decode, parity_scale_codec::Encode)]
+struct ReportWithoutOracleFlag<AI, BN> {
+    at: BN,
+    by: AI,
+    outcome: OutcomeReport,
+}
+
+// The layout of `Market` before `max_disputes`, `created_at` and `created_at_timestamp` were
+// added.
+#[derive(parity_scale_codec::Decode, parity_scale_codec::Encode)]
+struct MarketWithoutCreation<AI, BN, M> {
+    creator: AI,
+    creation: MarketCreation,
+    creator_fee: u8,
+    oracle: AI,
+    metadata: BoundedMetadata,
+    market_type: MarketType,
+    period: MarketPeriod<BN, M>,
+    scoring_rule: ScoringRule,
+    status: MarketStatus,
+    report: Option<ReportWithoutOracleFlag<AI, BN>>,
+    resolved_outcome: Option<OutcomeReport>,
+    mdm: MarketDisputeMechanism<AI>,
+}
+
+// The layout of `Market` before `Report::by_oracle` was added.
+#[derive(parity_scale_codec::Decode, parity_scale_codec::Encode)]
+struct MarketWithoutOracleFlag<AI, BN, M> {
+    creator: AI,
+    creation: MarketCreation,
+    creator_fee: u8,
+    oracle: AI,
+    metadata: BoundedMetadata,
+    market_type: MarketType,
+    period: MarketPeriod<BN, M>,
+    scoring_rule: ScoringRule,
+    status: MarketStatus,
+    report: Option<ReportWithoutOracleFlag<AI, BN>>,
+    resolved_outcome: Option<OutcomeReport>,
+    mdm: MarketDisputeMechanism<AI>,
+    max_disputes: Option<u32>,
+    created_at: BN,
+    created_at_timestamp: M,
+}
+
+// The layout of `Market` before `kind` was added.
+#[derive(parity_scale_codec::Decode, parity_scale_codec::Encode)]
+struct MarketWithoutKind<AI, BN, M> {
+    creator: AI,
+    creation: MarketCreation,
+    creator_fee: u8,
+    oracle: AI,
+    metadata: BoundedMetadata,
+    market_type: MarketType,
+    period: MarketPeriod<BN, M>,
+    scoring_rule: ScoringRule,
+    status: MarketStatus,
+    report: Option<Report<AI, BN>>,
+    resolved_outcome: Option<OutcomeReport>,
+    mdm: MarketDisputeMechanism<AI>,
+    max_disputes: Option<u32>,
+    created_at: BN,
+    created_at_timestamp: M,
+}
+
+/// Adds `max_disputes`, `created_at` and `created_at_timestamp` to all stored markets. Existing
+/// markets fall back to the global `MaxDisputes`. Their creation is unknown, so it is set to zero.
+///
+/// Markets are translated straight into the current layout, so the storage version is set to
+/// `STORAGE_VERSION` and all later migrations are skipped afterwards.
+pub(crate) fn add_creation_to_markets<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let mut translated: Weight = 0;
+    Markets::<T>::translate::<MarketWithoutCreation<T::AccountId, T::BlockNumber, MomentOf<T>>, _>(
+        |_, old| {
+            translated = translated.saturating_add(1);
+            let report = old.report.map(|report| with_oracle_flag(report, &old.oracle));
+            let kind = old.market_type.kind();
+            Some(Market {
+                creator: old.creator,
+                creation: old.creation,
+                creator_fee: old.creator_fee,
+                oracle: old.oracle,
+                metadata: old.metadata,
+                market_type: old.market_type,
+                period: old.period,
+                scoring_rule: old.scoring_rule,
+                status: old.status,
+                report,
+                resolved_outcome: old.resolved_outcome,
+                mdm: old.mdm,
+                max_disputes: None,
+                created_at: Default::default(),
+                created_at_timestamp: Default::default(),
+                kind,
+            })
+        },
+    );
+    STORAGE_VERSION.put::<Pallet<T>>();
+    T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+}
+
+/// Adds `by_oracle` to the reports of all stored markets. The oracle of a market can't have been
+/// reassigned before this migration, so the flag is derived from the current oracle.
+///
+/// Like `add_creation_to_markets`, markets are translated straight into the current layout.
+pub(crate) fn add_by_oracle_to_reports<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 2 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let mut translated: Weight = 0;
+    Markets::<T>::translate::<MarketWithoutOracleFlag<T::AccountId, T::BlockNumber, MomentOf<T>>, _>(
+        |_, old| {
+            translated = translated.saturating_add(1);
+            let report = old.report.map(|report| with_oracle_flag(report, &old.oracle));
+            let kind = old.market_type.kind();
+            Some(Market {
+                creator: old.creator,
+                creation: old.creation,
+                creator_fee: old.creator_fee,
+                oracle: old.oracle,
+                metadata: old.metadata,
+                market_type: old.market_type,
+                period: old.period,
+                scoring_rule: old.scoring_rule,
+                status: old.status,
+                report,
+                resolved_outcome: old.resolved_outcome,
+                mdm: old.mdm,
+                max_disputes: old.max_disputes,
+                created_at: old.created_at,
+                created_at_timestamp: old.created_at_timestamp,
+                kind,
+            })
+        },
+    );
+    STORAGE_VERSION.put::<Pallet<T>>();
+    T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+}
+
+/// Adds `kind` to all stored markets. Categorical markets with exactly two categories are
+/// considered binary.
+pub(crate) fn add_kind_to_markets<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 3 {
+        return T::DbWeight::get().reads(1);
+    }
+
+    let mut translated: Weight = 0;
+    Markets::<T>::translate::<MarketWithoutKind<T::AccountId, T::BlockNumber, MomentOf<T>>, _>(
+        |_, old| {
+            translated = translated.saturating_add(1);
+            let kind = old.market_type.kind();
+            Some(Market {
+                creator: old.creator,
+                creation: old.creation,
+                creator_fee: old.creator_fee,
+                oracle: old.oracle,
+                metadata: old.metadata,
+                market_type: old.market_type,
+                period: old.period,
+                scoring_rule: old.scoring_rule,
+                status: old.status,
+                report: old.report,
+                resolved_outcome: old.resolved_outcome,
+                mdm: old.mdm,
+                max_disputes: old.max_disputes,
+                created_at: old.created_at,
+                created_at_timestamp: old.created_at_timestamp,
+                kind,
+            })
+        },
+    );
+    StorageVersion::new(3).put::<Pallet<T>>();
+    T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+}
+
+fn with_oracle_flag<AI, BN>(report: ReportWithoutOracleFlag<AI, BN>, oracle: &AI) -> Report<AI, BN>
+where
+    AI: PartialEq,
+{
+    let by_oracle = &report.by == oracle;
+    Report { at: report.at, by: report.by, outcome: report.outcome, by_oracle }
+}
@@ -24,6 +24,7 @@ fuzz_target!(|data: Data| {
             data.create_scalar_market_outcome_range,
             market_dispute_mechanism(data.create_scalar_market_mdm),
             ScoringRule::CPMM,
+            Default::default(),
         );
 
         let _ = PredictionMarkets::on_initialize(2);
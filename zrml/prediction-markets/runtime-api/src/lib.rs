@@ -3,13 +3,56 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use parity_scale_codec::Codec;
-use zeitgeist_primitives::types::Asset;
+use sp_std::vec::Vec;
+use zeitgeist_primitives::types::{
+    Asset, BondBreakdown, MarketView, OutcomeReport, PoolId, Report,
+};
 
 sp_api::decl_runtime_apis! {
-    pub trait PredictionMarketsApi<MarketId, Hash> where
+    /// Functions that query a market return `None` if the market doesn't exist or the query
+    /// doesn't apply to it.
+    ///
+    /// Version 2 added the `AccountId`, `Balance`, `BlockNumber` and `Moment` parameters and
+    /// every function except `market_outcome_share_id`.
+    #[api_version(2)]
+    pub trait PredictionMarketsApi<MarketId, Hash, Moment, Balance, AccountId, BlockNumber> where
         MarketId: Codec,
         Hash: Codec,
+        Moment: Codec,
+        Balance: Codec,
+        AccountId: Codec,
+        BlockNumber: Codec,
     {
+        fn all_market_pools() -> Vec<(MarketId, PoolId)>;
+
+        fn current_report(market_id: MarketId) -> Option<Report<AccountId, BlockNumber>>;
+
+        fn dispute_bond_for(market_id: MarketId) -> Option<Balance>;
+
+        fn dispute_preview(market_id: MarketId, outcome: OutcomeReport) -> Option<(Balance, Balance)>;
+
+        fn estimated_end_timestamp(market_id: MarketId) -> Option<Moment>;
+
+        fn market_bonds(market_id: MarketId) -> Option<BondBreakdown<Balance>>;
+
+        fn hypothetical_winnings(market_id: MarketId, who: AccountId, outcome: OutcomeReport) -> Option<Balance>;
+
+        fn markets_by_oracle(oracle: AccountId) -> Vec<MarketId>;
+
+        fn markets_ending_between(from: Moment, to: Moment) -> Vec<MarketId>;
+
+        fn market_pools_paged(start: MarketId, limit: u32) -> Vec<(MarketId, PoolId)>;
+
         fn market_outcome_share_id(market_id: MarketId, outcome: u16) -> Asset<MarketId>;
+
+        fn my_market_view(market_id: MarketId, who: AccountId) -> Option<MarketView<AccountId, BlockNumber, Moment, Balance>>;
+
+        fn outcome_payout_liability(market_id: MarketId, outcome: OutcomeReport) -> Option<Balance>;
+
+        fn outstanding_complete_sets(market_id: MarketId) -> Option<Balance>;
+
+        fn payout_table(market_id: MarketId) -> Option<Vec<(u16, Balance)>>;
+
+        fn scheduled_resolution_block(market_id: MarketId) -> Option<BlockNumber>;
     }
 }
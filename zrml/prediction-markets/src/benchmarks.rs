@@ -10,17 +10,18 @@ use crate::Pallet as PredictionMarket;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, vec, whitelisted_caller};
 use frame_support::{
     dispatch::UnfilteredDispatchable,
-    traits::{Currency, EnsureOrigin, Get, Hooks},
+    traits::{Currency, EnsureOrigin, Get, Hooks, NamedReservableCurrency},
 };
 use frame_system::RawOrigin;
 use orml_traits::MultiCurrency;
 use sp_runtime::traits::{One, SaturatedConversion, Zero};
 use zeitgeist_primitives::{
     constants::{MinLiquidity, MinWeight, BASE},
-    traits::DisputeApi,
+    traits::{DisputeApi, Swaps},
     types::{
-        Asset, MarketCreation, MarketDisputeMechanism, MarketPeriod, MarketStatus, MarketType,
-        MaxRuntimeUsize, MultiHash, OutcomeReport, ScalarPosition, ScoringRule, SubsidyUntil,
+        Asset, MarketCreation, MarketDispute, MarketDisputeMechanism, MarketPeriod, MarketStatus,
+        MarketType, MaxRuntimeUsize, MultiHash, OutcomeReport, ScalarPosition, ScoringRule,
+        SubsidyUntil,
     },
 };
 use zrml_market_commons::MarketCommonsPalletApi;
@@ -68,6 +69,7 @@ fn create_market_common<T: Config>(
             categories,
             mdm: MarketDisputeMechanism::SimpleDisputes,
             scoring_rule,
+            options: Default::default(),
         }
         .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
     } else if let MarketType::Scalar(range) = options {
@@ -79,6 +81,7 @@ fn create_market_common<T: Config>(
             outcome_range: range,
             mdm: MarketDisputeMechanism::SimpleDisputes,
             scoring_rule,
+            options: Default::default(),
         }
         .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
     } else {
@@ -102,6 +105,31 @@ fn create_close_and_report_market<T: Config>(
     Ok((caller, market_id))
 }
 
+// Setup a scalar market that was disputed `MaxDisputes` times, i.e. a global dispute can be
+// started
+fn setup_global_dispute_common<T: Config>() -> Result<(T::AccountId, MarketIdOf<T>), &'static str> {
+    let (caller, market_id) = create_close_and_report_market::<T>(
+        MarketCreation::Permissionless,
+        MarketType::Scalar(0u128..=u128::MAX),
+        OutcomeReport::Scalar(42),
+    )?;
+    let at = frame_system::Pallet::<T>::block_number();
+    let disputes = (0..T::MaxDisputes::get())
+        .map(|i| MarketDispute {
+            at,
+            by: account("Disputant", i, 0),
+            outcome: OutcomeReport::Scalar(i.into()),
+        })
+        .collect();
+    crate::Disputes::<T>::insert(market_id, disputes);
+    crate::MarketIdsPerDisputeBlock::<T>::mutate(at, |ids| ids.push(market_id));
+    T::MarketCommons::mutate_market(&market_id, |market| {
+        market.status = MarketStatus::Disputed;
+        Ok(())
+    })?;
+    Ok((caller, market_id))
+}
+
 // Generates `acc_total` accounts, of which `acc_asset` account do own `asset`
 fn generate_accounts_with_assets<T: Config>(
     acc_total: u32,
@@ -243,6 +271,51 @@ benchmarks! {
         let call = Call::<T>::admin_destroy_market { market_id };
     }: { call.dispatch_bypass_filter(approval_origin)? }
 
+    admin_extend_review {
+        let (_, market_id) = create_market_common::<T>(
+            MarketCreation::Advised,
+            MarketType::Categorical(T::MaxCategories::get()),
+            ScoringRule::CPMM
+        )?;
+        let deadline = crate::ReviewDeadlines::<T>::get(&market_id).ok_or("no review deadline")?;
+        let approval_origin = T::ApprovalOrigin::successful_origin();
+        let call = Call::<T>::admin_extend_review {
+            market_id,
+            new_deadline: deadline + T::BlockNumber::one(),
+        };
+    }: { call.dispatch_bypass_filter(approval_origin)? }
+
+    admin_migrate_pool {
+        let a in (T::MinCategories::get().into())..T::MaxCategories::get().into();
+        let (caller, market_id) = create_market_common::<T>(
+            MarketCreation::Permissionless,
+            MarketType::Categorical(a.saturated_into()),
+            ScoringRule::CPMM
+        )?;
+        let min_liquidity: BalanceOf::<T> = MinLiquidity::get().saturated_into();
+        let _ = Call::<T>::buy_complete_set { market_id, amount: min_liquidity * 2u32.into() }
+            .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+
+        let weight_len: usize = MaxRuntimeUsize::from(a).into();
+        let weights = vec![MinWeight::get(); weight_len.saturating_add(1)];
+        let _ = Call::<T>::deploy_swap_pool_for_market { market_id, weights: weights.clone() }
+            .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+        let market = T::MarketCommons::market(&market_id)?;
+        let mut assets = Pallet::<T>::outcome_assets(market_id, &market);
+        assets.push(Asset::Ztg);
+        let new_pool_id = T::Swaps::create_pool(
+            caller,
+            assets,
+            Some(Asset::Ztg),
+            market_id,
+            ScoringRule::CPMM,
+            Some(Zero::zero()),
+            Some(weights),
+        )?;
+        let approval_origin = T::ApprovalOrigin::successful_origin();
+        let call = Call::<T>::admin_migrate_pool { market_id, new_pool_id };
+    }: { call.dispatch_bypass_filter(approval_origin)? }
+
     admin_move_market_to_closed {
         let (caller, market_id) = create_market_common::<T>(
             MarketCreation::Permissionless,
@@ -276,6 +349,27 @@ benchmarks! {
         let call = Call::<T>::approve_market { market_id };
     }: { call.dispatch_bypass_filter(origin)? }
 
+    attach_market_evidence {
+        // a = num. evidence entries that are already attached, their total length is checked
+        // against `MaxTotalMetadataBytes`
+        let a in 0..T::MaxAdditionalMetadata::get() - 1;
+        let (caller, market_id) = create_market_common::<T>(
+            MarketCreation::Permissionless,
+            MarketType::Categorical(T::MaxCategories::get()),
+            ScoringRule::CPMM
+        )?;
+        let mut evidence = [0u8; 50];
+        evidence[0] = 0x15;
+        evidence[1] = 0x30;
+        for _ in 0..a {
+            let _ = Call::<T>::attach_market_evidence {
+                market_id,
+                evidence: MultiHash::Sha3_384(evidence),
+            }
+            .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+        }
+    }: _(RawOrigin::Signed(caller), market_id, MultiHash::Sha3_384(evidence))
+
     buy_complete_set {
         let a in (T::MinCategories::get().into())..T::MaxCategories::get().into();
         let (caller, market_id) = create_market_common::<T>(
@@ -294,19 +388,49 @@ benchmarks! {
         )?;
     }: _(RawOrigin::Signed(caller), market_id)
 
+    claim_dispute_bond {
+        // a = num. disputes of the caller
+        let a in 1..T::MaxDisputes::get();
+        let (caller, market_id) = create_close_and_report_market::<T>(
+            MarketCreation::Permissionless,
+            MarketType::Scalar(0u128..=u128::MAX),
+            OutcomeReport::Scalar(42)
+        )?;
+        let at = frame_system::Pallet::<T>::block_number();
+        let mut disputes = vec![];
+        for i in 0..a {
+            disputes.push(MarketDispute {
+                at,
+                by: caller.clone(),
+                outcome: OutcomeReport::Scalar(42),
+            });
+            CurrencyOf::<T>::reserve_named(
+                &crate::pallet::RESERVE_ID,
+                &caller,
+                default_dispute_bond::<T>(i as usize),
+            )?;
+        }
+        crate::Disputes::<T>::insert(market_id, disputes);
+        T::MarketCommons::mutate_market(&market_id, |market| {
+            market.status = MarketStatus::Resolved;
+            market.resolved_outcome = Some(OutcomeReport::Scalar(42));
+            Ok(())
+        })?;
+    }: _(RawOrigin::Signed(caller), market_id)
+
     create_categorical_market {
         let (caller, oracle, period, metadata, creation) =
             create_market_common_parameters::<T>(MarketCreation::Permissionless)?;
         let categories = T::MaxCategories::get();
     }: _(RawOrigin::Signed(caller), oracle, period, metadata, creation, categories,
-            MarketDisputeMechanism::SimpleDisputes, ScoringRule::CPMM)
+            MarketDisputeMechanism::SimpleDisputes, ScoringRule::CPMM, Default::default())
 
     create_scalar_market {
         let (caller, oracle, period, metadata, creation) =
             create_market_common_parameters::<T>(MarketCreation::Permissionless)?;
         let outcome_range = 0u128..=u128::MAX;
     }: _(RawOrigin::Signed(caller), oracle, period, metadata, creation, outcome_range,
-            MarketDisputeMechanism::SimpleDisputes, ScoringRule::CPMM)
+            MarketDisputeMechanism::SimpleDisputes, ScoringRule::CPMM, Default::default())
 
     deploy_swap_pool_for_market {
         let a in (T::MinCategories::get().into())..T::MaxCategories::get().into();
@@ -337,6 +461,10 @@ benchmarks! {
         let _ = T::SimpleDisputes::on_dispute(&disputes, &market_id, &market)?;
     }
 
+    global_dispute {
+        let (caller, market_id) = setup_global_dispute_common::<T>()?;
+    }: _(RawOrigin::Signed(caller), market_id)
+
     internal_resolve_categorical_reported {
         // a = total accounts
         let a in 0..10;
@@ -494,6 +622,21 @@ benchmarks! {
             Ok(())
         })?;
     }: { Pallet::<T>::start_subsidy(&market_clone.unwrap(), market_id)? }
+
+    // Worst case: The voter increases an existing stake.
+    vote_on_global_dispute {
+        let (caller, market_id) = setup_global_dispute_common::<T>()?;
+        let _ = Call::<T>::global_dispute { market_id }
+            .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+        let outcome = OutcomeReport::Scalar(42);
+        let _ = Call::<T>::vote_on_global_dispute {
+            market_id,
+            outcome: outcome.clone(),
+            amount: T::MinGlobalDisputeInitialStake::get(),
+        }
+        .dispatch_bypass_filter(RawOrigin::Signed(caller.clone()).into())?;
+        let amount = T::MinGlobalDisputeStake::get();
+    }: _(RawOrigin::Signed(caller), market_id, outcome, amount)
 }
 
 impl_benchmark_test_suite!(
@@ -23,17 +23,24 @@
 //! #### Public Dispatches
 //!
 //! - `buy_complete_set` - Buys a complete set of outcome assets for a market.
+//! - `buy_conditional_set` - Exchanges outcome shares of a market for a complete set of shares of another market that are conditioned on that outcome.
 //! - `cancel_pending_market` - Allows the proposer of a market that is currently in a `Proposed` state to cancel the market proposal.
+//! - `claim_dispute_bond` - Confirms how the caller's dispute bonds were settled after resolution.
+//! - `close_market_early` - Allows the creator of an active market to end it immediately.
 //! - `create_categorical_market` - Creates a new categorical market.
 //! - `create_cpmm_market_and_deploy_assets` - Create a market using CPMM scoring rule, buy a complete set of the assets used and deploy.
 //!    within and deploy an arbitrary amount of those that's greater than the minimum amount.
 //! - `create_scalar_market` - Creates a new scalar market.
 //! - `deploy_swap_pool_for_market` - Deploys a single "canonical" pool for a market.
 //! - `dispute` - Submits a disputed outcome for a market.
-//! - `global_dispute` - `unimplemented!()`
+//! - `global_dispute` - Starts a global dispute for a market that was disputed `MaxDisputes` times.
 //! - `redeem_shares` - Redeems the winning shares for a market.
 //! - `report` - Reports an outcome for a market.
+//! - `resolve` - Resolves an overdue market and rewards the caller with `ResolutionReward`.
 //! - `sell_complete_set` - Sells a complete set of outcome assets for a market.
+//! - `sell_complete_set_for` - Sells a complete set of outcome assets for a market and sends the collateral to another account.
+//! - `sell_conditional_set` - Exchanges a complete set of conditional shares back for the outcome shares they are conditioned on.
+//! - `vote_on_global_dispute` - Stakes on an outcome of a market's global dispute.
 //!
 //! #### Admin Dispatches
 //!
@@ -41,6 +48,8 @@
 //! admin functions can only be called by the `ApprovalOrigin` origin.
 //!
 //! - `admin_destroy_market` - Destroys a market and all related assets, regardless of its state.
+//! - `admin_extend_review` - Postpones the review deadline of a `Proposed` market.
+//! - `admin_migrate_pool` - Points a market to another pool that contains the market's assets.
 //! - `admin_move_market_to_closed` - Immediately moves a market that is an `Active` state to closed.
 //! - `admin_move_market_to_resolved` - Immediately moves a market that is `Reported` or `Disputed` to resolved.
 //!
@@ -68,32 +77,35 @@ pub use pallet::*;
 mod pallet {
     use crate::weights::*;
     use alloc::{vec, vec::Vec};
-    use core::{cmp, marker::PhantomData, ops::RangeInclusive};
+    use core::{cmp, convert::TryFrom, marker::PhantomData, ops::RangeInclusive};
     use frame_support::{
         dispatch::{DispatchResultWithPostInfo, Weight},
         ensure, log,
-        pallet_prelude::{StorageMap, StorageValue, ValueQuery},
+        pallet_prelude::{StorageDoubleMap, StorageMap, StorageValue, ValueQuery},
         storage::{with_transaction, TransactionOutcome},
         traits::{
             Currency, EnsureOrigin, ExistenceRequirement, Get, Hooks, Imbalance, IsType,
-            NamedReservableCurrency, OnUnbalanced,
+            NamedReservableCurrency, OnUnbalanced, WithdrawReasons,
         },
         transactional, Blake2_128Concat, PalletId, Twox64Concat,
     };
     use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-    use orml_traits::MultiCurrency;
+    use orml_traits::{MultiCurrency, MultiReservableCurrency};
     use sp_arithmetic::per_things::Perbill;
     use sp_runtime::{
-        traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
+        traits::{AccountIdConversion, CheckedAdd, CheckedDiv, CheckedSub, One, Saturating, Zero},
         ArithmeticError, DispatchError, DispatchResult, SaturatedConversion,
     };
     use zeitgeist_primitives::{
-        constants::{MinLiquidity, PmPalletId, MILLISECS_PER_BLOCK},
+        constants::{MinLiquidity, PmPalletId, BASE},
         traits::{DisputeApi, Swaps, ZeitgeistMultiReservableCurrency},
         types::{
-            Asset, Market, MarketCreation, MarketDispute, MarketDisputeMechanism, MarketPeriod,
-            MarketStatus, MarketType, MultiHash, OutcomeReport, Report, ScalarPosition,
-            ScoringRule, SubsidyUntil,
+            scalar_payout_percents, Asset, BondBreakdown, BoundedMetadata, CategoryIndex,
+            DisputeResolutionRule, Market, MarketCreation, MarketCreationOptions, MarketDispute,
+            MarketDisputeMechanism, MarketEnd, MarketEndKinds, MarketPeriod, MarketStatus,
+            MarketType, MarketView, MultiHash, OutcomeDescriptor, OutcomeReport, PoolId, Report,
+            ScalarDisputeStep, ScalarPosition, ScalarResolutionPolicy, ScoringRule, SubsidyUntil,
+            ValidityBondPolicy,
         },
     };
     use zrml_liquidity_mining::LiquidityMiningPalletApi;
@@ -115,16 +127,20 @@ mod pallet {
     impl<T: Config> Pallet<T> {
         /// Allows the `ApprovalOrigin` to immediately destroy a market.
         ///
+        /// NOTE: Fails with `TooManyHoldersToDestroy` if the outcome shares of the market are held
+        /// by more than `MaxDestroyedShareAccounts` accounts, since they can't all be removed
+        /// within a single block.
+        ///
         /// todo: this should check if there's any outstanding funds reserved if it stays
         /// in for production
         #[pallet::weight(
             T::WeightInfo::admin_destroy_reported_market(
-                4_500,
-                4_500,
+                T::MaxDestroyedShareAccounts::get(),
+                T::MaxDestroyedShareAccounts::get(),
                 T::MaxCategories::get().into()
             ).max(T::WeightInfo::admin_destroy_disputed_market(
-                4_500,
-                4_500,
+                T::MaxDestroyedShareAccounts::get(),
+                T::MaxDestroyedShareAccounts::get(),
                 T::MaxCategories::get().into()
             ))
         )]
@@ -140,24 +156,52 @@ mod pallet {
             let market_status = market.status;
             let outcome_assets = Self::outcome_assets(market_id, &market);
             let outcome_assets_amount = outcome_assets.len();
-            Self::clear_auto_resolve(&market_id)?;
-            T::MarketCommons::remove_market(&market_id)?;
-
-            let mut outcome_assets_iter = outcome_assets.into_iter();
 
-            // Delete of this market's outcome assets.
-            let mut manage_outcome_asset = |asset: Asset<_>| -> usize {
-                let (total_accounts, accounts) = T::Shares::accounts_by_currency_id(asset);
+            // Look up all holders before anything is removed, so that the market is left intact
+            // if it has too many of them.
+            let mut holders = Vec::with_capacity(outcome_assets_amount);
+            for (idx, asset) in outcome_assets.into_iter().enumerate() {
+                let (asset_total_accounts, accounts) = T::Shares::accounts_by_currency_id(asset);
+                if idx == 0 {
+                    total_accounts = asset_total_accounts;
+                }
                 share_accounts = share_accounts.saturating_add(accounts.len());
-                T::Shares::destroy_all(asset, accounts.iter().cloned());
-                total_accounts
-            };
+                holders.push((asset, accounts));
+            }
+            ensure!(
+                share_accounts <= T::MaxDestroyedShareAccounts::get().saturated_into::<usize>(),
+                Error::<T>::TooManyHoldersToDestroy
+            );
 
-            if let Some(first_asset) = outcome_assets_iter.next() {
-                total_accounts = manage_outcome_asset(first_asset);
+            if market_status == MarketStatus::Disputed {
+                Self::decrement_disputed_markets_count();
+            }
+            Self::clear_auto_resolve(&market_id)?;
+            Self::refund_market_account(&market_id, &market.creator)?;
+            T::MarketCommons::remove_market(&market_id)?;
+            MarketsByOracle::<T>::remove(&market.oracle, &market_id);
+            MarketBonds::<T>::remove(&market_id);
+            OutcomeDescriptors::<T>::remove(&market_id);
+            OracleGracePeriods::<T>::remove(&market_id);
+            AdditionalMetadata::<T>::remove(&market_id);
+            Self::release_auto_deploy_pool_seed(&market_id, &market.creator);
+            Self::clear_review_deadline(&market_id);
+            GlobalDisputeEnds::<T>::remove(&market_id);
+            ResolutionFailures::<T>::remove(&market_id);
+            for (voter, (_, stake)) in GlobalDisputeVotes::<T>::drain_prefix(&market_id) {
+                CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &voter, stake);
+            }
+            if let Some(resolved_outcome) = &market.resolved_outcome {
+                Self::return_unclaimed_dispute_bonds(&market_id, resolved_outcome);
             }
-            for asset in outcome_assets_iter {
-                let _ = manage_outcome_asset(asset);
+            DisputeBondClaims::<T>::remove_prefix(&market_id, None);
+
+            // Delete of this market's outcome assets.
+            for (asset, accounts) in holders {
+                for (account, _) in accounts.iter() {
+                    AccountPositions::<T>::remove(account, &market_id);
+                }
+                T::Shares::destroy_all(asset, accounts.into_iter());
             }
 
             // Weight correction
@@ -180,7 +224,63 @@ mod pallet {
             }
         }
 
-        /// Allows the `ApprovalOrigin` to immediately move an open market to closed.
+        /// Allows the `ApprovalOrigin` to postpone the review deadline of a proposed market.
+        /// Proposed markets that are neither approved nor rejected until their review deadline
+        /// are removed and their bonds are returned.
+        ///
+        #[pallet::weight(T::WeightInfo::admin_extend_review())]
+        pub fn admin_extend_review(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            new_deadline: T::BlockNumber,
+        ) -> DispatchResult {
+            T::ApprovalOrigin::ensure_origin(origin)?;
+
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.status == MarketStatus::Proposed, Error::<T>::MarketIsNotProposed);
+            let deadline =
+                ReviewDeadlines::<T>::get(&market_id).ok_or(Error::<T>::MarketIsNotProposed)?;
+            ensure!(new_deadline > deadline, Error::<T>::ReviewDeadlineNotExtended);
+
+            Self::clear_review_deadline(&market_id);
+            Self::schedule_review_deadline(market_id, new_deadline);
+            Self::deposit_event(Event::MarketReviewExtended(market_id, new_deadline));
+            Ok(())
+        }
+
+        /// Allows the `ApprovalOrigin` to point a market to another pool, e.g. after the pool
+        /// format of the swaps pallet changed.
+        ///
+        /// NOTE: The new pool must contain every outcome asset of the market and the base asset,
+        /// and nothing else.
+        ///
+        #[pallet::weight(T::WeightInfo::admin_migrate_pool(T::MaxCategories::get().into()))]
+        pub fn admin_migrate_pool(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            new_pool_id: PoolId,
+        ) -> DispatchResult {
+            T::ApprovalOrigin::ensure_origin(origin)?;
+
+            let market = T::MarketCommons::market(&market_id)?;
+            let old_pool_id = T::MarketCommons::market_pool(&market_id)?;
+            ensure!(T::Swaps::pool_exists(new_pool_id), Error::<T>::PoolDoesNotExist);
+
+            let mut assets = Self::outcome_assets(market_id, &market);
+            assets.push(Asset::Ztg);
+            let new_pool = T::Swaps::pool(new_pool_id)?;
+            ensure!(new_pool.assets.len() == assets.len(), Error::<T>::PoolAssetCountMismatch);
+            for asset in assets.iter() {
+                ensure!(new_pool.assets.contains(asset), Error::<T>::PoolDoesNotContainAsset);
+            }
+
+            T::MarketCommons::insert_market_pool(market_id, new_pool_id);
+            Self::deposit_event(Event::MarketPoolMigrated(market_id, old_pool_id, new_pool_id));
+            Ok(())
+        }
+
+        /// Allows the `ApprovalOrigin` to immediately move an open market to closed. The end of
+        /// markets that already ended is left untouched.
         //
         // ***** IMPORTANT *****
         //
@@ -192,24 +292,47 @@ mod pallet {
             market_id: MarketIdOf<T>,
         ) -> DispatchResult {
             T::ApprovalOrigin::ensure_origin(origin)?;
-            T::MarketCommons::mutate_market(&market_id, |m| {
-                m.period = match m.period {
-                    MarketPeriod::Block(ref range) => {
-                        let current_block = <frame_system::Pallet<T>>::block_number();
-                        MarketPeriod::Block(range.start..current_block)
+            Self::close_market(&market_id)?;
+            Self::reconcile_market_escrow(&market_id)
+        }
+
+        /// Allows the `ApprovalOrigin` to set the end of an active market that wasn't reported
+        /// yet, e.g. to correct a misconfigured end. The end may be in the past, which closes the
+        /// market immediately, but must be after the start of the market and of the same kind as
+        /// its period.
+        #[pallet::weight(T::WeightInfo::admin_move_market_to_closed())]
+        pub fn admin_set_market_end(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            end: MarketEnd<T::BlockNumber, MomentOf<T>>,
+        ) -> DispatchResult {
+            T::ApprovalOrigin::ensure_origin(origin)?;
+            T::MarketCommons::mutate_market(&market_id, |market| {
+                ensure!(
+                    market.status == MarketStatus::Active && market.report.is_none(),
+                    Error::<T>::MarketIsNotActive
+                );
+                market.period = match (&market.period, &end) {
+                    (MarketPeriod::Block(range), MarketEnd::Block(block)) => {
+                        ensure!(range.start < *block, Error::<T>::InvalidMarketEnd);
+                        MarketPeriod::Block(range.start..*block)
                     }
-                    MarketPeriod::Timestamp(ref range) => {
-                        let now = T::MarketCommons::now();
-                        MarketPeriod::Timestamp(range.start..now)
+                    (MarketPeriod::Timestamp(range), MarketEnd::Timestamp(moment)) => {
+                        ensure!(range.start < *moment, Error::<T>::InvalidMarketEnd);
+                        MarketPeriod::Timestamp(range.start..*moment)
                     }
+                    _ => return Err(Error::<T>::InvalidMarketEnd.into()),
                 };
                 Ok(())
             })?;
+            Self::deposit_event(Event::MarketEndSet(market_id, end));
             Ok(())
         }
 
         /// Allows the `ApprovalOrigin` to immediately move a reported or disputed
         /// market to resolved.
+        ///
+        /// NOTE: This is also the way to resolve a market whose resolution stalled.
         ////
         #[pallet::weight(T::WeightInfo::admin_move_market_to_resolved_overhead()
             .saturating_add(T::WeightInfo::internal_resolve_categorical_reported(
@@ -218,6 +341,7 @@ mod pallet {
                 T::MaxCategories::get().into()
             ).saturating_sub(T::WeightInfo::internal_resolve_scalar_reported())
         ))]
+        #[transactional]
         pub fn admin_move_market_to_resolved(
             origin: OriginFor<T>,
             market_id: MarketIdOf<T>,
@@ -225,10 +349,21 @@ mod pallet {
             T::ApprovalOrigin::ensure_origin(origin)?;
 
             let market = T::MarketCommons::market(&market_id)?;
-            ensure!(
-                market.status == MarketStatus::Reported || market.status == MarketStatus::Disputed,
-                "not reported nor disputed"
-            );
+            if market.status == MarketStatus::ResolutionStalled {
+                // A stalled market continues to resolve from where it stalled.
+                let status = if Disputes::<T>::get(&market_id).is_empty() {
+                    MarketStatus::Reported
+                } else {
+                    MarketStatus::Disputed
+                };
+                Self::set_market_status(&market_id, status)?;
+            } else {
+                ensure!(
+                    market.status == MarketStatus::Reported
+                        || market.status == MarketStatus::Disputed,
+                    "not reported nor disputed"
+                );
+            }
             Self::clear_auto_resolve(&market_id)?;
             let market = T::MarketCommons::market(&market_id)?;
             let weight = Self::on_resolution(&market_id, &market)?;
@@ -241,34 +376,86 @@ mod pallet {
         /// NOTE: Returns the proposer's bond since the market has been
         /// deemed valid by an advisory committee.
         ///
+        /// NOTE: Deploys the pool that was requested with `auto_deploy_pool` at creation.
+        ///
         /// NOTE: Can only be called by the `ApprovalOrigin`.
         ///
-        #[pallet::weight(T::WeightInfo::approve_market())]
+        #[pallet::weight(
+            T::WeightInfo::approve_market()
+                .saturating_add(Pallet::<T>::auto_deploy_pool_weight(&None))
+        )]
+        #[transactional]
         pub fn approve_market(
             origin: OriginFor<T>,
             market_id: MarketIdOf<T>,
         ) -> DispatchResultWithPostInfo {
+            let approver = ensure_signed(origin.clone()).ok();
             T::ApprovalOrigin::ensure_origin(origin)?;
             let mut extra_weight = 0;
-            let mut status = MarketStatus::Active;
 
-            T::MarketCommons::mutate_market(&market_id, |m| {
-                ensure!(m.status == MarketStatus::Proposed, Error::<T>::MarketIsNotProposed);
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.status == MarketStatus::Proposed, Error::<T>::MarketIsNotProposed);
+            let status = if market.scoring_rule == ScoringRule::CPMM {
+                MarketStatus::Active
+            } else {
+                MarketStatus::CollectingSubsidy
+            };
+            Self::set_market_status(&market_id, status)?;
+            if status == MarketStatus::CollectingSubsidy {
+                // The pool is created for the market with its new status.
+                extra_weight =
+                    Self::start_subsidy(&T::MarketCommons::market(&market_id)?, market_id)?;
+            }
+            let advisory_bond = Self::creation_bonds(&market_id, &market.creation).advisory;
+            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, advisory_bond);
+            Self::clear_review_deadline(&market_id);
+            if status == MarketStatus::Active {
+                extra_weight = Self::auto_deploy_pool(market_id)?;
+            }
 
-                if m.scoring_rule == ScoringRule::CPMM {
-                    m.status = MarketStatus::Active;
-                } else {
-                    m.status = MarketStatus::CollectingSubsidy;
-                    status = MarketStatus::CollectingSubsidy;
-                    extra_weight = Self::start_subsidy(m, market_id)?;
-                }
+            Self::deposit_event(Event::MarketApproved(market_id, status, approver));
+            Ok(Some(T::WeightInfo::approve_market().saturating_add(extra_weight)).into())
+        }
 
-                CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &m.creator, T::AdvisoryBond::get());
-                Ok(())
+        /// Attaches `evidence`, e.g. clarified resolution criteria, to the additional metadata of
+        /// a market.
+        ///
+        /// NOTE: Can only be called by the creator of the market while it's active or closed,
+        /// which includes markets that ended, but weren't reported yet. At most
+        /// `MaxAdditionalMetadata` entries can be attached to a single market and the evidence
+        /// counts towards `MaxTotalMetadataBytes`.
+        ///
+        #[pallet::weight(T::WeightInfo::attach_market_evidence(T::MaxAdditionalMetadata::get()))]
+        pub fn attach_market_evidence(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            evidence: MultiHash,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(sender == market.creator, Error::<T>::SenderNotCreator);
+            ensure!(
+                market.status == MarketStatus::Active || market.status == MarketStatus::Closed,
+                Error::<T>::MarketIsNotActiveOrClosed
+            );
+
+            // Require sha3-384 as multihash.
+            let MultiHash::Sha3_384(ref multihash) = evidence;
+            ensure!(multihash[0] == 0x15 && multihash[1] == 0x30, <Error<T>>::InvalidMultihash);
+            let descriptors = OutcomeDescriptors::<T>::get(&market_id).unwrap_or_default();
+            AdditionalMetadata::<T>::try_mutate(&market_id, |entries| {
+                let max_entries: usize = T::MaxAdditionalMetadata::get().saturated_into();
+                ensure!(entries.len() < max_entries, Error::<T>::TooMuchAdditionalMetadata);
+                let mut metadata_fields = vec![&market.metadata[..], &multihash[..]];
+                metadata_fields.extend(descriptors.iter().map(OutcomeDescriptor::as_bytes));
+                metadata_fields.extend(entries.iter().map(|entry| &entry[..]));
+                Self::ensure_metadata_is_within_limit(&metadata_fields)?;
+                entries.push(Self::bounded_metadata(multihash)?);
+                Ok::<_, DispatchError>(())
             })?;
 
-            Self::deposit_event(Event::MarketApproved(market_id, status));
-            Ok(Some(T::WeightInfo::approve_market().saturating_add(extra_weight)).into())
+            Self::deposit_event(Event::MarketEvidenceAttached(market_id, evidence));
+            Ok(())
         }
 
         /// Buys the complete set of outcome shares of a market. For example, when calling this
@@ -277,6 +464,9 @@ mod pallet {
         ///
         /// The amount of each share will equal the provided `amount` parameter.
         ///
+        /// NOTE: The `CompleteSetMintFee` of `amount` is charged on top of `amount` and handed to
+        /// `Slash`.
+        ///
         /// NOTE: This is the only way to create new shares.
         // Note: `buy_complete_set` weight consumption is dependent on how many assets exists.
         // Unfortunately this information can only be retrieved with a storage call, therefore
@@ -295,6 +485,134 @@ mod pallet {
             Self::do_buy_complete_set(sender, market_id, amount)
         }
 
+        /// Assembles complete sets of outcome shares of `market_id` by buying every outcome asset
+        /// from the market's pool instead of minting them.
+        ///
+        /// `collateral_in` is split evenly across the outcome assets, so no swap can spend more
+        /// than its part. The number of assembled sets is the smallest amount of outcome shares
+        /// received. Shares received on top of that are sold back to the pool, so the sender
+        /// receives complete sets only and the rest of the collateral is refunded.
+        ///
+        /// NOTE: Fails with `SlippageExceeded` if less than `min_sets_out` sets are assembled.
+        #[pallet::weight(
+            T::WeightInfo::buy_complete_set(T::MaxCategories::get().into())
+            // Overly generous estimation, since we have no access to Swaps WeightInfo
+            // (it is loosely coupled to this pallet using a trait). Contains weight for
+            // swap_exact_amount_in() twice per outcome asset
+            .saturating_add(10_000_000_000.saturating_mul(T::MaxCategories::get().into()))
+        )]
+        #[transactional]
+        pub fn buy_complete_set_via_pool(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            #[pallet::compact] collateral_in: BalanceOf<T>,
+            #[pallet::compact] min_sets_out: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!collateral_in.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(
+                CurrencyOf::<T>::free_balance(&sender) >= collateral_in,
+                Error::<T>::NotEnoughBalance
+            );
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            ensure!(market.status == MarketStatus::Active, Error::<T>::MarketIsNotActive);
+            Self::ensure_market_is_active(&market.period)?;
+            let pool_id = T::MarketCommons::market_pool(&market_id)?;
+
+            let assets = Self::outcome_assets(market_id, &market);
+            let assets_len: BalanceOf<T> = assets.len().saturated_into();
+            let amount_in_per_asset =
+                collateral_in.checked_div(&assets_len).ok_or(ArithmeticError::DivisionByZero)?;
+            ensure!(!amount_in_per_asset.is_zero(), Error::<T>::ZeroAmount);
+
+            let mut received = Vec::with_capacity(assets.len());
+            for asset in assets.iter() {
+                let balance_before = T::Shares::free_balance(*asset, &sender);
+                let _ = T::Swaps::swap_exact_amount_in(
+                    sender.clone(),
+                    pool_id,
+                    Asset::Ztg,
+                    amount_in_per_asset,
+                    *asset,
+                    Zero::zero(),
+                    u128::MAX.saturated_into(),
+                )?;
+                let amount =
+                    T::Shares::free_balance(*asset, &sender).saturating_sub(balance_before);
+                received.push((*asset, amount));
+            }
+            let sets_out = received.iter().map(|(_, amount)| *amount).min().unwrap_or_default();
+            ensure!(sets_out >= min_sets_out, Error::<T>::SlippageExceeded);
+
+            // Sell the shares that don't complete a set back to the pool.
+            for (asset, amount) in received {
+                let excess = amount.saturating_sub(sets_out);
+                if excess.is_zero() {
+                    continue;
+                }
+                let _ = T::Swaps::swap_exact_amount_in(
+                    sender.clone(),
+                    pool_id,
+                    asset,
+                    excess,
+                    Asset::Ztg,
+                    Zero::zero(),
+                    u128::MAX.saturated_into(),
+                )?;
+            }
+            Self::update_account_position(&sender, market_id, &assets);
+
+            Self::deposit_event(Event::BoughtCompleteSetViaPool(market_id, sets_out, sender));
+            Ok(())
+        }
+
+        /// Exchanges `amount` shares of `parent_outcome` of `parent_market_id` for a complete set
+        /// of `CombinatorialShare`s of the categorical market `child_market_id` that are
+        /// conditioned on `parent_outcome`.
+        ///
+        /// NOTE: The parent shares are held in escrow by the account of `child_market_id` until
+        /// the set is sold or the conditional shares are redeemed with
+        /// `redeem_conditional_shares`.
+        #[pallet::weight(
+            T::WeightInfo::buy_complete_set(T::MaxCategories::get().into())
+        )]
+        #[transactional]
+        pub fn buy_conditional_set(
+            origin: OriginFor<T>,
+            parent_market_id: MarketIdOf<T>,
+            parent_outcome: CategoryIndex,
+            child_market_id: MarketIdOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let (parent_asset, conditional_assets, child_market) =
+                Self::conditional_assets(parent_market_id, parent_outcome, child_market_id)?;
+            Self::ensure_conditional_market_is_active(&child_market_id, &child_market)?;
+            ensure!(
+                T::Shares::free_balance(parent_asset, &sender) >= amount,
+                Error::<T>::InsufficientShareBalance
+            );
+
+            let escrow_account = Self::market_account(child_market_id);
+            T::Shares::transfer(parent_asset, &sender, &escrow_account, amount)?;
+            for asset in conditional_assets.iter() {
+                T::Shares::deposit(*asset, &sender, amount)?;
+            }
+            let parent_market = T::MarketCommons::market(&parent_market_id)?;
+            let parent_assets = Self::outcome_assets(parent_market_id, &parent_market);
+            Self::update_account_position(&sender, parent_market_id, &parent_assets);
+
+            Self::deposit_event(Event::BoughtConditionalSet(
+                parent_market_id,
+                parent_outcome,
+                child_market_id,
+                sender,
+            ));
+            Ok(())
+        }
+
         #[pallet::weight(T::WeightInfo::dispute(T::MaxDisputes::get()))]
         pub fn dispute(
             origin: OriginFor<T>,
@@ -306,12 +624,15 @@ mod pallet {
             let curr_block_num = <frame_system::Pallet<T>>::block_number();
             let market = T::MarketCommons::market(&market_id)?;
             let num_disputes: u32 = disputes.len().saturated_into();
-            Self::validate_dispute(&disputes, &market, num_disputes, &outcome)?;
-            CurrencyOf::<T>::reserve_named(
-                &RESERVE_ID,
-                &who,
-                default_dispute_bond::<T>(disputes.len()),
-            )?;
+            Self::validate_dispute(&disputes, &market, num_disputes, &outcome, &who)?;
+            if market.status != MarketStatus::Disputed {
+                ensure!(
+                    DisputedMarketsCount::<T>::get() < T::MaxConcurrentDisputedMarkets::get(),
+                    Error::<T>::TooManyDisputedMarkets
+                );
+            }
+            let bond = default_dispute_bond::<T>(disputes.len());
+            CurrencyOf::<T>::reserve_named(&RESERVE_ID, &who, bond)?;
             match market.mdm {
                 MarketDisputeMechanism::Authorized(_) => {
                     T::Authorized::on_dispute(&disputes, &market_id, &market)?
@@ -323,20 +644,19 @@ mod pallet {
                     T::SimpleDisputes::on_dispute(&disputes, &market_id, &market)?
                 }
             }
-            Self::remove_last_dispute_from_market_ids_per_dispute_block(&disputes, &market_id)?;
-            Self::set_market_as_disputed(&market, &market_id)?;
-            let market_dispute = MarketDispute { at: curr_block_num, by: who, outcome };
+            Self::reschedule_dispute_block(&disputes, &market_id, curr_block_num)?;
+            Self::set_market_status(&market_id, MarketStatus::Disputed)?;
+            let market_dispute =
+                MarketDispute { at: curr_block_num, by: who, outcome: outcome.clone() };
             <Disputes<T>>::mutate(market_id, |disputes| {
                 disputes.push(market_dispute.clone());
             });
-            <MarketIdsPerDisputeBlock<T>>::mutate(curr_block_num, |ids| {
-                ids.push(market_id);
-            });
             Self::deposit_event(Event::MarketDisputed(
                 market_id,
                 MarketStatus::Disputed,
                 market_dispute,
             ));
+            Self::deposit_event(Event::DisputeEscalated(market_id, num_disputes, outcome, bond));
             Self::calculate_actual_weight(
                 &T::WeightInfo::dispute,
                 num_disputes,
@@ -348,6 +668,8 @@ mod pallet {
         /// in a production environment since this better aligns incentives.
         /// See also: Polkadot Treasury
         ///
+        /// NOTE: The `CancellationPenalty` of the advisory bond is slashed, the rest is returned.
+        ///
         #[pallet::weight(T::WeightInfo::cancel_pending_market())]
         pub fn cancel_pending_market(
             origin: OriginFor<T>,
@@ -361,14 +683,132 @@ mod pallet {
             let status = market.status;
             ensure!(creator == sender, "Canceller must be market creator.");
             ensure!(status == MarketStatus::Proposed, "Market must be pending approval.");
-            // The market is being cancelled, return the deposit.
-            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &creator, T::AdvisoryBond::get());
+            // The market is being cancelled, return the deposit minus the cancellation penalty.
+            let advisory_bond = Self::creation_bonds(&market_id, &market.creation).advisory;
+            let penalty = T::CancellationPenalty::get().mul_floor(advisory_bond);
+            if !penalty.is_zero() {
+                let (imbalance, _) =
+                    CurrencyOf::<T>::slash_reserved_named(&RESERVE_ID, &creator, penalty);
+                T::Slash::on_unbalanced(imbalance);
+            }
+            CurrencyOf::<T>::unreserve_named(
+                &RESERVE_ID,
+                &creator,
+                advisory_bond.saturating_sub(penalty),
+            );
+            Self::refund_market_account(&market_id, &creator)?;
             T::MarketCommons::remove_market(&market_id)?;
+            MarketsByOracle::<T>::remove(&market.oracle, &market_id);
+            MarketBonds::<T>::remove(&market_id);
+            OutcomeDescriptors::<T>::remove(&market_id);
+            OracleGracePeriods::<T>::remove(&market_id);
+            Self::release_auto_deploy_pool_seed(&market_id, &creator);
+            Self::clear_review_deadline(&market_id);
             Self::deposit_event(Event::MarketCancelled(market_id));
             Ok(())
         }
 
-        #[pallet::weight(T::WeightInfo::create_categorical_market())]
+        /// Returns the dispute bonds of the caller that disputed the resolved outcome of a market
+        /// and confirms the slashed bonds of all other disputes of the caller.
+        ///
+        /// NOTE: Bonds of incorrect disputes are slashed on resolution, while bonds of correct
+        /// disputes stay reserved until they're claimed. Each disputer can claim once; later
+        /// claims succeed without effect.
+        ///
+        #[pallet::weight(T::WeightInfo::claim_dispute_bond(T::MaxDisputes::get()))]
+        pub fn claim_dispute_bond(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.status == MarketStatus::Resolved, Error::<T>::MarketIsNotResolved);
+            let resolved_outcome =
+                market.resolved_outcome.ok_or(Error::<T>::MarketIsNotResolved)?;
+
+            let mut returned = BalanceOf::<T>::zero();
+            let mut slashed = BalanceOf::<T>::zero();
+            for (i, dispute) in Disputes::<T>::get(market_id).iter().enumerate() {
+                if dispute.by != who {
+                    continue;
+                }
+                let bond = default_dispute_bond::<T>(i);
+                if dispute.outcome == resolved_outcome {
+                    returned = returned.saturating_add(bond);
+                } else {
+                    slashed = slashed.saturating_add(bond);
+                }
+            }
+            ensure!(!returned.is_zero() || !slashed.is_zero(), Error::<T>::NoDisputeBond);
+
+            if DisputeBondClaims::<T>::contains_key(market_id, &who) {
+                return Ok(());
+            }
+            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &who, returned);
+            DisputeBondClaims::<T>::insert(market_id, &who, ());
+            Self::deposit_event(Event::DisputeBondClaimed(market_id, who, returned, slashed));
+            Ok(())
+        }
+
+        /// Allows the creator of a proposed market to remove it once its review deadline passed
+        /// without a decision. All bonds of the market are returned.
+        ///
+        /// NOTE: Proposals are removed automatically at their review deadline. This is a fallback
+        /// for proposals that weren't, e.g. because they were created before review deadlines
+        /// existed. Their deadline is `AdvisoryReviewPeriod` blocks after their creation.
+        ///
+        #[pallet::weight(T::WeightInfo::reject_market())]
+        #[transactional]
+        pub fn claim_expired_proposal(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(sender == market.creator, Error::<T>::SenderNotCreator);
+            ensure!(market.status == MarketStatus::Proposed, Error::<T>::MarketIsNotProposed);
+            let deadline = ReviewDeadlines::<T>::get(&market_id).unwrap_or_else(|| {
+                market.created_at.saturating_add(T::AdvisoryReviewPeriod::get())
+            });
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= deadline,
+                Error::<T>::ProposalNotExpired
+            );
+            Self::remove_expired_proposal(&market_id, &market)
+        }
+
+        /// Allows the creator of an active market to end it immediately, e.g. because the event
+        /// it's about was cancelled. Trading stops and the market can be reported right away.
+        ///
+        #[pallet::weight(T::WeightInfo::admin_move_market_to_closed())]
+        pub fn close_market_early(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(sender == market.creator, Error::<T>::SenderNotCreator);
+            ensure!(market.status == MarketStatus::Active, Error::<T>::MarketIsNotActive);
+            Self::ensure_market_is_active(&market.period)?;
+            Self::close_market(&market_id)?;
+            Self::reconcile_market_escrow(&market_id)
+        }
+
+        /// Creates a new categorical market. See [`MarketCreationOptions`] for the optional
+        /// parameters.
+        ///
+        /// NOTE: The `outcome_descriptors` count towards `MaxTotalMetadataBytes`.
+        ///
+        /// NOTE: The seed collateral of the `auto_deploy_pool` is reserved from the creator until
+        /// the pool is deployed. Requires the CPMM scoring rule.
+        ///
+        /// NOTE: The `oracle_grace_period` must not exceed `ReportingPeriod`.
+        #[pallet::weight(
+            T::WeightInfo::create_categorical_market()
+                .saturating_add(Pallet::<T>::auto_deploy_pool_weight(&options.auto_deploy_pool))
+        )]
+        #[transactional]
         pub fn create_categorical_market(
             origin: OriginFor<T>,
             oracle: T::AccountId,
@@ -378,11 +818,29 @@ mod pallet {
             categories: u16,
             mdm: MarketDisputeMechanism<T::AccountId>,
             scoring_rule: ScoringRule,
+            options: MarketCreationOptions<BalanceOf<T>>,
         ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            let MarketCreationOptions {
+                oracle_bond,
+                validity_bond,
+                outcome_descriptors,
+                max_disputes,
+                auto_deploy_pool,
+                oracle_grace_period,
+            } = options;
+            let oracle_bond = Self::oracle_bond_or_default(oracle_bond)?;
+            let validity_bond = Self::validity_bond_or_default(validity_bond)?;
+            Self::ensure_max_disputes_is_valid(max_disputes)?;
+            Self::ensure_oracle_grace_period_is_valid(oracle_grace_period)?;
+            Self::ensure_market_end_kind_is_allowed(&period)?;
             Self::ensure_market_is_active(&period)?;
+            if auto_deploy_pool.is_some() {
+                ensure!(scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            }
 
-            ensure!(categories >= T::MinCategories::get(), <Error<T>>::NotEnoughCategories);
+            let min_categories = cmp::max(T::MinCategories::get(), 2);
+            ensure!(categories >= min_categories, <Error<T>>::NotEnoughCategories);
             ensure!(categories <= T::MaxCategories::get(), <Error<T>>::TooManyCategories);
 
             if scoring_rule == ScoringRule::RikiddoSigmoidFeeMarketEma {
@@ -392,32 +850,48 @@ mod pallet {
             // Require sha3-384 as multihash.
             let MultiHash::Sha3_384(multihash) = metadata;
             ensure!(multihash[0] == 0x15 && multihash[1] == 0x30, <Error<T>>::InvalidMultihash);
+            let mut metadata_fields = vec![&multihash[..]];
+            if let Some(ref descriptors) = outcome_descriptors {
+                Self::ensure_outcome_descriptors_are_valid(descriptors, categories)?;
+                metadata_fields.extend(descriptors.iter().map(OutcomeDescriptor::as_bytes));
+            }
+            Self::ensure_metadata_is_within_limit(&metadata_fields)?;
 
+            let bonds = Self::configured_bonds(&creation, oracle_bond, validity_bond);
+            let seed = if auto_deploy_pool.is_some() {
+                Self::auto_deploy_pool_seed()
+            } else {
+                Zero::zero()
+            };
+            let reserved = bonds.total().saturating_add(seed);
+            ensure!(
+                CurrencyOf::<T>::free_balance(&sender) >= reserved,
+                Error::<T>::InsufficientBalanceForBond
+            );
+            CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, reserved)?;
             let status: MarketStatus = match creation {
                 MarketCreation::Permissionless => {
-                    let required_bond = T::ValidityBond::get() + T::OracleBond::get();
-                    CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, required_bond)?;
-
                     if scoring_rule == ScoringRule::CPMM {
                         MarketStatus::Active
                     } else {
                         MarketStatus::CollectingSubsidy
                     }
                 }
-                MarketCreation::Advised => {
-                    let required_bond = T::AdvisoryBond::get() + T::OracleBond::get();
-                    CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, required_bond)?;
-                    MarketStatus::Proposed
-                }
+                MarketCreation::Advised => MarketStatus::Proposed,
             };
 
+            let market_type = MarketType::Categorical(categories);
             let market = Market {
                 creation,
                 creator_fee: 0,
                 creator: sender,
-                market_type: MarketType::Categorical(categories),
+                created_at: <frame_system::Pallet<T>>::block_number(),
+                created_at_timestamp: T::MarketCommons::now(),
+                kind: market_type.kind(),
+                market_type,
+                max_disputes,
                 mdm,
-                metadata: Vec::from(multihash),
+                metadata: Self::bounded_metadata(&multihash)?,
                 oracle,
                 period,
                 report: None,
@@ -425,11 +899,32 @@ mod pallet {
                 scoring_rule,
                 status,
             };
+            if let Some(ref weights) = auto_deploy_pool {
+                Self::ensure_weights_are_valid(&market, weights)?;
+            }
             let market_id = T::MarketCommons::push_market(market.clone())?;
+            Self::fund_market_account(&market.creator, market_id)?;
+            MarketBonds::<T>::insert(market_id, bonds);
+            MarketsByOracle::<T>::insert(&market.oracle, market_id, ());
+            if let Some(descriptors) = outcome_descriptors {
+                OutcomeDescriptors::<T>::insert(market_id, descriptors);
+            }
+            if let Some(weights) = auto_deploy_pool {
+                AutoDeployPools::<T>::insert(market_id, (seed, weights));
+            }
+            if let Some(grace_period) = oracle_grace_period {
+                OracleGracePeriods::<T>::insert(market_id, grace_period);
+            }
             let mut extra_weight = 0;
 
             if market.status == MarketStatus::CollectingSubsidy {
                 extra_weight = Self::start_subsidy(&market, market_id)?;
+            } else if market.status == MarketStatus::Proposed {
+                let deadline = <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::AdvisoryReviewPeriod::get());
+                Self::schedule_review_deadline(market_id, deadline);
+            } else if market.status == MarketStatus::Active {
+                extra_weight = Self::auto_deploy_pool(market_id)?;
             }
 
             Self::deposit_event(Event::MarketCreated(market_id, market));
@@ -460,6 +955,8 @@ mod pallet {
         /// * `keep_outcome_assets`: Specifies how many outcome assets to keep. Any left-over
         ///     assets that are specified as zero in this vector are sold. Must have the same
         ///     length as `amount_outcome_assets`.
+        /// * `oracle_bond`: An optional oracle bond that is higher than `OracleBond`.
+        /// * `validity_bond`: An optional validity bond that is higher than `ValidityBond`.
         #[pallet::weight(
             T::WeightInfo::create_scalar_market().max(T::WeightInfo::create_categorical_market())
             .saturating_add(T::WeightInfo::buy_complete_set(T::MaxCategories::get().min(amount_outcome_assets.len().saturated_into()).into()))
@@ -483,17 +980,15 @@ mod pallet {
             amount_outcome_assets: Vec<BalanceOf<T>>,
             weights: Vec<u128>,
             keep_outcome_assets: Vec<BalanceOf<T>>,
+            oracle_bond: Option<BalanceOf<T>>,
+            validity_bond: Option<BalanceOf<T>>,
         ) -> DispatchResultWithPostInfo {
             let _ = ensure_signed(origin.clone())?;
 
-            if let MarketType::Categorical(num_cat) = assets {
-                ensure!(
-                    amount_outcome_assets.len().saturated_into::<u16>() == num_cat,
-                    Error::<T>::NotEnoughAssets
-                );
-            } else if let MarketType::Scalar(_) = assets {
-                ensure!(amount_outcome_assets.len() == 2, Error::<T>::NotEnoughAssets);
-            }
+            ensure!(
+                amount_outcome_assets.len() == usize::from(assets.outcome_count()),
+                Error::<T>::NotEnoughAssets
+            );
 
             ensure!(
                 amount_outcome_assets.len() == keep_outcome_assets.len(),
@@ -511,6 +1006,7 @@ mod pallet {
                     category_count,
                     mdm,
                     ScoringRule::CPMM,
+                    MarketCreationOptions { oracle_bond, validity_bond, ..Default::default() },
                 )?
                 .actual_weight
                 .unwrap_or_else(T::WeightInfo::create_categorical_market),
@@ -523,6 +1019,7 @@ mod pallet {
                     range,
                     mdm,
                     ScoringRule::CPMM,
+                    MarketCreationOptions { oracle_bond, validity_bond, ..Default::default() },
                 )?
                 .actual_weight
                 .unwrap_or_else(T::WeightInfo::create_scalar_market),
@@ -561,7 +1058,20 @@ mod pallet {
             Ok(Some(weight_market_creation.saturating_add(deploy_and_populate_weight)).into())
         }
 
-        #[pallet::weight(T::WeightInfo::create_scalar_market())]
+        /// Creates a new scalar market. See [`MarketCreationOptions`] for the optional
+        /// parameters.
+        ///
+        /// NOTE: Scalar markets don't support `outcome_descriptors`.
+        ///
+        /// NOTE: The seed collateral of the `auto_deploy_pool` is reserved from the creator until
+        /// the pool is deployed. Requires the CPMM scoring rule.
+        ///
+        /// NOTE: The `oracle_grace_period` must not exceed `ReportingPeriod`.
+        #[pallet::weight(
+            T::WeightInfo::create_scalar_market()
+                .saturating_add(Pallet::<T>::auto_deploy_pool_weight(&options.auto_deploy_pool))
+        )]
+        #[transactional]
         pub fn create_scalar_market(
             origin: OriginFor<T>,
             oracle: T::AccountId,
@@ -571,9 +1081,27 @@ mod pallet {
             outcome_range: RangeInclusive<u128>,
             mdm: MarketDisputeMechanism<T::AccountId>,
             scoring_rule: ScoringRule,
+            options: MarketCreationOptions<BalanceOf<T>>,
         ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            let MarketCreationOptions {
+                oracle_bond,
+                validity_bond,
+                outcome_descriptors,
+                max_disputes,
+                auto_deploy_pool,
+                oracle_grace_period,
+            } = options;
+            ensure!(outcome_descriptors.is_none(), Error::<T>::InvalidOutcomeDescriptor);
+            let oracle_bond = Self::oracle_bond_or_default(oracle_bond)?;
+            let validity_bond = Self::validity_bond_or_default(validity_bond)?;
+            Self::ensure_max_disputes_is_valid(max_disputes)?;
+            Self::ensure_oracle_grace_period_is_valid(oracle_grace_period)?;
+            Self::ensure_market_end_kind_is_allowed(&period)?;
             Self::ensure_market_is_active(&period)?;
+            if auto_deploy_pool.is_some() {
+                ensure!(scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            }
 
             ensure!(outcome_range.start() < outcome_range.end(), "Invalid range provided.");
 
@@ -584,32 +1112,43 @@ mod pallet {
             // Require sha3-384 as multihash.
             let MultiHash::Sha3_384(multihash) = metadata;
             ensure!(multihash[0] == 0x15 && multihash[1] == 0x30, <Error<T>>::InvalidMultihash);
+            Self::ensure_metadata_is_within_limit(&[&multihash])?;
 
+            let bonds = Self::configured_bonds(&creation, oracle_bond, validity_bond);
+            let seed = if auto_deploy_pool.is_some() {
+                Self::auto_deploy_pool_seed()
+            } else {
+                Zero::zero()
+            };
+            let reserved = bonds.total().saturating_add(seed);
+            ensure!(
+                CurrencyOf::<T>::free_balance(&sender) >= reserved,
+                Error::<T>::InsufficientBalanceForBond
+            );
+            CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, reserved)?;
             let status: MarketStatus = match creation {
                 MarketCreation::Permissionless => {
-                    let required_bond = T::ValidityBond::get() + T::OracleBond::get();
-                    CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, required_bond)?;
-
                     if scoring_rule == ScoringRule::CPMM {
                         MarketStatus::Active
                     } else {
                         MarketStatus::CollectingSubsidy
                     }
                 }
-                MarketCreation::Advised => {
-                    let required_bond = T::AdvisoryBond::get() + T::OracleBond::get();
-                    CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, required_bond)?;
-                    MarketStatus::Proposed
-                }
+                MarketCreation::Advised => MarketStatus::Proposed,
             };
 
+            let market_type = MarketType::Scalar(outcome_range);
             let market = Market {
                 creation,
                 creator_fee: 0,
                 creator: sender,
-                market_type: MarketType::Scalar(outcome_range),
+                created_at: <frame_system::Pallet<T>>::block_number(),
+                created_at_timestamp: T::MarketCommons::now(),
+                kind: market_type.kind(),
+                market_type,
+                max_disputes,
                 mdm,
-                metadata: Vec::from(multihash),
+                metadata: Self::bounded_metadata(&multihash)?,
                 oracle,
                 period,
                 report: None,
@@ -617,11 +1156,29 @@ mod pallet {
                 status,
                 scoring_rule,
             };
+            if let Some(ref weights) = auto_deploy_pool {
+                Self::ensure_weights_are_valid(&market, weights)?;
+            }
             let market_id = T::MarketCommons::push_market(market.clone())?;
-            let mut extra_weight = 0;
+            Self::fund_market_account(&market.creator, market_id)?;
+            MarketBonds::<T>::insert(market_id, bonds);
+            MarketsByOracle::<T>::insert(&market.oracle, market_id, ());
+            if let Some(weights) = auto_deploy_pool {
+                AutoDeployPools::<T>::insert(market_id, (seed, weights));
+            }
+            if let Some(grace_period) = oracle_grace_period {
+                OracleGracePeriods::<T>::insert(market_id, grace_period);
+            }
+            let mut extra_weight = 0;
 
             if market.status == MarketStatus::CollectingSubsidy {
                 extra_weight = Self::start_subsidy(&market, market_id)?;
+            } else if market.status == MarketStatus::Proposed {
+                let deadline = <frame_system::Pallet<T>>::block_number()
+                    .saturating_add(T::AdvisoryReviewPeriod::get());
+                Self::schedule_review_deadline(market_id, deadline);
+            } else if market.status == MarketStatus::Active {
+                extra_weight = Self::auto_deploy_pool(market_id)?;
             }
 
             Self::deposit_event(Event::MarketCreated(market_id, market));
@@ -666,8 +1223,10 @@ mod pallet {
             keep_outcome_assets: Vec<BalanceOf<T>>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin.clone())?;
+            let market = T::MarketCommons::market(&market_id)?;
+            Self::ensure_weights_are_valid(&market, &weights)?;
             // Buy a complete set of assets based on the highest number to be deployed
-            let assets = T::MarketCommons::market(&market_id)?.market_type;
+            let assets = market.market_type;
             let zero_balance = <BalanceOf<T>>::zero();
             let max_assets = amount_outcome_assets
                 .iter()
@@ -685,6 +1244,10 @@ mod pallet {
                 Vec::with_capacity(amount_outcome_assets.len());
             let mut add_liqudity =
                 |amount: BalanceOf<T>, asset: Asset<MarketIdOf<T>>| -> DispatchResult {
+                    ensure!(
+                        T::Swaps::pool_contains_asset(pool_id, &asset),
+                        Error::<T>::PoolDoesNotContainAsset
+                    );
                     let local_weight = T::Swaps::pool_join_with_exact_asset_amount(
                         who.clone(),
                         pool_id,
@@ -782,46 +1345,92 @@ mod pallet {
             weights: Vec<u128>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
+            Self::do_deploy_swap_pool_for_market(sender, market_id, weights)
+        }
 
-            let market = T::MarketCommons::market(&market_id)?;
-            ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
-            Self::ensure_market_is_active(&market.period)?;
-
-            // ensure a swap pool does not already exist
-            ensure!(T::MarketCommons::market_pool(&market_id).is_err(), Error::<T>::SwapPoolExists);
-
-            let mut assets = Self::outcome_assets(market_id, &market);
-            let base_asset = Asset::Ztg;
-            assets.push(base_asset);
-
-            let pool_id = T::Swaps::create_pool(
-                sender,
-                assets,
-                Some(base_asset),
-                market_id,
-                ScoringRule::CPMM,
-                Some(Zero::zero()),
-                Some(weights),
-            )?;
-
-            T::MarketCommons::insert_market_pool(market_id, pool_id);
+        /// Deploys a pool for each `(market_id, weights)` entry as `deploy_swap_pool_for_market`
+        /// would.
+        ///
+        /// NOTE: Each entry is deployed in its own transaction. An entry that fails, e.g. because
+        /// the market already has a pool, is rolled back and reported with
+        /// `PoolDeploymentFailed` without affecting the other entries. At most
+        /// `MaxPoolDeploymentsPerBatch` entries are allowed.
+        ///
+        #[pallet::weight(
+            entries.iter().fold(0, |acc: Weight, (_, weights)| {
+                acc.saturating_add(
+                    T::WeightInfo::deploy_swap_pool_for_market(weights.len() as u32)
+                )
+            })
+        )]
+        pub fn deploy_swap_pools_batch(
+            origin: OriginFor<T>,
+            entries: Vec<(MarketIdOf<T>, Vec<u128>)>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(
+                entries.len() <= T::MaxPoolDeploymentsPerBatch::get() as usize,
+                Error::<T>::TooManyPoolDeployments
+            );
+            for (market_id, weights) in entries {
+                let result = with_transaction(|| {
+                    match Self::do_deploy_swap_pool_for_market(sender.clone(), market_id, weights) {
+                        Ok(()) => {
+                            TransactionOutcome::Commit(T::MarketCommons::market_pool(&market_id))
+                        }
+                        Err(err) => TransactionOutcome::Rollback(Err(err)),
+                    }
+                });
+                match result {
+                    Ok(pool_id) => Self::deposit_event(Event::PoolDeployed(market_id, pool_id)),
+                    Err(err) => Self::deposit_event(Event::PoolDeploymentFailed(market_id, err)),
+                }
+            }
             Ok(())
         }
 
         /// Starts a global dispute.
         ///
-        /// NOTE: Requires the market to be already disputed `MaxDisputes` amount of times.
+        /// The market isn't resolved by its dispute mechanism anymore. Instead, accounts can stake
+        /// on outcomes with `vote_on_global_dispute` for `GlobalDisputePeriod` blocks, after
+        /// which the market resolves to the outcome with the highest stake.
+        ///
+        /// NOTE: Requires the market to be already disputed `MaxDisputes` amount of times, or as
+        /// often as the `max_disputes` of the market allows.
         ///
-        #[pallet::weight(10_000_000)]
+        #[pallet::weight(T::WeightInfo::global_dispute())]
         pub fn global_dispute(origin: OriginFor<T>, market_id: MarketIdOf<T>) -> DispatchResult {
             let _sender = ensure_signed(origin)?;
-            let _market = T::MarketCommons::market(&market_id)?;
-            // TODO: implement global disputes
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.status == MarketStatus::Disputed, Error::<T>::MarketIsNotDisputed);
+            ensure!(
+                !GlobalDisputeEnds::<T>::contains_key(&market_id),
+                Error::<T>::GlobalDisputeAlreadyStarted
+            );
+            let disputes = Disputes::<T>::get(market_id);
+            let num_disputes: u32 = disputes.len().saturated_into();
+            ensure!(
+                num_disputes >= Self::max_disputes_of(&market),
+                Error::<T>::MaxDisputesNotReached
+            );
+
+            Self::remove_last_dispute_from_market_ids_per_dispute_block(&disputes, &market_id)?;
+            let curr_block_num = <frame_system::Pallet<T>>::block_number();
+            let end = curr_block_num.saturating_add(T::GlobalDisputePeriod::get());
+            GlobalDisputeEnds::<T>::insert(market_id, end);
+            MarketIdsPerGlobalDisputeEnd::<T>::mutate(end, |ids| {
+                ids.push(market_id);
+            });
+
+            Self::deposit_event(Event::GlobalDisputeStarted(market_id, end));
             Ok(())
         }
 
         /// Redeems the winning shares of a prediction market.
         ///
+        /// NOTE: The `creator_fee` of the market is paid to its creator and the
+        /// `RedemptionTreasuryFee` is handed to `Slash`. Both are deducted from the payout.
+        ///
         #[pallet::weight(T::WeightInfo::redeem_shares_categorical()
             .max(T::WeightInfo::redeem_shares_scalar())
         )]
@@ -838,82 +1447,34 @@ mod pallet {
 
             // Check to see if the sender has any winning shares.
             let resolved_outcome =
-                market.resolved_outcome.ok_or(Error::<T>::MarketIsNotResolved)?;
-
-            let winning_assets = match resolved_outcome {
-                OutcomeReport::Categorical(category_index) => {
-                    let winning_currency_id = Asset::CategoricalOutcome(market_id, category_index);
-                    let winning_balance = T::Shares::free_balance(winning_currency_id, &sender);
-
-                    ensure!(winning_balance > BalanceOf::<T>::zero(), Error::<T>::NoWinningBalance);
-
-                    // Ensure the market account has enough to pay out - if this is
-                    // ever not true then we have an accounting problem.
-                    ensure!(
-                        CurrencyOf::<T>::free_balance(&market_account) >= winning_balance,
-                        Error::<T>::InsufficientFundsInMarketAccount,
-                    );
-
-                    vec![(winning_currency_id, winning_balance, winning_balance)]
-                }
-                OutcomeReport::Scalar(value) => {
-                    let long_currency_id = Asset::ScalarOutcome(market_id, ScalarPosition::Long);
-                    let short_currency_id = Asset::ScalarOutcome(market_id, ScalarPosition::Short);
-                    let long_balance = T::Shares::free_balance(long_currency_id, &sender);
-                    let short_balance = T::Shares::free_balance(short_currency_id, &sender);
-
-                    ensure!(
-                        long_balance > BalanceOf::<T>::zero()
-                            || short_balance > BalanceOf::<T>::zero(),
-                        Error::<T>::NoWinningBalance
-                    );
-
-                    let bound = if let MarketType::Scalar(range) = market.market_type {
-                        range
-                    } else {
-                        return Err(Error::<T>::InvalidMarketType.into());
-                    };
+                market.resolved_outcome.clone().ok_or(Error::<T>::MarketIsNotResolved)?;
 
-                    let calc_payouts = |final_value: u128,
-                                        low: u128,
-                                        high: u128|
-                     -> (Perbill, Perbill) {
-                        if final_value <= low {
-                            return (Perbill::zero(), Perbill::one());
-                        }
-                        if final_value >= high {
-                            return (Perbill::one(), Perbill::zero());
-                        }
-
-                        let payout_long: Perbill = Perbill::from_rational(
-                            final_value.saturating_sub(low),
-                            high.saturating_sub(low),
-                        );
-                        let payout_short: Perbill = Perbill::from_parts(
-                            Perbill::one().deconstruct().saturating_sub(payout_long.deconstruct()),
-                        );
-                        (payout_long, payout_short)
-                    };
-
-                    let (long_percent, short_percent) =
-                        calc_payouts(value, *bound.start(), *bound.end());
-
-                    let long_payout = long_percent.mul_floor(long_balance);
-                    let short_payout = short_percent.mul_floor(short_balance);
-                    // Ensure the market account has enough to pay out - if this is
-                    // ever not true then we have an accounting problem.
-                    ensure!(
-                        CurrencyOf::<T>::free_balance(&market_account)
-                            >= long_payout + short_payout,
-                        Error::<T>::InsufficientFundsInMarketAccount,
-                    );
+            let mut winning_assets =
+                Self::winning_assets(market_id, &market, &sender, &resolved_outcome)?;
+            ensure!(
+                winning_assets.iter().any(|(_, _, balance)| !balance.is_zero()),
+                Error::<T>::NoWinningBalance
+            );
 
-                    vec![
-                        (long_currency_id, long_payout, long_balance),
-                        (short_currency_id, short_payout, short_balance),
-                    ]
+            // Ensure the market account has enough to pay out - if this is
+            // ever not true then we have an accounting problem. If enabled, the
+            // remaining collateral is split pro-rata between all winners instead.
+            let market_balance = CurrencyOf::<T>::free_balance(&market_account);
+            let total_payout = winning_assets
+                .iter()
+                .fold(BalanceOf::<T>::zero(), |acc, (_, payout, _)| acc.saturating_add(*payout));
+            if market_balance < total_payout {
+                ensure!(T::ProRataRedemption::get(), Error::<T>::InsufficientFundsInMarketAccount);
+                let liability =
+                    Self::winning_shares_liability(market_id, &market, &resolved_outcome)?;
+                let ratio = Perbill::from_rational(
+                    market_balance.saturated_into::<u128>(),
+                    liability.saturated_into::<u128>(),
+                );
+                for (_, payout, _) in winning_assets.iter_mut() {
+                    *payout = ratio.mul_floor(*payout);
                 }
-            };
+            }
 
             for (currency_id, payout, balance) in winning_assets {
                 // Destroy the shares.
@@ -921,23 +1482,51 @@ mod pallet {
 
                 // Pay out the winner.
                 let remaining_bal = CurrencyOf::<T>::free_balance(&market_account);
+                let actual_payout = payout.min(remaining_bal);
+                let existence_requirement =
+                    Self::market_account_existence_requirement(&market_account, actual_payout);
+                let (to_creator, to_treasury, to_redeemer) = Self::split_redemption_payout(
+                    actual_payout,
+                    Perbill::from_percent(market.creator_fee.into()),
+                    T::RedemptionTreasuryFee::get(),
+                )?;
 
+                if !to_creator.is_zero() {
+                    CurrencyOf::<T>::transfer(
+                        &market_account,
+                        &market.creator,
+                        to_creator,
+                        existence_requirement,
+                    )?;
+                }
+                if !to_treasury.is_zero() {
+                    let imbalance = CurrencyOf::<T>::withdraw(
+                        &market_account,
+                        to_treasury,
+                        WithdrawReasons::FEE,
+                        existence_requirement,
+                    )?;
+                    T::Slash::on_unbalanced(imbalance);
+                }
                 CurrencyOf::<T>::transfer(
                     &market_account,
                     &sender,
-                    payout.min(remaining_bal),
-                    ExistenceRequirement::AllowDeath,
+                    to_redeemer,
+                    existence_requirement,
                 )?;
             }
+            Self::update_account_position(
+                &sender,
+                market_id,
+                &Self::outcome_assets(market_id, &market),
+            );
 
             // Weight correction
-            if let OutcomeReport::Categorical(_) = resolved_outcome {
-                return Ok(Some(T::WeightInfo::redeem_shares_categorical()).into());
-            } else if let OutcomeReport::Scalar(_) = resolved_outcome {
-                return Ok(Some(T::WeightInfo::redeem_shares_scalar()).into());
+            if let MarketType::Categorical(_) = market.market_type {
+                Ok(Some(T::WeightInfo::redeem_shares_categorical()).into())
+            } else {
+                Ok(Some(T::WeightInfo::redeem_shares_scalar()).into())
             }
-
-            Ok(None.into())
         }
 
         /// Rejects a market that is waiting for approval from the advisory
@@ -947,24 +1536,37 @@ mod pallet {
         ///
         #[pallet::weight(T::WeightInfo::reject_market())]
         pub fn reject_market(origin: OriginFor<T>, market_id: MarketIdOf<T>) -> DispatchResult {
+            let rejector = ensure_signed(origin.clone()).ok();
             T::ApprovalOrigin::ensure_origin(origin)?;
 
             let market = T::MarketCommons::market(&market_id)?;
+            let advisory_bond = Self::creation_bonds(&market_id, &market.creation).advisory;
             let creator = market.creator;
-            let (imbalance, _) = CurrencyOf::<T>::slash_reserved_named(
-                &RESERVE_ID,
-                &creator,
-                T::AdvisoryBond::get(),
-            );
+            let (imbalance, _) =
+                CurrencyOf::<T>::slash_reserved_named(&RESERVE_ID, &creator, advisory_bond);
             // Slashes the imbalance.
             T::Slash::on_unbalanced(imbalance);
+            Self::refund_market_account(&market_id, &creator)?;
             T::MarketCommons::remove_market(&market_id)?;
-            Self::deposit_event(Event::MarketRejected(market_id));
+            MarketsByOracle::<T>::remove(&market.oracle, &market_id);
+            MarketBonds::<T>::remove(&market_id);
+            OutcomeDescriptors::<T>::remove(&market_id);
+            OracleGracePeriods::<T>::remove(&market_id);
+            Self::release_auto_deploy_pool_seed(&market_id, &creator);
+            Self::clear_review_deadline(&market_id);
+            Self::deposit_event(Event::MarketRejected(market_id, rejector));
             Ok(())
         }
 
         /// Reports the outcome of a market.
         ///
+        /// NOTE: Only the oracle may report until the oracle grace period of the market ends.
+        ///
+        /// NOTE: The oracle may correct its own report by calling this again while the
+        /// reporting period lasts and the market hasn't been disputed.
+        ///
+        /// NOTE: `Invalid` can be reported on every market.
+        ///
         #[pallet::weight(T::WeightInfo::report())]
         pub fn report(
             origin: OriginFor<T>,
@@ -974,33 +1576,40 @@ mod pallet {
             let sender = ensure_signed(origin.clone())?;
 
             let current_block = <frame_system::Pallet<T>>::block_number();
-            let market_report = Report { at: current_block, by: sender.clone(), outcome };
+            let mut market_report =
+                Report { at: current_block, by: sender.clone(), outcome, by_oracle: false };
+            let mut corrected_report_block = None;
 
             T::MarketCommons::mutate_market(&market_id, |market| {
-                // TODO make this a conditional check
-                // ensure!(outcome <= market.outcomes(), Error::<T>::OutcomeOutOfRange);
-                ensure!(market.report.is_none(), Error::<T>::MarketAlreadyReported);
-
+                Self::ensure_outcome_is_reportable(market, &market_report.outcome)?;
                 Self::ensure_market_is_closed(&market.period)?;
 
-                let mut should_check_origin = false;
-                match market.period {
-                    MarketPeriod::Block(ref range) => {
-                        if current_block <= range.end + T::ReportingPeriod::get().into() {
-                            should_check_origin = true;
-                        }
-                    }
-                    MarketPeriod::Timestamp(ref range) => {
-                        let rp_moment: MomentOf<T> = T::ReportingPeriod::get().into();
-                        let reporting_period_in_ms = rp_moment * MILLISECS_PER_BLOCK.into();
-                        if T::MarketCommons::now() <= range.end + reporting_period_in_ms {
-                            should_check_origin = true;
-                        }
-                    }
+                let is_before = |deadline| match deadline {
+                    MarketEnd::Block(deadline) => current_block <= deadline,
+                    MarketEnd::Timestamp(deadline) => T::MarketCommons::now() <= deadline,
+                };
+                let is_reporting_period = is_before(Self::reporting_deadline(market));
+                let is_grace_period = is_before(Self::oracle_grace_deadline(&market_id, market));
+
+                if let Some(ref previous_report) = market.report {
+                    // Only an undisputed report of the oracle can be corrected, and only by the
+                    // oracle itself while the reporting period lasts.
+                    ensure!(
+                        is_reporting_period
+                            && market.status == MarketStatus::Reported
+                            && previous_report.by_oracle,
+                        Error::<T>::MarketAlreadyReported
+                    );
+                    ensure!(sender == market.oracle, Error::<T>::ReporterNotOracle);
+                    // The market is resolved at the beginning of this block at the latest.
+                    let resolution_block =
+                        previous_report.at.saturating_add(T::DisputePeriod::get());
+                    ensure!(current_block < resolution_block, Error::<T>::ResolutionDue);
+                    corrected_report_block = Some(previous_report.at);
                 }
 
-                if should_check_origin {
-                    let sender_is_oracle = sender == market.oracle;
+                let sender_is_oracle = sender == market.oracle;
+                if is_grace_period || !T::AllowPublicReporting::get() {
                     let origin_has_permission = T::ApprovalOrigin::ensure_origin(origin).is_ok();
                     ensure!(
                         sender_is_oracle || origin_has_permission,
@@ -1008,12 +1617,18 @@ mod pallet {
                     );
                 }
 
+                market_report.by_oracle = sender_is_oracle;
                 market.report = Some(market_report.clone());
-                market.status = MarketStatus::Reported;
 
                 Ok(())
             })?;
+            Self::set_market_status(&market_id, MarketStatus::Reported)?;
 
+            if let Some(block) = corrected_report_block {
+                MarketIdsPerReportBlock::<T>::mutate(&block, |ids| {
+                    remove_item::<MarketIdOf<T>>(ids, &market_id);
+                });
+            }
             MarketIdsPerReportBlock::<T>::mutate(&current_block, |ids| {
                 ids.push(market_id);
             });
@@ -1026,56 +1641,235 @@ mod pallet {
             Ok(())
         }
 
+        /// Resolves a reported or disputed market that is overdue, because it wasn't resolved
+        /// automatically once its dispute period ended.
+        ///
+        /// NOTE: The caller receives `ResolutionReward` from the pallet account, which is funded
+        /// with the bonds slashed on resolution. If the slashed bonds don't cover the reward, only
+        /// the available part is paid.
+        ///
+        #[pallet::weight(T::WeightInfo::internal_resolve_categorical_disputed(
+            4_200,
+            4_200,
+            T::MaxCategories::get().into(),
+            T::MaxDisputes::get(),
+        ))]
+        #[transactional]
+        pub fn resolve(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.status != MarketStatus::Resolved, Error::<T>::MarketAlreadyResolved);
+            ensure!(
+                !GlobalDisputeEnds::<T>::contains_key(&market_id),
+                Error::<T>::GlobalDisputeAlreadyStarted
+            );
+
+            let report_block = market.report.as_ref().map(|report| report.at);
+            let due_block = match market.status {
+                MarketStatus::Reported => report_block,
+                MarketStatus::Disputed => {
+                    Disputes::<T>::get(&market_id).last().map(|dispute| dispute.at).or(report_block)
+                }
+                _ => None,
+            }
+            .ok_or(Error::<T>::MarketIsNotReported)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now >= due_block.saturating_add(T::DisputePeriod::get()),
+                Error::<T>::ResolutionNotDue
+            );
+
+            Self::clear_auto_resolve(&market_id)?;
+            let weight = Self::on_resolution(&market_id, &market)?;
+
+            let pallet_account = T::PalletId::get().into_account();
+            let reward =
+                T::ResolutionReward::get().min(CurrencyOf::<T>::free_balance(&pallet_account));
+            if !reward.is_zero() {
+                CurrencyOf::<T>::transfer(
+                    &pallet_account,
+                    &sender,
+                    reward,
+                    ExistenceRequirement::AllowDeath,
+                )?;
+            }
+            Ok(Some(weight).into())
+        }
+
         /// Destroys a complete set of outcomes shares for a market.
         ///
+        /// NOTE: Returns `amount` of collateral, i.e. the `CompleteSetMintFee` paid for buying the
+        /// set isn't refunded.
+        ///
         #[pallet::weight(
             T::WeightInfo::sell_complete_set(T::MaxCategories::get().into())
         )]
+        #[transactional]
         pub fn sell_complete_set(
             origin: OriginFor<T>,
             market_id: MarketIdOf<T>,
             #[pallet::compact] amount: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            Self::do_sell_complete_set(sender.clone(), sender, market_id, amount)
+        }
 
-            let market = T::MarketCommons::market(&market_id)?;
-            ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
-            Self::ensure_market_is_active(&market.period)?;
-
-            let market_account = Self::market_account(market_id);
-            ensure!(
-                CurrencyOf::<T>::free_balance(&market_account) >= amount,
-                "Market account does not have sufficient reserves.",
-            );
-
-            let assets = Self::outcome_assets(market_id, &market);
+        /// Destroys a complete set of outcome shares of the signer and transfers the collateral
+        /// to `beneficiary`.
+        #[pallet::weight(
+            T::WeightInfo::sell_complete_set(T::MaxCategories::get().into())
+        )]
+        #[transactional]
+        pub fn sell_complete_set_for(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            market_id: MarketIdOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::do_sell_complete_set(sender, beneficiary, market_id, amount)
+        }
 
-            // verify first.
-            for asset in assets.iter() {
-                // Ensures that the sender has sufficient amount of each
-                // share in the set.
+        /// Exchanges a complete set of `amount` `CombinatorialShare`s of `child_market_id` that
+        /// are conditioned on `parent_outcome` of `parent_market_id` back for the parent shares.
+        #[pallet::weight(
+            T::WeightInfo::sell_complete_set(T::MaxCategories::get().into())
+        )]
+        #[transactional]
+        pub fn sell_conditional_set(
+            origin: OriginFor<T>,
+            parent_market_id: MarketIdOf<T>,
+            parent_outcome: CategoryIndex,
+            child_market_id: MarketIdOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let (parent_asset, conditional_assets, child_market) =
+                Self::conditional_assets(parent_market_id, parent_outcome, child_market_id)?;
+            Self::ensure_conditional_market_is_active(&child_market_id, &child_market)?;
+            for asset in conditional_assets.iter() {
                 ensure!(
                     T::Shares::free_balance(*asset, &sender) >= amount,
-                    Error::<T>::InsufficientShareBalance,
+                    Error::<T>::InsufficientShareBalance
                 );
             }
 
-            // write last.
-            for asset in assets.iter() {
+            for asset in conditional_assets.iter() {
                 T::Shares::slash(*asset, &sender, amount);
             }
+            let escrow_account = Self::market_account(child_market_id);
+            T::Shares::transfer(parent_asset, &escrow_account, &sender, amount)?;
+            let parent_market = T::MarketCommons::market(&parent_market_id)?;
+            let parent_assets = Self::outcome_assets(parent_market_id, &parent_market);
+            Self::update_account_position(&sender, parent_market_id, &parent_assets);
+
+            Self::deposit_event(Event::SoldConditionalSet(
+                parent_market_id,
+                parent_outcome,
+                child_market_id,
+                sender,
+            ));
+            Ok(())
+        }
 
-            CurrencyOf::<T>::transfer(
-                &market_account,
-                &sender,
-                amount,
-                ExistenceRequirement::AllowDeath,
-            )?;
+        /// Redeems the `CombinatorialShare`s of the resolved market `child_market_id` that are
+        /// conditioned on `parent_outcome` of `parent_market_id` for parent shares held in escrow.
+        ///
+        /// Each share of the resolved child outcome pays one parent share. If the child market
+        /// resolved to `Invalid`, each conditional share pays an equal fraction of a parent share.
+        #[pallet::weight(
+            T::WeightInfo::sell_complete_set(T::MaxCategories::get().into())
+        )]
+        #[transactional]
+        pub fn redeem_conditional_shares(
+            origin: OriginFor<T>,
+            parent_market_id: MarketIdOf<T>,
+            parent_outcome: CategoryIndex,
+            child_market_id: MarketIdOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let (parent_asset, conditional_assets, child_market) =
+                Self::conditional_assets(parent_market_id, parent_outcome, child_market_id)?;
+            ensure!(child_market.status == MarketStatus::Resolved, Error::<T>::MarketIsNotResolved);
+            let resolved_outcome =
+                child_market.resolved_outcome.ok_or(Error::<T>::MarketIsNotResolved)?;
+            let categories: BalanceOf<T> = conditional_assets.len().saturated_into();
 
-            Self::deposit_event(Event::SoldCompleteSet(market_id, sender));
-            let assets_len: u32 = assets.len().saturated_into();
-            let max_cats: u32 = T::MaxCategories::get().into();
-            Self::calculate_actual_weight(&T::WeightInfo::sell_complete_set, assets_len, max_cats)
+            let mut payout = BalanceOf::<T>::zero();
+            for (child_outcome, asset) in conditional_assets.into_iter().enumerate() {
+                let balance = T::Shares::free_balance(asset, &sender);
+                if balance.is_zero() {
+                    continue;
+                }
+                let child_outcome: CategoryIndex = child_outcome.saturated_into();
+                let share_payout = match resolved_outcome {
+                    OutcomeReport::Categorical(winner) if winner == child_outcome => balance,
+                    OutcomeReport::Invalid => {
+                        balance.checked_div(&categories).ok_or(ArithmeticError::DivisionByZero)?
+                    }
+                    _ => Zero::zero(),
+                };
+                payout = payout.saturating_add(share_payout);
+                T::Shares::slash(asset, &sender, balance);
+            }
+            ensure!(!payout.is_zero(), Error::<T>::NoWinningBalance);
+
+            let escrow_account = Self::market_account(child_market_id);
+            T::Shares::transfer(parent_asset, &escrow_account, &sender, payout)?;
+            let parent_market = T::MarketCommons::market(&parent_market_id)?;
+            let parent_assets = Self::outcome_assets(parent_market_id, &parent_market);
+            Self::update_account_position(&sender, parent_market_id, &parent_assets);
+            Ok(())
+        }
+
+        /// Stakes `amount` on `outcome` in the global dispute of a market.
+        ///
+        /// The stake is reserved until the global dispute ends. Stakes on the winning outcome are
+        /// returned together with a share of the losing stakes proportional to their size, all
+        /// other stakes are slashed. An account can only stake on a single outcome per market,
+        /// but can increase its stake.
+        ///
+        /// The first vote has to stake at least `MinGlobalDisputeInitialStake`, all subsequent
+        /// voters need a total stake of at least `MinGlobalDisputeStake`.
+        ///
+        #[pallet::weight(T::WeightInfo::vote_on_global_dispute())]
+        pub fn vote_on_global_dispute(
+            origin: OriginFor<T>,
+            market_id: MarketIdOf<T>,
+            outcome: OutcomeReport,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(
+                GlobalDisputeEnds::<T>::contains_key(&market_id),
+                Error::<T>::GlobalDisputeNotStarted
+            );
+            let market = T::MarketCommons::market(&market_id)?;
+            Self::ensure_outcome_matches_market_type(&market, &outcome)?;
+
+            let stake = match GlobalDisputeVotes::<T>::get(&market_id, &sender) {
+                Some((voted_outcome, stake)) => {
+                    ensure!(voted_outcome == outcome, Error::<T>::GlobalDisputeVoteMismatch);
+                    stake.saturating_add(amount)
+                }
+                None => amount,
+            };
+            let min_stake = if GlobalDisputeVotes::<T>::iter_prefix(&market_id).next().is_none() {
+                T::MinGlobalDisputeInitialStake::get()
+            } else {
+                T::MinGlobalDisputeStake::get()
+            };
+            ensure!(stake >= min_stake, Error::<T>::GlobalDisputeStakeTooLow);
+            CurrencyOf::<T>::reserve_named(&RESERVE_ID, &sender, amount)?;
+            GlobalDisputeVotes::<T>::insert(&market_id, &sender, (outcome.clone(), stake));
+
+            Self::deposit_event(Event::GlobalDisputeVoted(market_id, sender, outcome, amount));
+            Ok(())
         }
     }
 
@@ -1086,18 +1880,47 @@ mod pallet {
         #[pallet::constant]
         type AdvisoryBond: Get<BalanceOf<Self>>;
 
-        type ApprovalOrigin: EnsureOrigin<Self::Origin>;
+        /// The number of blocks the advisory committee has to approve or reject a proposed
+        /// market. Afterwards, the market is removed and its bonds are returned.
+        #[pallet::constant]
+        type AdvisoryReviewPeriod: Get<Self::BlockNumber>;
 
-        /// See [`AuthorizedPalletApi`].
-        type Authorized: zrml_authorized::AuthorizedPalletApi<
-            AccountId = Self::AccountId,
-            Balance = BalanceOf<Self>,
-            BlockNumber = Self::BlockNumber,
+        /// If `true`, the oracle of a market may dispute its own report and the disputes of
+        /// others. Otherwise, disputes of the oracle are rejected.
+        #[pallet::constant]
+        type AllowOracleSelfDispute: Get<bool>;
+
+        /// If `true`, anyone can report the outcome of a market once the reporting period has
+        /// passed without a report of the oracle. Otherwise, only the oracle can report.
+        #[pallet::constant]
+        type AllowPublicReporting: Get<bool>;
+
+        /// The kinds of `MarketPeriod` markets may be created with. See [`MarketEndKinds`].
+        #[pallet::constant]
+        type AllowedMarketEndKinds: Get<MarketEndKinds>;
+
+        type ApprovalOrigin: EnsureOrigin<Self::Origin>;
+
+        /// See [`AuthorizedPalletApi`].
+        type Authorized: zrml_authorized::AuthorizedPalletApi<
+            AccountId = Self::AccountId,
+            Balance = BalanceOf<Self>,
+            BlockNumber = Self::BlockNumber,
             MarketId = MarketIdOf<Self>,
             Moment = MomentOf<Self>,
             Origin = Self::Origin,
         >;
 
+        /// The share of the advisory bond that is slashed and handed to `Slash` when the creator
+        /// cancels a proposed market. The rest of the bond is returned to the creator.
+        #[pallet::constant]
+        type CancellationPenalty: Get<Perbill>;
+
+        /// The share of `amount` that is charged on top of the collateral when buying `amount`
+        /// complete sets. The fee is handed to `Slash` and not refunded when selling the sets.
+        #[pallet::constant]
+        type CompleteSetMintFee: Get<Perbill>;
+
         /// See [`CourtPalletApi`].
         type Court: zrml_court::CourtPalletApi<
             AccountId = Self::AccountId,
@@ -1121,9 +1944,18 @@ mod pallet {
         #[pallet::constant]
         type DisputePeriod: Get<Self::BlockNumber>;
 
+        /// Determines how the outcome of a disputed categorical market is derived from its
+        /// disputes. See [`DisputeResolutionRule`].
+        #[pallet::constant]
+        type DisputeResolutionRule: Get<DisputeResolutionRule>;
+
         /// Event
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
+        /// The number of blocks a global dispute remains open for votes.
+        #[pallet::constant]
+        type GlobalDisputePeriod: Get<Self::BlockNumber>;
+
         type LiquidityMining: LiquidityMiningPalletApi<
             AccountId = Self::AccountId,
             Balance = BalanceOf<Self>,
@@ -1137,6 +1969,11 @@ mod pallet {
             BlockNumber = Self::BlockNumber,
         >;
 
+        /// The maximum number of entries of additional metadata that the creator can attach to a
+        /// market with `attach_market_evidence`.
+        #[pallet::constant]
+        type MaxAdditionalMetadata: Get<u32>;
+
         /// The maximum number of categories available for categorical markets.
         #[pallet::constant]
         type MaxCategories: Get<u16>;
@@ -1145,18 +1982,79 @@ mod pallet {
         #[pallet::constant]
         type MaxSubsidyPeriod: Get<MomentOf<Self>>;
 
-        /// The minimum number of categories available for categorical markets.
+        /// The minimum number of categories available for categorical markets. Values below two
+        /// are ignored, since a market needs at least two outcomes to be meaningful.
         #[pallet::constant]
         type MinCategories: Get<u16>;
 
+        /// The lower bound of the bond that has to be reserved for any dispute, regardless of
+        /// `DisputeBond` and `DisputeFactor`.
+        #[pallet::constant]
+        type MinEffectiveDisputeBond: Get<BalanceOf<Self>>;
+
+        /// The minimum stake of the first vote in a global dispute. Voting is only opened once
+        /// an account stakes at least this amount.
+        #[pallet::constant]
+        type MinGlobalDisputeInitialStake: Get<BalanceOf<Self>>;
+
+        /// The minimum total stake of any account in a global dispute after voting was opened.
+        #[pallet::constant]
+        type MinGlobalDisputeStake: Get<BalanceOf<Self>>;
+
         /// The shortest period of collecting subsidy for a Rikiddo market.
         #[pallet::constant]
         type MinSubsidyPeriod: Get<MomentOf<Self>>;
 
+        /// Restricts the values that disputes of scalar markets may propose relative to the
+        /// report or the previous dispute. See [`ScalarDisputeStep`].
+        #[pallet::constant]
+        type MonotonicScalarDisputes: Get<ScalarDisputeStep>;
+
+        /// The maximum number of markets that can be in the `Disputed` state at the same time.
+        #[pallet::constant]
+        type MaxConcurrentDisputedMarkets: Get<u32>;
+
+        /// The maximum number of outcome share accounts that `admin_destroy_market` can remove
+        /// within a single block.
+        #[pallet::constant]
+        type MaxDestroyedShareAccounts: Get<u32>;
+
         /// The maximum number of disputes allowed on any single market.
         #[pallet::constant]
         type MaxDisputes: Get<u32>;
 
+        /// The maximum number of disputes a single account can submit for a single market.
+        #[pallet::constant]
+        type MaxDisputesPerAccount: Get<u32>;
+
+        /// The maximum number of expired proposals that are removed in a single block. The
+        /// remaining proposals are removed in the following blocks.
+        #[pallet::constant]
+        type MaxExpiredProposalsPerBlock: Get<u32>;
+
+        /// The maximum number of bytes of an `OutcomeDescriptor::Label`.
+        #[pallet::constant]
+        type MaxOutcomeLabelLength: Get<u32>;
+
+        /// The maximum number of pools that can be deployed with `deploy_swap_pools_batch`.
+        #[pallet::constant]
+        type MaxPoolDeploymentsPerBatch: Get<u32>;
+
+        /// The number of times the automatic resolution of a market may fail before the market
+        /// is stalled and has to be resolved with `admin_move_market_to_resolved`.
+        #[pallet::constant]
+        type MaxResolutionFailures: Get<u32>;
+
+        /// The maximum number of bytes of metadata that can be stored with a single market,
+        /// summed over all of its metadata fields.
+        #[pallet::constant]
+        type MaxTotalMetadataBytes: Get<u32>;
+
+        /// The expected time between two blocks in milliseconds. Used to convert block numbers
+        /// into timestamps, e.g. the `ReportingPeriod` of timestamp markets.
+        #[pallet::constant]
+        type MillisecondsPerBlock: Get<u32>;
+
         /// Shares
         type Shares: ZeitgeistMultiReservableCurrency<
             Self::AccountId,
@@ -1169,14 +2067,51 @@ mod pallet {
         type PalletId: Get<PalletId>;
 
         /// The base amount of currency that must be bonded to ensure the oracle reports
-        ///  in a timely manner.
+        ///  in a timely manner. Markets may choose a higher bond on creation, but never a lower
+        ///  one.
         #[pallet::constant]
         type OracleBond: Get<BalanceOf<Self>>;
 
-        /// The number of blocks the reporting period remains open.
+        /// The number of blocks after the end of a market in which only the oracle may report.
+        /// Markets may choose a different grace period on creation, up to `ReportingPeriod`.
+        /// Converted into milliseconds using `MillisecondsPerBlock` for timestamp markets.
+        #[pallet::constant]
+        type OracleGracePeriod: Get<u32>;
+
+        /// If set, the weights of a pool deployed for a market, including the weight of the base
+        /// asset, must sum to this value. Keeps the weights of all pools normalized, e.g. to
+        /// encode prior probabilities.
+        #[pallet::constant]
+        type PoolWeightsSum: Get<Option<u128>>;
+
+        /// If `true`, winners are paid out pro-rata from the remaining collateral when the
+        /// market account is unable to cover all winning shares. Otherwise, redeeming fails
+        /// with `InsufficientFundsInMarketAccount`.
+        #[pallet::constant]
+        type ProRataRedemption: Get<bool>;
+
+        /// The share of every redeemed payout that is handed to `Slash` (e.g. the treasury). It is
+        /// charged in addition to the `creator_fee` of the market.
+        #[pallet::constant]
+        type RedemptionTreasuryFee: Get<Perbill>;
+
+        /// The number of blocks the reporting period remains open, i.e. the oracle may correct its
+        /// report. Converted into milliseconds using `MillisecondsPerBlock` for timestamp
+        /// markets.
         #[pallet::constant]
         type ReportingPeriod: Get<u32>;
 
+        /// The reward paid from the pallet account to the account that resolves an overdue
+        /// market with `resolve`. Funded with the bonds slashed on resolution, which top up the
+        /// pallet account to this amount before the rest is handed to `Slash`.
+        #[pallet::constant]
+        type ResolutionReward: Get<BalanceOf<Self>>;
+
+        /// Determines how the outcome of a disputed scalar market is derived from the report
+        /// and its disputes. See [`ScalarResolutionPolicy`].
+        #[pallet::constant]
+        type ScalarResolutionPolicy: Get<ScalarResolutionPolicy>;
+
         /// See [`SimpleDisputesPalletApi`].
         type SimpleDisputes: DisputeApi<
             AccountId = Self::AccountId,
@@ -1190,6 +2125,11 @@ mod pallet {
         /// Slash
         type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+        /// The share of the bonds slashed in a disputed market that is handed to `Slash` (e.g.
+        /// the treasury). The rest is split evenly between the correct reporters.
+        #[pallet::constant]
+        type SlashSplit: Get<Perbill>;
+
         /// Swaps pallet API
         type Swaps: Swaps<Self::AccountId, Balance = BalanceOf<Self>, MarketId = MarketIdOf<Self>>;
 
@@ -1198,21 +2138,58 @@ mod pallet {
         #[pallet::constant]
         type ValidityBond: Get<BalanceOf<Self>>;
 
+        /// Determines whether the validity bond is returned or slashed when a market is resolved.
+        /// See [`ValidityBondPolicy`].
+        #[pallet::constant]
+        type ValidityBondPolicy: Get<ValidityBondPolicy>;
+
         /// Weights generated by benchmarks
         type WeightInfo: WeightInfoZeitgeist;
+
+        /// If `true`, the collateral of a categorical market that resolved to an outcome without
+        /// any supply is refunded pro-rata to the holders of the other outcome shares, since it
+        /// can't be redeemed otherwise. Otherwise, it stays in the market account.
+        #[pallet::constant]
+        type ZeroSupplyRefund: Get<bool>;
     }
 
     #[pallet::error]
     pub enum Error<T> {
+        /// The account has already submitted `MaxDisputesPerAccount` disputes for the market.
+        AccountDisputeLimitReached,
+        /// A market can't be conditioned on one of its own outcomes.
+        CannotConditionOnSameMarket,
         /// Someone is trying to call `dispute` with the same outcome that is currently
         /// registered on-chain.
         CannotDisputeSameOutcome,
+        /// The disputed value of a scalar market violates `MonotonicScalarDisputes`.
+        DisputeValueOutOfStep,
+        /// The global dispute of the market has already been started.
+        GlobalDisputeAlreadyStarted,
+        /// The market has no running global dispute.
+        GlobalDisputeNotStarted,
+        /// The stake is below `MinGlobalDisputeInitialStake` for the first vote of a global
+        /// dispute or below `MinGlobalDisputeStake` for any other vote.
+        GlobalDisputeStakeTooLow,
+        /// The account has already staked on another outcome of the global dispute.
+        GlobalDisputeVoteMismatch,
+        /// The sender does not have enough free balance to reserve the bonds of the market.
+        InsufficientBalanceForBond,
         /// Market account does not have enough funds to pay out.
         InsufficientFundsInMarketAccount,
         /// Sender does not have enough share balance.
         InsufficientShareBalance,
+        /// The new end of a market isn't after its start or doesn't match the kind of its period.
+        InvalidMarketEnd,
+        /// The `max_disputes` specified on market creation is zero or higher than `MaxDisputes`.
+        InvalidMaxDisputes,
         /// An invalid Hash was included in a multihash parameter
         InvalidMultihash,
+        /// The `oracle_grace_period` specified on market creation is higher than
+        /// `ReportingPeriod`.
+        InvalidOracleGracePeriod,
+        /// An outcome descriptor is malformed.
+        InvalidOutcomeDescriptor,
         /// An invalid market type was found.
         InvalidMarketType,
         /// An operation is requested that is unsupported for the given scoring rule.
@@ -1223,16 +2200,26 @@ mod pallet {
         OutcomeOutOfRange,
         /// Market is already reported on.
         MarketAlreadyReported,
+        /// Market is already resolved.
+        MarketAlreadyResolved,
+        /// The period of the market is not of a kind allowed by `AllowedMarketEndKinds`.
+        MarketEndKindNotAllowed,
         /// Market was expected to be active.
         MarketIsNotActive,
+        /// Market was expected to be active or closed.
+        MarketIsNotActiveOrClosed,
         /// Market was expected to be closed.
         MarketIsNotClosed,
         /// A market in subsidy collection phase was expected.
         MarketIsNotCollectingSubsidy,
+        /// A disputed market was expected.
+        MarketIsNotDisputed,
         /// A proposed market was expected.
         MarketIsNotProposed,
         /// A reported market was expected.
         MarketIsNotReported,
+        /// A reported or disputed market was expected.
+        MarketIsNotReportedOrDisputed,
         /// A resolved market was expected.
         MarketIsNotResolved,
         /// The market is not reported on.
@@ -1243,20 +2230,69 @@ mod pallet {
         MarketStartTooLate,
         /// The maximum number of disputes has been reached.
         MaxDisputesReached,
+        /// The market has to be disputed `MaxDisputes` times before a global dispute can start.
+        MaxDisputesNotReached,
+        /// The metadata of the market exceeds `MaxTotalMetadataBytes` or `MaxMetadataLength`.
+        MetadataTooLong,
+        /// The asset is not an outcome asset of a market.
+        NotAnOutcomeAsset,
         /// The number of assets specified in a parameter does not match the total asset count.
         NotEnoughAssets,
         /// The number of categories for a categorical market is too low
         NotEnoughCategories,
+        /// The account didn't dispute the market.
+        NoDisputeBond,
         /// The user has no winning balance.
         NoWinningBalance,
+        /// The oracle bond specified on market creation is lower than `OracleBond`.
+        OracleBondTooLow,
+        /// The oracle of a market can't dispute it unless `AllowOracleSelfDispute` is set.
+        OracleCannotDispute,
+        /// The number of outcome descriptors doesn't match the number of categories.
+        OutcomeDescriptorsLengthMismatch,
         /// Submitted outcome does not match market type
         OutcomeMismatch,
+        /// The assets of a pool don't match the outcomes of the market plus the base asset.
+        PoolAssetCountMismatch,
+        /// The pool of the market doesn't contain an asset it was expected to contain.
+        PoolDoesNotContainAsset,
+        /// The pool does not exist.
+        PoolDoesNotExist,
+        /// The review deadline of the proposed market hasn't passed yet.
+        ProposalNotExpired,
+        /// The weights of the pool don't sum to `PoolWeightsSum`.
+        PoolWeightsSumInvalid,
         /// The report is not coming from designated oracle.
         ReporterNotOracle,
+        /// The market is scheduled for resolution and its report can't be corrected anymore.
+        ResolutionDue,
+        /// The dispute period of the market hasn't ended yet.
+        ResolutionNotDue,
+        /// The new review deadline of a proposed market must be later than the current one.
+        ReviewDeadlineNotExtended,
+        /// The sender is not the creator of the market.
+        SenderNotCreator,
+        /// Fewer complete sets than requested were assembled from the pool.
+        SlippageExceeded,
         /// A swap pool already exists for this market.
         SwapPoolExists,
         /// Too many categories for a categorical market
         TooManyCategories,
+        /// `MaxConcurrentDisputedMarkets` markets are already disputed.
+        TooManyDisputedMarkets,
+        /// More than `MaxPoolDeploymentsPerBatch` pools are deployed at once.
+        TooManyPoolDeployments,
+        /// The outcome shares of the market are held by more than `MaxDestroyedShareAccounts`
+        /// accounts.
+        TooManyHoldersToDestroy,
+        /// The market already holds `MaxAdditionalMetadata` entries of additional metadata.
+        TooMuchAdditionalMetadata,
+        /// The validity bond specified on market creation is lower than `ValidityBond`.
+        ValidityBondTooLow,
+        /// The number of weights doesn't match the number of outcome assets plus the base asset.
+        WeightsLengthMismatch,
+        /// The amount is zero.
+        ZeroAmount,
     }
 
     #[pallet::event]
@@ -1269,26 +2305,96 @@ mod pallet {
         BadOnInitialize,
         /// A complete set of shares has been bought \[market_id, buyer\]
         BoughtCompleteSet(MarketIdOf<T>, <T as frame_system::Config>::AccountId),
-        /// A market has been approved \[market_id, new_market_status\]
-        MarketApproved(MarketIdOf<T>, MarketStatus),
+        /// Complete sets of shares have been bought from the market's pool \[market_id, sets,
+        /// buyer\]
+        BoughtCompleteSetViaPool(
+            MarketIdOf<T>,
+            BalanceOf<T>,
+            <T as frame_system::Config>::AccountId,
+        ),
+        /// A conditional set of shares has been bought \[parent_market_id, parent_outcome,
+        /// child_market_id, buyer\]
+        BoughtConditionalSet(
+            MarketIdOf<T>,
+            CategoryIndex,
+            MarketIdOf<T>,
+            <T as frame_system::Config>::AccountId,
+        ),
+        /// A disputer claimed its settled dispute bonds \[market_id, disputer, returned, slashed\]
+        DisputeBondClaimed(
+            MarketIdOf<T>,
+            <T as frame_system::Config>::AccountId,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// A market has been disputed once more. The first dispute has index zero
+        /// \[market_id, dispute_index, outcome, bond\]
+        DisputeEscalated(MarketIdOf<T>, u32, OutcomeReport, BalanceOf<T>),
+        /// A global dispute has been started \[market_id, end_block\]
+        GlobalDisputeStarted(MarketIdOf<T>, T::BlockNumber),
+        /// Stake has been added to a global dispute \[market_id, voter, outcome, amount\]
+        GlobalDisputeVoted(
+            MarketIdOf<T>,
+            <T as frame_system::Config>::AccountId,
+            OutcomeReport,
+            BalanceOf<T>,
+        ),
+        /// The collateral held by the market account doesn't match the outstanding complete sets
+        /// of the market \[market_id, escrow, outstanding_complete_sets\]
+        MarketAccountingMismatch(MarketIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// A market has been approved. The approver is only known if the approval origin was
+        /// signed. \[market_id, new_market_status, approver\]
+        MarketApproved(MarketIdOf<T>, MarketStatus, Option<<T as frame_system::Config>::AccountId>),
         /// A market has been created \[market_id, creator\]
         MarketCreated(MarketIdOf<T>, Market<T::AccountId, T::BlockNumber, MomentOf<T>>),
+        /// The end of a market has been set by the `ApprovalOrigin` \[market_id, end\]
+        MarketEndSet(MarketIdOf<T>, MarketEnd<T::BlockNumber, MomentOf<T>>),
+        /// The creator attached evidence to a market \[market_id, evidence\]
+        MarketEvidenceAttached(MarketIdOf<T>, MultiHash),
         /// A market was started after gathering enough subsidy. \[market_id, new_market_status\]
         MarketStartedWithSubsidy(MarketIdOf<T>, MarketStatus),
         /// A market was discarded after failing to gather enough subsidy. \[market_id, new_market_status\]
         MarketInsufficientSubsidy(MarketIdOf<T>, MarketStatus),
         /// A pending market has been cancelled. \[market_id\]
         MarketCancelled(MarketIdOf<T>),
+        /// A proposed market wasn't reviewed until its deadline and has been removed \[market_id\]
+        MarketExpired(MarketIdOf<T>),
         /// A market has been disputed \[market_id, new_market_status, new_outcome\]
         MarketDisputed(MarketIdOf<T>, MarketStatus, MarketDispute<T::AccountId, T::BlockNumber>),
-        /// A pending market has been rejected as invalid. \[market_id\]
-        MarketRejected(MarketIdOf<T>),
+        /// A market has been pointed to another pool \[market_id, old_pool_id, new_pool_id\]
+        MarketPoolMigrated(MarketIdOf<T>, PoolId, PoolId),
+        /// A pending market has been rejected as invalid. The rejector is only known if the
+        /// approval origin was signed. \[market_id, rejector\]
+        MarketRejected(MarketIdOf<T>, Option<<T as frame_system::Config>::AccountId>),
         /// A market has been reported on \[market_id, new_market_status, reported_outcome\]
         MarketReported(MarketIdOf<T>, MarketStatus, Report<T::AccountId, T::BlockNumber>),
+        /// The review deadline of a proposed market has been postponed \[market_id, new_deadline\]
+        MarketReviewExtended(MarketIdOf<T>, T::BlockNumber),
         /// A market has been resolved \[market_id, new_market_status, real_outcome\]
         MarketResolved(MarketIdOf<T>, MarketStatus, OutcomeReport),
+        /// A market could not be resolved automatically and was skipped \[market_id\]
+        MarketResolutionFailed(MarketIdOf<T>),
+        /// A market failed to resolve `MaxResolutionFailures` times and isn't resolved
+        /// automatically anymore \[market_id\]
+        MarketResolutionStalled(MarketIdOf<T>),
+        /// A pool has been deployed as part of a batch \[market_id, pool_id\]
+        PoolDeployed(MarketIdOf<T>, PoolId),
+        /// A pool of a batch couldn't be deployed \[market_id, error\]
+        PoolDeploymentFailed(MarketIdOf<T>, DispatchError),
+        /// An account doesn't hold any outcome shares of a market anymore \[market_id, account\]
+        PositionClosed(MarketIdOf<T>, <T as frame_system::Config>::AccountId),
+        /// An account acquired outcome shares of a market \[market_id, account\]
+        PositionOpened(MarketIdOf<T>, <T as frame_system::Config>::AccountId),
         /// A complete set of shares has been sold \[market_id, seller\]
         SoldCompleteSet(MarketIdOf<T>, <T as frame_system::Config>::AccountId),
+        /// A conditional set of shares has been sold \[parent_market_id, parent_outcome,
+        /// child_market_id, seller\]
+        SoldConditionalSet(
+            MarketIdOf<T>,
+            CategoryIndex,
+            MarketIdOf<T>,
+            <T as frame_system::Config>::AccountId,
+        ),
     }
 
     #[pallet::hooks]
@@ -1296,11 +2402,23 @@ mod pallet {
         fn on_initialize(now: T::BlockNumber) -> Weight {
             let mut total_weight: Weight =
                 Self::process_subsidy_collecting_markets(now, T::MarketCommons::now());
+            total_weight = total_weight.saturating_add(Self::remove_expired_proposals(now));
 
             with_transaction(|| {
                 let output = Self::resolution_manager(now, |market_id, market| {
-                    let weight = Self::on_resolution(market_id, market)?;
-                    total_weight = total_weight.saturating_add(weight);
+                    // A market that can't be resolved must not stop the others from resolving.
+                    match Self::on_resolution_transactional(market_id, market) {
+                        Ok(weight) => total_weight = total_weight.saturating_add(weight),
+                        Err(err) => {
+                            log::error!(
+                                "Market {:?} could not be resolved. Error: {:?}",
+                                market_id,
+                                err
+                            );
+                            Self::deposit_event(Event::MarketResolutionFailed(*market_id));
+                            Self::record_failed_resolution(market_id);
+                        }
+                    }
                     Ok(())
                 });
 
@@ -1316,11 +2434,50 @@ mod pallet {
 
             total_weight
         }
+
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::clean_up_block_indices(now, remaining_weight)
+        }
     }
 
     #[pallet::pallet]
     pub struct Pallet<T>(PhantomData<T>);
 
+    /// The markets in which an account holds outcome shares.
+    ///
+    /// Updated whenever shares are bought, sold or redeemed through this pallet. Shares that
+    /// change hands elsewhere (transfers, swaps) are accounted for on the next update.
+    #[pallet::storage]
+    pub type AccountPositions<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, MarketIdOf<T>, ()>;
+
+    /// The evidence that the creators attached to their markets after creation, in the order it
+    /// was attached.
+    #[pallet::storage]
+    pub type AdditionalMetadata<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, Vec<BoundedMetadata>, ValueQuery>;
+
+    /// The seed collateral reserved from the creator and the weights of the pools that are
+    /// deployed as soon as their market is active. Markets leave the map once their pool is
+    /// deployed or they are removed.
+    #[pallet::storage]
+    pub type AutoDeployPools<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, (BalanceOf<T>, Vec<u128>)>;
+
+    /// The next block of `MarketIdsPerReportBlock` and `MarketIdsPerDisputeBlock` that is
+    /// inspected by the `on_idle` cleanup.
+    #[pallet::storage]
+    pub type CleanupCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// The disputers that already claimed their dispute bonds of a resolved market.
+    #[pallet::storage]
+    pub type DisputeBondClaims<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, MarketIdOf<T>, Blake2_128Concat, T::AccountId, ()>;
+
+    /// The number of markets that are currently in the `Disputed` state.
+    #[pallet::storage]
+    pub type DisputedMarketsCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     /// For each market, this holds the dispute information for each dispute that's
     /// been issued.
     #[pallet::storage]
@@ -1332,17 +2489,57 @@ mod pallet {
         ValueQuery,
     >;
 
+    /// The block at which the global dispute of a market ends. A market only ends up here while
+    /// its global dispute is running.
+    #[pallet::storage]
+    pub type GlobalDisputeEnds<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, T::BlockNumber>;
+
+    /// The outcome and the total stake of each account that voted in the global dispute of a
+    /// market.
+    #[pallet::storage]
+    pub type GlobalDisputeVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        MarketIdOf<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        (OutcomeReport, BalanceOf<T>),
+    >;
+
+    /// The bonds that were reserved from the creator of a market when it was created. These are
+    /// returned or slashed instead of the configured bonds, which may have changed since.
+    #[pallet::storage]
+    pub type MarketBonds<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, BondBreakdown<BalanceOf<T>>>;
+
     /// A mapping of market identifiers to the block they were disputed at.
     /// A market only ends up here if it was disputed.
     #[pallet::storage]
     pub type MarketIdsPerDisputeBlock<T: Config> =
         StorageMap<_, Twox64Concat, T::BlockNumber, Vec<MarketIdOf<T>>, ValueQuery>;
 
+    /// A mapping of market identifiers to the block their global dispute ends at.
+    #[pallet::storage]
+    pub type MarketIdsPerGlobalDisputeEnd<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<MarketIdOf<T>>, ValueQuery>;
+
     /// A mapping of market identifiers to the block that they were reported on.
     #[pallet::storage]
     pub type MarketIdsPerReportBlock<T: Config> =
         StorageMap<_, Twox64Concat, T::BlockNumber, Vec<MarketIdOf<T>>, ValueQuery>;
 
+    /// A mapping of proposed market identifiers to the block their review deadline ends at.
+    #[pallet::storage]
+    pub type MarketIdsPerReviewDeadline<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<MarketIdOf<T>>, ValueQuery>;
+
+    /// The markets that an account is the oracle of. Markets leave the index once they're
+    /// resolved or removed.
+    #[pallet::storage]
+    pub type MarketsByOracle<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, MarketIdOf<T>, ()>;
+
     /// Contains a list of all markets that are currently collecting subsidy and the deadline.
     // All the values are "cached" here. Results in data duplication, but speeds up the iteration
     // over every market significantly (otherwise 25µs per relevant market per block).
@@ -1350,6 +2547,29 @@ mod pallet {
     pub type MarketsCollectingSubsidy<T: Config> =
         StorageValue<_, Vec<SubsidyUntil<T::BlockNumber, MomentOf<T>, MarketIdOf<T>>>, ValueQuery>;
 
+    /// The descriptors of the outcomes of categorical markets, indexed by category. Only markets
+    /// that were created with outcome descriptors are listed here.
+    #[pallet::storage]
+    pub type OutcomeDescriptors<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, Vec<OutcomeDescriptor>>;
+
+    /// The oracle grace periods of the markets that were created with one. Other markets use
+    /// `OracleGracePeriod`.
+    #[pallet::storage]
+    pub type OracleGracePeriods<T: Config> = StorageMap<_, Blake2_128Concat, MarketIdOf<T>, u32>;
+
+    /// The number of times the automatic resolution of a market failed. Markets leave the map
+    /// once they're resolved or stalled.
+    #[pallet::storage]
+    pub type ResolutionFailures<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, u32, ValueQuery>;
+
+    /// The block until which the advisory committee has to review a proposed market. A market
+    /// only ends up here while it's proposed.
+    #[pallet::storage]
+    pub type ReviewDeadlines<T: Config> =
+        StorageMap<_, Blake2_128Concat, MarketIdOf<T>, T::BlockNumber>;
+
     impl<T: Config> Pallet<T> {
         pub fn outcome_assets(
             market_id: MarketIdOf<T>,
@@ -1376,6 +2596,141 @@ mod pallet {
             T::PalletId::get().into_sub_account(market_id.saturated_into::<u128>())
         }
 
+        // Transfers the existential deposit from `creator` to the account of `market_id`, so that
+        // complete sets of any size can be bought from the start.
+        fn fund_market_account(creator: &T::AccountId, market_id: MarketIdOf<T>) -> DispatchResult {
+            let market_account = Self::market_account(market_id);
+            let missing = CurrencyOf::<T>::minimum_balance()
+                .saturating_sub(CurrencyOf::<T>::free_balance(&market_account));
+            if missing.is_zero() {
+                return Ok(());
+            }
+            CurrencyOf::<T>::transfer(
+                creator,
+                &market_account,
+                missing,
+                ExistenceRequirement::KeepAlive,
+            )
+        }
+
+        // Returns the existential deposit transferred by `fund_market_account` to `creator` once
+        // `market_id` is removed, unless that would leave less than the existential deposit of
+        // other funds in the market account.
+        fn refund_market_account(
+            market_id: &MarketIdOf<T>,
+            creator: &T::AccountId,
+        ) -> DispatchResult {
+            let market_account = Self::market_account(*market_id);
+            let free = CurrencyOf::<T>::free_balance(&market_account);
+            let deposit = CurrencyOf::<T>::minimum_balance().min(free);
+            let remaining = free.saturating_sub(deposit);
+            if deposit.is_zero()
+                || (!remaining.is_zero() && remaining < CurrencyOf::<T>::minimum_balance())
+            {
+                return Ok(());
+            }
+            CurrencyOf::<T>::transfer(
+                &market_account,
+                creator,
+                deposit,
+                Self::market_account_existence_requirement(&market_account, deposit),
+            )
+        }
+
+        // The number of disputes allowed on `market`, which is `MaxDisputes` unless the market
+        // was created with a lower limit.
+        fn max_disputes_of(market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>) -> u32 {
+            market.max_disputes.unwrap_or_else(T::MaxDisputes::get)
+        }
+
+        // The market account must stay alive until all of its collateral is paid out, otherwise
+        // the remaining collateral would be lost as dust.
+        fn market_account_existence_requirement(
+            market_account: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> ExistenceRequirement {
+            if amount >= CurrencyOf::<T>::free_balance(market_account) {
+                ExistenceRequirement::AllowDeath
+            } else {
+                ExistenceRequirement::KeepAlive
+            }
+        }
+
+        // Walks `MarketIdsPerReportBlock` and `MarketIdsPerDisputeBlock` from `CleanupCursor` up
+        // to the last block handled by `resolution_manager`, resolving overdue markets and
+        // removing entries that don't point to pending markets anymore. Stops at the first block
+        // that can't be handled within `remaining_weight`.
+        fn clean_up_block_indices(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let dispute_period = T::DisputePeriod::get();
+            // Reading and writing the cursor.
+            let mut total_weight = db_weight.reads_writes(1, 1);
+            if now <= dispute_period || total_weight > remaining_weight {
+                return 0;
+            }
+
+            let last_block = now.saturating_sub(dispute_period);
+            let max_market_weight = db_weight.reads(2).saturating_add(
+                T::WeightInfo::internal_resolve_categorical_disputed(
+                    0,
+                    0,
+                    T::MaxCategories::get().into(),
+                    T::MaxDisputes::get(),
+                ),
+            );
+            let mut cursor = CleanupCursor::<T>::get();
+
+            while cursor <= last_block {
+                let lookup_weight = db_weight.reads(2);
+                if total_weight.saturating_add(lookup_weight) > remaining_weight {
+                    break;
+                }
+                let report_ids = MarketIdsPerReportBlock::<T>::get(&cursor);
+                let dispute_ids = MarketIdsPerDisputeBlock::<T>::get(&cursor);
+                total_weight = total_weight.saturating_add(lookup_weight);
+
+                let market_count: Weight =
+                    report_ids.len().saturating_add(dispute_ids.len()).saturated_into();
+                let max_block_weight = db_weight
+                    .writes(2)
+                    .saturating_add(max_market_weight.saturating_mul(market_count));
+                if total_weight.saturating_add(max_block_weight) > remaining_weight {
+                    break;
+                }
+
+                let mut retain = |ids: Vec<MarketIdOf<T>>, status| {
+                    let mut pending = Vec::new();
+                    for market_id in ids {
+                        let (keep, weight) =
+                            Self::resolve_overdue_market(&cursor, &market_id, status);
+                        total_weight = total_weight.saturating_add(weight);
+                        if keep {
+                            pending.push(market_id);
+                        }
+                    }
+                    pending
+                };
+                let report_ids = retain(report_ids, MarketStatus::Reported);
+                let dispute_ids = retain(dispute_ids, MarketStatus::Disputed);
+
+                if report_ids.is_empty() {
+                    MarketIdsPerReportBlock::<T>::remove(&cursor);
+                } else {
+                    MarketIdsPerReportBlock::<T>::insert(&cursor, report_ids);
+                }
+                if dispute_ids.is_empty() {
+                    MarketIdsPerDisputeBlock::<T>::remove(&cursor);
+                } else {
+                    MarketIdsPerDisputeBlock::<T>::insert(&cursor, dispute_ids);
+                }
+                total_weight = total_weight.saturating_add(db_weight.writes(2));
+                cursor = cursor.saturating_add(1u32.into());
+            }
+
+            CleanupCursor::<T>::put(cursor);
+            total_weight
+        }
+
         /// Clears this market from being stored for automatic resolution.
         fn clear_auto_resolve(market_id: &MarketIdOf<T>) -> DispatchResult {
             let market = T::MarketCommons::market(market_id)?;
@@ -1395,20 +2750,175 @@ mod pallet {
                         remove_item::<MarketIdOf<T>>(ids, market_id);
                     });
                 }
+                if let Some(end) = GlobalDisputeEnds::<T>::get(market_id) {
+                    MarketIdsPerGlobalDisputeEnd::<T>::mutate(&end, |ids| {
+                        remove_item::<MarketIdOf<T>>(ids, market_id);
+                    });
+                }
             }
 
             Ok(())
         }
 
+        // Ends the period of `market_id` at the current block or time. Markets that already
+        // ended are left untouched.
+        fn close_market(market_id: &MarketIdOf<T>) -> DispatchResult {
+            T::MarketCommons::mutate_market(market_id, |m| {
+                m.period = match m.period {
+                    MarketPeriod::Block(ref range) => {
+                        let current_block = <frame_system::Pallet<T>>::block_number();
+                        MarketPeriod::Block(range.start..range.end.min(current_block))
+                    }
+                    MarketPeriod::Timestamp(ref range) => {
+                        let now = T::MarketCommons::now();
+                        MarketPeriod::Timestamp(range.start..range.end.min(now))
+                    }
+                };
+                Ok(())
+            })
+        }
+
+        // Compares the collateral held by the account of `market_id`, excluding its existential
+        // deposit, with the outstanding complete sets of the market, which must be equal until the
+        // market is resolved. A discrepancy can't be fixed by the caller, so it's reported with
+        // `MarketAccountingMismatch` instead of failing.
+        fn reconcile_market_escrow(market_id: &MarketIdOf<T>) -> DispatchResult {
+            let market_account = Self::market_account(*market_id);
+            let escrow = CurrencyOf::<T>::free_balance(&market_account)
+                .saturating_sub(CurrencyOf::<T>::minimum_balance());
+            let outstanding = Self::outstanding_complete_sets(market_id)?;
+            if escrow != outstanding {
+                log::warn!(
+                    "Market {:?} holds {:?} collateral for {:?} outstanding complete sets",
+                    market_id,
+                    escrow,
+                    outstanding
+                );
+                Self::deposit_event(Event::MarketAccountingMismatch(
+                    *market_id,
+                    escrow,
+                    outstanding,
+                ));
+            }
+            Ok(())
+        }
+
+        // Returns the seed collateral of a pool that won't be deployed automatically anymore,
+        // because its market is removed.
+        fn release_auto_deploy_pool_seed(market_id: &MarketIdOf<T>, creator: &T::AccountId) {
+            if let Some((seed, _)) = AutoDeployPools::<T>::take(market_id) {
+                CurrencyOf::<T>::unreserve_named(&RESERVE_ID, creator, seed);
+            }
+        }
+
+        fn clear_review_deadline(market_id: &MarketIdOf<T>) {
+            if let Some(deadline) = ReviewDeadlines::<T>::take(market_id) {
+                MarketIdsPerReviewDeadline::<T>::mutate(&deadline, |ids| {
+                    remove_item::<MarketIdOf<T>>(ids, market_id);
+                });
+            }
+        }
+
+        // Returns the parent outcome asset, the `CombinatorialShare`s of the categorical market
+        // `child_market_id` that are conditioned on it and the child market itself.
+        fn conditional_assets(
+            parent_market_id: MarketIdOf<T>,
+            parent_outcome: CategoryIndex,
+            child_market_id: MarketIdOf<T>,
+        ) -> Result<
+            (
+                Asset<MarketIdOf<T>>,
+                Vec<Asset<MarketIdOf<T>>>,
+                Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            ),
+            DispatchError,
+        > {
+            ensure!(parent_market_id != child_market_id, Error::<T>::CannotConditionOnSameMarket);
+            let parent_market = T::MarketCommons::market(&parent_market_id)?;
+            let parent_categories = match parent_market.market_type {
+                MarketType::Categorical(categories) => categories,
+                MarketType::Scalar(_) => return Err(Error::<T>::InvalidMarketType.into()),
+            };
+            ensure!(parent_outcome < parent_categories, Error::<T>::OutcomeOutOfRange);
+
+            let child_market = T::MarketCommons::market(&child_market_id)?;
+            let child_categories = match child_market.market_type {
+                MarketType::Categorical(categories) => categories,
+                MarketType::Scalar(_) => return Err(Error::<T>::InvalidMarketType.into()),
+            };
+            let conditional_assets = (0..child_categories)
+                .map(|child_outcome| {
+                    Asset::CombinatorialShare(
+                        parent_market_id,
+                        parent_outcome,
+                        child_market_id,
+                        child_outcome,
+                    )
+                })
+                .collect();
+            Ok((
+                Asset::CategoricalOutcome(parent_market_id, parent_outcome),
+                conditional_assets,
+                child_market,
+            ))
+        }
+
+        // Conditional sets can only be bought and sold while the child market is active.
+        fn ensure_conditional_market_is_active(
+            child_market_id: &MarketIdOf<T>,
+            child_market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> DispatchResult {
+            ensure!(!Self::is_global_disputed(child_market_id), Error::<T>::MarketIsNotActive);
+            Self::ensure_market_is_active(&child_market.period)
+        }
+
+        // Returns the bonds that have to be reserved for a market created with `creation`,
+        // `oracle_bond` and `validity_bond` under the current configuration.
+        fn configured_bonds(
+            creation: &MarketCreation,
+            oracle_bond: BalanceOf<T>,
+            validity_bond: BalanceOf<T>,
+        ) -> BondBreakdown<BalanceOf<T>> {
+            match creation {
+                MarketCreation::Advised => BondBreakdown {
+                    advisory: T::AdvisoryBond::get(),
+                    validity: Zero::zero(),
+                    oracle: oracle_bond,
+                },
+                MarketCreation::Permissionless => BondBreakdown {
+                    advisory: Zero::zero(),
+                    validity: validity_bond,
+                    oracle: oracle_bond,
+                },
+            }
+        }
+
+        // Returns the bonds that were reserved when `market_id` was created. Markets created
+        // before the bonds were stored fall back to the current configuration.
+        fn creation_bonds(
+            market_id: &MarketIdOf<T>,
+            creation: &MarketCreation,
+        ) -> BondBreakdown<BalanceOf<T>> {
+            MarketBonds::<T>::get(market_id).unwrap_or_else(|| {
+                Self::configured_bonds(creation, T::OracleBond::get(), T::ValidityBond::get())
+            })
+        }
+
         pub(crate) fn do_buy_complete_set(
             who: T::AccountId,
             market_id: MarketIdOf<T>,
             amount: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
-            ensure!(CurrencyOf::<T>::free_balance(&who) >= amount, Error::<T>::NotEnoughBalance);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let fee = T::CompleteSetMintFee::get().mul_floor(amount);
+            ensure!(
+                CurrencyOf::<T>::free_balance(&who) >= amount.saturating_add(fee),
+                Error::<T>::NotEnoughBalance
+            );
 
             let market = T::MarketCommons::market(&market_id)?;
             ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            ensure!(!Self::is_global_disputed(&market_id), Error::<T>::MarketIsNotActive);
             Self::ensure_market_is_active(&market.period)?;
 
             let market_account = Self::market_account(market_id);
@@ -1418,11 +2928,21 @@ mod pallet {
                 amount,
                 ExistenceRequirement::KeepAlive,
             )?;
+            if !fee.is_zero() {
+                let imbalance = CurrencyOf::<T>::withdraw(
+                    &who,
+                    fee,
+                    WithdrawReasons::FEE,
+                    ExistenceRequirement::KeepAlive,
+                )?;
+                T::Slash::on_unbalanced(imbalance);
+            }
 
             let assets = Self::outcome_assets(market_id, &market);
             for asset in assets.iter() {
                 T::Shares::deposit(*asset, &who, amount)?;
             }
+            Self::update_account_position(&who, market_id, &assets);
 
             Self::deposit_event(Event::BoughtCompleteSet(market_id, who));
 
@@ -1431,6 +2951,138 @@ mod pallet {
             Self::calculate_actual_weight(&T::WeightInfo::buy_complete_set, assets_len, max_cats)
         }
 
+        pub(crate) fn do_deploy_swap_pool_for_market(
+            who: T::AccountId,
+            market_id: MarketIdOf<T>,
+            weights: Vec<u128>,
+        ) -> DispatchResult {
+            let market = T::MarketCommons::market(&market_id)?;
+            Self::ensure_weights_are_valid(&market, &weights)?;
+            ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            ensure!(market.status == MarketStatus::Active, Error::<T>::MarketIsNotActive);
+            Self::ensure_market_is_active(&market.period)?;
+
+            // ensure a swap pool does not already exist
+            Self::ensure_market_has_no_pool(&market_id)?;
+
+            let mut assets = Self::outcome_assets(market_id, &market);
+            let base_asset = Asset::Ztg;
+            assets.push(base_asset);
+
+            let pool_id = T::Swaps::create_pool(
+                who,
+                assets,
+                Some(base_asset),
+                market_id,
+                ScoringRule::CPMM,
+                Some(Zero::zero()),
+                Some(weights),
+            )?;
+            // The pool must consist of the outcome assets of the market and the base asset only.
+            let expected_len = usize::from(market.outcomes()).saturating_add(1);
+            let pool = T::Swaps::pool(pool_id)?;
+            ensure!(pool.assets.len() == expected_len, Error::<T>::PoolAssetCountMismatch);
+
+            T::MarketCommons::insert_market_pool(market_id, pool_id);
+            Ok(())
+        }
+
+        pub(crate) fn do_sell_complete_set(
+            who: T::AccountId,
+            beneficiary: T::AccountId,
+            market_id: MarketIdOf<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let market = T::MarketCommons::market(&market_id)?;
+            ensure!(market.scoring_rule == ScoringRule::CPMM, Error::<T>::InvalidScoringRule);
+            ensure!(!Self::is_global_disputed(&market_id), Error::<T>::MarketIsNotActive);
+            Self::ensure_market_is_active(&market.period)?;
+
+            let market_account = Self::market_account(market_id);
+            ensure!(
+                CurrencyOf::<T>::free_balance(&market_account) >= amount,
+                "Market account does not have sufficient reserves.",
+            );
+
+            let assets = Self::outcome_assets(market_id, &market);
+
+            // verify first.
+            for asset in assets.iter() {
+                // Ensures that the seller has sufficient amount of each
+                // share in the set.
+                ensure!(
+                    T::Shares::free_balance(*asset, &who) >= amount,
+                    Error::<T>::InsufficientShareBalance,
+                );
+            }
+
+            // write last.
+            for asset in assets.iter() {
+                T::Shares::slash(*asset, &who, amount);
+            }
+
+            CurrencyOf::<T>::transfer(
+                &market_account,
+                &beneficiary,
+                amount,
+                Self::market_account_existence_requirement(&market_account, amount),
+            )?;
+            Self::update_account_position(&who, market_id, &assets);
+
+            Self::deposit_event(Event::SoldCompleteSet(market_id, who));
+            let assets_len: u32 = assets.len().saturated_into();
+            let max_cats: u32 = T::MaxCategories::get().into();
+            Self::calculate_actual_weight(&T::WeightInfo::sell_complete_set, assets_len, max_cats)
+        }
+
+        // Deploys the pool that was requested at the creation of `market_id`, if any. The seed
+        // collateral reserved at creation is used to buy `MinLiquidity` complete sets, which are
+        // deployed along with `MinLiquidity` of the base asset.
+        fn auto_deploy_pool(market_id: MarketIdOf<T>) -> Result<Weight, DispatchError> {
+            let (seed, weights) = match AutoDeployPools::<T>::take(&market_id) {
+                Some(auto_deploy_pool) => auto_deploy_pool,
+                None => return Ok(0),
+            };
+            let creator = T::MarketCommons::market(&market_id)?.creator;
+            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &creator, seed);
+            let weight_bcs = Self::do_buy_complete_set(
+                creator.clone(),
+                market_id,
+                MinLiquidity::get().saturated_into(),
+            )?
+            .actual_weight
+            .unwrap_or_else(|| T::WeightInfo::buy_complete_set(T::MaxCategories::get().into()));
+            let weights_len = weights.len().saturated_into();
+            Self::do_deploy_swap_pool_for_market(creator, market_id, weights)?;
+            Ok(weight_bcs.saturating_add(T::WeightInfo::deploy_swap_pool_for_market(weights_len)))
+        }
+
+        // The seed collateral that is reserved for a pool that is deployed automatically: a
+        // complete set and the base asset of `MinLiquidity` each, and the `CompleteSetMintFee`.
+        fn auto_deploy_pool_seed() -> BalanceOf<T> {
+            let min_liquidity: BalanceOf<T> = MinLiquidity::get().saturated_into();
+            let fee = T::CompleteSetMintFee::get().mul_floor(min_liquidity);
+            min_liquidity.saturating_add(min_liquidity).saturating_add(fee)
+        }
+
+        // The worst-case weight of deploying the pool of a market automatically. `weights` is
+        // `None` if the weights aren't known upfront.
+        fn auto_deploy_pool_weight(weights: &Option<Vec<u128>>) -> Weight {
+            let weights_len = match weights {
+                Some(weights) => weights.len().saturated_into(),
+                None => T::MaxCategories::get().saturating_add(1).into(),
+            };
+            T::WeightInfo::buy_complete_set(T::MaxCategories::get().into())
+                .saturating_add(T::WeightInfo::deploy_swap_pool_for_market(weights_len))
+        }
+
+        // Copies `bytes` into `BoundedMetadata`. Fails if `bytes` exceeds `MaxMetadataLength`.
+        fn bounded_metadata(bytes: &[u8]) -> Result<BoundedMetadata, DispatchError> {
+            BoundedMetadata::try_from(bytes.to_vec())
+                .map_err(|_| Error::<T>::MetadataTooLong.into())
+        }
+
         fn calculate_actual_weight<F>(
             func: F,
             weight_parameter: u32,
@@ -1475,6 +3127,19 @@ mod pallet {
             }
         }
 
+        fn ensure_account_disputes_do_not_exceed_max(
+            disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            let account_disputes = disputes.iter().filter(|dispute| &dispute.by == who).count();
+            let max_account_disputes: usize = T::MaxDisputesPerAccount::get().saturated_into();
+            ensure!(
+                account_disputes < max_account_disputes,
+                Error::<T>::AccountDisputeLimitReached
+            );
+            Ok(())
+        }
+
         fn ensure_can_not_dispute_the_same_outcome(
             disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
             report: &Report<T::AccountId, T::BlockNumber>,
@@ -1488,12 +3153,76 @@ mod pallet {
             Ok(())
         }
 
+        // Checks `outcome` against `MonotonicScalarDisputes` and the value of the previous dispute
+        // or, if there is none, the report. Non-scalar outcomes are always in step.
+        fn ensure_scalar_dispute_is_in_step(
+            disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
+            report: &Report<T::AccountId, T::BlockNumber>,
+            outcome: &OutcomeReport,
+        ) -> DispatchResult {
+            let previous = disputes.last().map_or(&report.outcome, |dispute| &dispute.outcome);
+            let (previous, value) = match (previous, outcome) {
+                (OutcomeReport::Scalar(previous), OutcomeReport::Scalar(value)) => {
+                    (*previous, *value)
+                }
+                _ => return Ok(()),
+            };
+            let in_step = match T::MonotonicScalarDisputes::get() {
+                ScalarDisputeStep::Unrestricted => true,
+                ScalarDisputeStep::Increasing => value >= previous,
+                ScalarDisputeStep::Decreasing => value <= previous,
+                ScalarDisputeStep::MaxDelta(delta) => {
+                    value.max(previous).saturating_sub(value.min(previous)) <= delta
+                }
+            };
+            ensure!(in_step, Error::<T>::DisputeValueOutOfStep);
+            Ok(())
+        }
+
         #[inline]
-        fn ensure_disputes_does_not_exceed_max_disputes(num_disputes: u32) -> DispatchResult {
-            ensure!(num_disputes < T::MaxDisputes::get(), Error::<T>::MaxDisputesReached);
+        fn ensure_disputes_does_not_exceed_max_disputes(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            num_disputes: u32,
+        ) -> DispatchResult {
+            ensure!(num_disputes < Self::max_disputes_of(market), Error::<T>::MaxDisputesReached);
+            Ok(())
+        }
+
+        fn ensure_max_disputes_is_valid(max_disputes: Option<u32>) -> DispatchResult {
+            if let Some(max_disputes) = max_disputes {
+                ensure!(
+                    max_disputes > 0 && max_disputes <= T::MaxDisputes::get(),
+                    Error::<T>::InvalidMaxDisputes
+                );
+            }
             Ok(())
         }
 
+        fn ensure_oracle_grace_period_is_valid(oracle_grace_period: Option<u32>) -> DispatchResult {
+            if let Some(grace_period) = oracle_grace_period {
+                ensure!(
+                    grace_period <= T::ReportingPeriod::get(),
+                    Error::<T>::InvalidOracleGracePeriod
+                );
+            }
+            Ok(())
+        }
+
+        // A pool that is still registered for the market but doesn't exist anymore doesn't
+        // count.
+        fn ensure_market_has_no_pool(market_id: &MarketIdOf<T>) -> DispatchResult {
+            if let Ok(pool_id) = T::MarketCommons::market_pool(market_id) {
+                ensure!(!T::Swaps::pool_exists(pool_id), Error::<T>::SwapPoolExists);
+            }
+            Ok(())
+        }
+
+        /// Returns whether the outcome of `market_id` is currently contested in a global dispute.
+        /// Trading is frozen until the global dispute ends.
+        pub fn is_global_disputed(market_id: &MarketIdOf<T>) -> bool {
+            GlobalDisputeEnds::<T>::contains_key(market_id)
+        }
+
         // Must be `MarketStatus::Active` and period within range
         fn ensure_market_is_active(
             period: &MarketPeriod<T::BlockNumber, MomentOf<T>>,
@@ -1539,7 +3268,7 @@ mod pallet {
                         .start
                         .saturating_sub(<frame_system::Pallet<T>>::block_number())
                         .saturated_into();
-                    interval_blocks.saturating_mul(MILLISECS_PER_BLOCK.into())
+                    interval_blocks.saturating_mul(T::MillisecondsPerBlock::get().into())
                 }
                 MarketPeriod::Timestamp(range) => {
                     range.start.saturating_sub(T::MarketCommons::now()).saturated_into()
@@ -1557,13 +3286,57 @@ mod pallet {
             Ok(())
         }
 
+        fn ensure_market_end_kind_is_allowed(
+            period: &MarketPeriod<T::BlockNumber, MomentOf<T>>,
+        ) -> DispatchResult {
+            ensure!(
+                T::AllowedMarketEndKinds::get().allows(period),
+                Error::<T>::MarketEndKindNotAllowed
+            );
+            Ok(())
+        }
+
+        // Ensures that the combined length of `metadata` doesn't exceed `MaxTotalMetadataBytes`.
+        fn ensure_metadata_is_within_limit(metadata: &[&[u8]]) -> DispatchResult {
+            let total_len = metadata.iter().fold(0usize, |acc, m| acc.saturating_add(m.len()));
+            let max_len: usize = T::MaxTotalMetadataBytes::get().saturated_into();
+            ensure!(total_len <= max_len, Error::<T>::MetadataTooLong);
+            Ok(())
+        }
+
+        fn ensure_outcome_descriptors_are_valid(
+            descriptors: &[OutcomeDescriptor],
+            categories: u16,
+        ) -> DispatchResult {
+            ensure!(
+                descriptors.len() == usize::from(categories),
+                Error::<T>::OutcomeDescriptorsLengthMismatch
+            );
+            let max_label_len: usize = T::MaxOutcomeLabelLength::get().saturated_into();
+            ensure!(
+                descriptors.iter().all(|descriptor| descriptor.is_valid(max_label_len)),
+                Error::<T>::InvalidOutcomeDescriptor
+            );
+            Ok(())
+        }
+
+        fn ensure_outcome_asset_of_existing_market(asset: &Asset<MarketIdOf<T>>) -> DispatchResult {
+            match asset {
+                Asset::CategoricalOutcome(market_id, _) | Asset::ScalarOutcome(market_id, _) => {
+                    T::MarketCommons::market(market_id)?;
+                    Ok(())
+                }
+                _ => Err(Error::<T>::NotAnOutcomeAsset.into()),
+            }
+        }
+
         fn ensure_outcome_matches_market_type(
             market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
             outcome: &OutcomeReport,
         ) -> DispatchResult {
             if let OutcomeReport::Categorical(ref inner) = outcome {
-                if let MarketType::Categorical(ref categories) = market.market_type {
-                    ensure!(inner < categories, Error::<T>::OutcomeOutOfRange);
+                if let MarketType::Categorical(_) = market.market_type {
+                    ensure!(*inner < market.outcomes(), Error::<T>::OutcomeOutOfRange);
                 } else {
                     return Err(Error::<T>::OutcomeMismatch.into());
                 }
@@ -1581,6 +3354,258 @@ mod pallet {
             Ok(())
         }
 
+        // Reports are checked like disputes, except that scalar values outside of the range of
+        // the market are allowed. Their payouts are clamped to the range.
+        fn ensure_outcome_is_reportable(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            outcome: &OutcomeReport,
+        ) -> DispatchResult {
+            match (outcome, &market.market_type) {
+                (OutcomeReport::Invalid, _) => Ok(()),
+                (OutcomeReport::Categorical(index), MarketType::Categorical(_)) => {
+                    ensure!(*index < market.outcomes(), Error::<T>::OutcomeOutOfRange);
+                    Ok(())
+                }
+                (OutcomeReport::Scalar(_), MarketType::Scalar(_)) => Ok(()),
+                _ => Err(Error::<T>::OutcomeMismatch.into()),
+            }
+        }
+
+        // Each outcome asset and the base asset need a weight. Checked before any work is done
+        // with `weights`, which is user-supplied and not bounded otherwise. If `PoolWeightsSum`
+        // is set, the weights must also sum to it.
+        fn ensure_weights_are_valid(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            weights: &[u128],
+        ) -> DispatchResult {
+            let expected_len = usize::from(market.outcomes()).saturating_add(1);
+            ensure!(weights.len() == expected_len, Error::<T>::WeightsLengthMismatch);
+            if let Some(weights_sum) = T::PoolWeightsSum::get() {
+                let sum = weights.iter().fold(0u128, |acc, weight| acc.saturating_add(*weight));
+                ensure!(sum == weights_sum, Error::<T>::PoolWeightsSumInvalid);
+            }
+            Ok(())
+        }
+
+        /// Returns the pools of all markets, ordered by market id.
+        ///
+        /// NOTE: Reads every market pool. Use `market_pools_paged` where the reads must be
+        /// bounded.
+        pub fn all_market_pools() -> Vec<(MarketIdOf<T>, PoolId)> {
+            let mut pools = T::MarketCommons::market_pools();
+            pools.sort_unstable_by_key(|(market_id, _)| *market_id);
+            pools
+        }
+
+        /// Returns the report of `market_id`, i.e. the reporter, the block of the report and the
+        /// reported outcome. `None` if the market doesn't exist or wasn't reported yet.
+        pub fn current_report(
+            market_id: &MarketIdOf<T>,
+        ) -> Option<Report<T::AccountId, T::BlockNumber>> {
+            T::MarketCommons::market(market_id).ok()?.report
+        }
+
+        /// Returns the asset that `market_id` is denominated in, i.e. the asset complete sets are
+        /// bought with and winning shares are redeemed for. Markets can't be created with another
+        /// collateral yet, so this is always `Asset::Ztg`.
+        pub fn collateral_asset(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<Asset<MarketIdOf<T>>, DispatchError> {
+            T::MarketCommons::market(market_id)?;
+            Ok(Asset::Ztg)
+        }
+
+        fn decrement_disputed_markets_count() {
+            DisputedMarketsCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+        }
+
+        /// Returns `true` if `market_id` can be disputed right now, i.e. it is reported or
+        /// disputed, isn't in a global dispute, hasn't reached its dispute limit and isn't due
+        /// for resolution yet. Returns `false` for all other markets, including unknown ones.
+        pub fn can_dispute(market_id: &MarketIdOf<T>) -> bool {
+            let market = match T::MarketCommons::market(market_id) {
+                Ok(market) => market,
+                Err(_) => return false,
+            };
+            if !matches!(market.status, MarketStatus::Reported | MarketStatus::Disputed) {
+                return false;
+            }
+            if Self::is_global_disputed(market_id) {
+                return false;
+            }
+            let num_disputes: u32 = Disputes::<T>::get(market_id).len().saturated_into();
+            if Self::ensure_disputes_does_not_exceed_max_disputes(&market, num_disputes).is_err() {
+                return false;
+            }
+            match Self::scheduled_resolution_block(market_id) {
+                Some(block) => <frame_system::Pallet<T>>::block_number() < block,
+                None => false,
+            }
+        }
+
+        /// Returns the bond that has to be reserved for the next dispute of `market_id`.
+        pub fn dispute_bond_for(market_id: &MarketIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+            T::MarketCommons::market(market_id)?;
+            Ok(default_dispute_bond::<T>(Disputes::<T>::get(market_id).len()))
+        }
+
+        /// Returns the disputes of `market_id` in order, each with a flag that is `true` if the
+        /// dispute matches the outcome the market resolved to. All flags are `false` until the
+        /// market is resolved.
+        pub fn dispute_outcomes(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<Vec<(MarketDispute<T::AccountId, T::BlockNumber>, bool)>, DispatchError>
+        {
+            let market = T::MarketCommons::market(market_id)?;
+            Ok(Disputes::<T>::get(market_id)
+                .into_iter()
+                .map(|dispute| {
+                    let was_correct = market.resolved_outcome.as_ref() == Some(&dispute.outcome);
+                    (dispute, was_correct)
+                })
+                .collect())
+        }
+
+        /// Returns the bonds of all disputes `market_id` allows, i.e. the bond of the `i`-th
+        /// dispute is at index `i`. Respects the dispute limit of the market.
+        pub fn dispute_bond_schedule(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<Vec<BalanceOf<T>>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            Ok((0..Self::max_disputes_of(&market) as usize)
+                .map(|i| default_dispute_bond::<T>(i))
+                .collect())
+        }
+
+        /// Returns the bond required for the next dispute of `market_id` and the reward that
+        /// disputing `outcome` would pay on top of the returned bond if the market resolved to
+        /// `outcome`. The reward is an upper bound, since later disputes of the same outcome
+        /// share it.
+        pub fn dispute_preview(
+            market_id: &MarketIdOf<T>,
+            outcome: &OutcomeReport,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            let report = market.report.as_ref().ok_or(Error::<T>::MarketNotReported)?;
+            Self::ensure_outcome_matches_market_type(&market, outcome)?;
+            let disputes = Disputes::<T>::get(market_id);
+            let required_bond = default_dispute_bond::<T>(disputes.len());
+
+            let mut slashed = if &report.outcome == outcome {
+                BalanceOf::<T>::zero()
+            } else {
+                Self::creation_bonds(market_id, &market.creation).oracle
+            };
+            let mut correct_reporters = 1u32;
+            for (i, dispute) in disputes.iter().enumerate() {
+                if &dispute.outcome == outcome {
+                    correct_reporters = correct_reporters.saturating_add(1);
+                } else {
+                    slashed = slashed.saturating_add(default_dispute_bond::<T>(i));
+                }
+            }
+            let rewards = slashed.saturating_sub(T::SlashSplit::get().mul_floor(slashed));
+            let potential_reward = rewards
+                .checked_div(&correct_reporters.into())
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            Ok((required_bond, potential_reward))
+        }
+
+        /// Returns the (estimated) timestamp at which the market ends. For markets that end at a
+        /// block, the timestamp is extrapolated from the current block and time.
+        pub fn estimated_end_timestamp(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<MomentOf<T>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            Ok(Self::end_timestamp(&market))
+        }
+
+        // Returns the (estimated) timestamp at which `market` ends.
+        fn end_timestamp(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> MomentOf<T> {
+            match market.period {
+                MarketPeriod::Block(ref range) => {
+                    let now = T::MarketCommons::now();
+                    let current_block = <frame_system::Pallet<T>>::block_number();
+                    let ms_per_block: u128 = T::MillisecondsPerBlock::get().into();
+                    if range.end >= current_block {
+                        let blocks: u128 = range.end.saturating_sub(current_block).saturated_into();
+                        now.saturating_add(<MomentOf<T>>::saturated_from(
+                            blocks.saturating_mul(ms_per_block),
+                        ))
+                    } else {
+                        let blocks: u128 = current_block.saturating_sub(range.end).saturated_into();
+                        now.saturating_sub(<MomentOf<T>>::saturated_from(
+                            blocks.saturating_mul(ms_per_block),
+                        ))
+                    }
+                }
+                MarketPeriod::Timestamp(ref range) => range.end,
+            }
+        }
+
+        // Returns the outcome with the highest total stake in the global dispute of `market_id`.
+        // Ties are broken in favor of the lowest outcome. Returns `None` if nobody voted.
+        fn global_dispute_winner(market_id: &MarketIdOf<T>) -> Option<OutcomeReport> {
+            let mut stakes: Vec<(OutcomeReport, BalanceOf<T>)> = Vec::new();
+            for (_, (outcome, stake)) in GlobalDisputeVotes::<T>::iter_prefix(market_id) {
+                if let Some(entry) = stakes.iter_mut().find(|(o, _)| *o == outcome) {
+                    entry.1 = entry.1.saturating_add(stake);
+                } else {
+                    stakes.push((outcome, stake));
+                }
+            }
+
+            let mut winner: Option<(OutcomeReport, BalanceOf<T>)> = None;
+            for (outcome, stake) in stakes {
+                let is_better = match &winner {
+                    Some((best_outcome, best_stake)) => {
+                        stake > *best_stake || (stake == *best_stake && outcome < *best_outcome)
+                    }
+                    None => true,
+                };
+                if is_better {
+                    winner = Some((outcome, stake));
+                }
+            }
+            winner.map(|(outcome, _)| outcome)
+        }
+
+        /// Returns the amount of collateral that `who` would receive for redeeming their shares of
+        /// `market_id` if the market resolved to `outcome`. For scalar markets, `outcome` is the
+        /// hypothetical resolution value.
+        pub fn hypothetical_winnings(
+            market_id: &MarketIdOf<T>,
+            who: &T::AccountId,
+            outcome: &OutcomeReport,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            Self::ensure_outcome_matches_market_type(&market, outcome)?;
+            let total_payout = Self::winning_assets(*market_id, &market, who, outcome)?
+                .iter()
+                .fold(BalanceOf::<T>::zero(), |acc, (_, payout, _)| acc.saturating_add(*payout));
+
+            let market_balance = CurrencyOf::<T>::free_balance(&Self::market_account(*market_id));
+            if market_balance >= total_payout || !T::ProRataRedemption::get() {
+                return Ok(total_payout);
+            }
+            let liability = Self::winning_shares_liability(*market_id, &market, outcome)?;
+            let ratio = Perbill::from_rational(
+                market_balance.saturated_into::<u128>(),
+                liability.saturated_into::<u128>(),
+            );
+            Ok(ratio.mul_floor(total_payout))
+        }
+
+        // Returns the fraction of a unit of collateral that each outcome share is worth if the
+        // market resolves to `OutcomeReport::Invalid`.
+        fn invalid_share_percent(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> Perbill {
+            Perbill::from_rational(1u32, u32::from(market.outcomes()))
+        }
+
         // If a market is categorical, destroys all non-winning assets.
         fn manage_resolved_categorical_market(
             market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
@@ -1596,7 +3621,16 @@ mod pallet {
                     let assets = Self::outcome_assets(*market_id, market);
                     total_categories = assets.len().saturated_into();
 
-                    let mut assets_iter = assets.iter().cloned();
+                    // If nobody holds the winning outcome, the losing shares are refunded on
+                    // redemption instead, so they must not be destroyed.
+                    let winning_asset = Asset::CategoricalOutcome(*market_id, winning_asset_idx);
+                    if T::ZeroSupplyRefund::get()
+                        && T::Shares::total_issuance(winning_asset).is_zero()
+                    {
+                        return Ok([total_accounts, total_asset_accounts, total_categories]);
+                    }
+
+                    let mut assets_iter = assets.clone().into_iter();
                     let mut manage_asset = |asset: Asset<_>, winning_asset_idx| {
                         if let Asset::CategoricalOutcome(_, idx) = asset {
                             if idx == winning_asset_idx {
@@ -1607,6 +3641,9 @@ mod pallet {
                             total_asset_accounts =
                                 total_asset_accounts.saturating_add(accounts.len());
                             T::Shares::destroy_all(asset, accounts.iter().cloned());
+                            for account in accounts.iter() {
+                                Self::update_account_position(account, *market_id, &assets);
+                            }
                             total_accounts
                         } else {
                             0
@@ -1625,42 +3662,136 @@ mod pallet {
             Ok([total_accounts, total_asset_accounts, total_categories])
         }
 
+        /// Returns the outcome of `disputes` with the highest total dispute bond. Ties are broken in
+        /// favor of the outcome that was disputed most recently.
+        ///
+        /// Returns `None` if there are no disputes.
+        fn highest_bonded_outcome(
+            disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
+        ) -> Option<OutcomeReport> {
+            // The total bond of each outcome and the index of its most recent dispute.
+            let mut bonds: Vec<(&OutcomeReport, BalanceOf<T>, usize)> = Vec::new();
+            for (i, dispute) in disputes.iter().enumerate() {
+                let bond = default_dispute_bond::<T>(i);
+                if let Some(entry) =
+                    bonds.iter_mut().find(|(outcome, ..)| *outcome == &dispute.outcome)
+                {
+                    entry.1 = entry.1.saturating_add(bond);
+                    entry.2 = i;
+                } else {
+                    bonds.push((&dispute.outcome, bond, i));
+                }
+            }
+            bonds
+                .into_iter()
+                .max_by_key(|(_, bond, last_idx)| (*bond, *last_idx))
+                .map(|(outcome, ..)| outcome.clone())
+        }
+
+        /// Returns the lower median of the scalar values of `report` and `disputes`.
+        ///
+        /// Returns `None` if any of the outcomes isn't scalar.
+        fn median_scalar_outcome(
+            report: &Report<T::AccountId, T::BlockNumber>,
+            disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
+        ) -> Option<OutcomeReport> {
+            let mut values = Vec::with_capacity(disputes.len().saturating_add(1));
+            for outcome in
+                core::iter::once(&report.outcome).chain(disputes.iter().map(|d| &d.outcome))
+            {
+                if let OutcomeReport::Scalar(value) = outcome {
+                    values.push(*value);
+                } else {
+                    return None;
+                }
+            }
+            values.sort_unstable();
+            let median_idx = values.len().saturating_sub(1).checked_div(2)?;
+            values.get(median_idx).copied().map(OutcomeReport::Scalar)
+        }
+
         fn on_resolution(
             market_id: &MarketIdOf<T>,
             market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
         ) -> Result<u64, DispatchError> {
-            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, T::ValidityBond::get());
+            let disputes = Disputes::<T>::get(market_id);
+            // A disputed market without disputes can't be resolved by its dispute mechanism, so
+            // it falls back to the report.
+            if market.status == MarketStatus::Disputed && disputes.is_empty() {
+                Self::set_market_status(market_id, MarketStatus::Reported)?;
+                let market = T::MarketCommons::market(market_id)?;
+                return Self::on_resolution(market_id, &market);
+            }
+
+            let bonds = Self::creation_bonds(market_id, &market.creation);
 
             let mut total_weight = 0;
-            let disputes = Disputes::<T>::get(market_id);
-            let resolved_outcome = match market.mdm {
-                MarketDisputeMechanism::Authorized(_) => {
-                    T::Authorized::on_resolution(&disputes, market_id, market)?
-                }
-                MarketDisputeMechanism::Court => {
-                    T::Court::on_resolution(&disputes, market_id, market)?
+            let report = T::MarketCommons::report(market)?;
+            let oracle_bond = bonds.oracle;
+
+            // a global dispute that received votes overrules the dispute mechanism
+            let resolved_outcome = if let Some(outcome) = Self::global_dispute_winner(market_id) {
+                outcome
+            } else {
+                let mut outcome = match market.mdm {
+                    MarketDisputeMechanism::Authorized(_) => {
+                        T::Authorized::on_resolution(&disputes, market_id, market)?
+                    }
+                    MarketDisputeMechanism::Court => {
+                        T::Court::on_resolution(&disputes, market_id, market)?
+                    }
+                    MarketDisputeMechanism::SimpleDisputes => {
+                        T::SimpleDisputes::on_resolution(&disputes, market_id, market)?
+                    }
+                };
+
+                if market.status == MarketStatus::Disputed
+                    && T::DisputeResolutionRule::get()
+                        == DisputeResolutionRule::HighestBondedOutcome
+                {
+                    if let MarketType::Categorical(_) = market.market_type {
+                        outcome = Self::highest_bonded_outcome(&disputes).unwrap_or(outcome);
+                    }
                 }
-                MarketDisputeMechanism::SimpleDisputes => {
-                    T::SimpleDisputes::on_resolution(&disputes, market_id, market)?
+
+                if market.status == MarketStatus::Disputed
+                    && T::ScalarResolutionPolicy::get() == ScalarResolutionPolicy::Median
+                {
+                    if let MarketType::Scalar(_) = market.market_type {
+                        outcome = Self::median_scalar_outcome(report, &disputes).unwrap_or(outcome);
+                    }
                 }
+
+                outcome
             };
 
-            let report = T::MarketCommons::report(market)?;
+            // by default, the validity bond is slashed if the market turned out to be invalid
+            let slash_validity_bond = match T::ValidityBondPolicy::get() {
+                ValidityBondPolicy::ReturnIfValid => resolved_outcome == OutcomeReport::Invalid,
+                ValidityBondPolicy::AlwaysBurn => true,
+                ValidityBondPolicy::AlwaysReturn => false,
+            };
+            if slash_validity_bond {
+                let (imbalance, _) = CurrencyOf::<T>::slash_reserved_named(
+                    &RESERVE_ID,
+                    &market.creator,
+                    bonds.validity,
+                );
+                Self::handle_slashed_bonds(imbalance);
+            } else {
+                CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, bonds.validity);
+            }
 
             match market.status {
                 MarketStatus::Reported => {
                     // the oracle bond gets returned if the reporter was the oracle
-                    if report.by == market.oracle {
-                        CurrencyOf::<T>::unreserve_named(
-                            &RESERVE_ID,
-                            &market.creator,
-                            T::OracleBond::get(),
-                        );
+                    if report.by_oracle {
+                        CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, oracle_bond);
                     } else {
                         let (imbalance, _) = CurrencyOf::<T>::slash_reserved_named(
                             &RESERVE_ID,
                             &market.creator,
-                            T::OracleBond::get(),
+                            oracle_bond,
                         );
 
                         // give it to the real reporter
@@ -1675,26 +3806,22 @@ mod pallet {
                     // if the reporter reported right, return the OracleBond, otherwise
                     // slash it to pay the correct reporters
                     if report.outcome == resolved_outcome {
-                        CurrencyOf::<T>::unreserve_named(
-                            &RESERVE_ID,
-                            &market.creator,
-                            T::OracleBond::get(),
-                        );
+                        CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, oracle_bond);
                     } else {
                         let (imbalance, _) = CurrencyOf::<T>::slash_reserved_named(
                             &RESERVE_ID,
                             &market.creator,
-                            T::OracleBond::get(),
+                            oracle_bond,
                         );
 
                         overall_imbalance.subsume(imbalance);
                     }
 
+                    // the bonds of correct reporters stay reserved until they're claimed with
+                    // `claim_dispute_bond`
                     for (i, dispute) in disputes.iter().enumerate() {
                         let actual_bond = default_dispute_bond::<T>(i);
                         if dispute.outcome == resolved_outcome {
-                            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &dispute.by, actual_bond);
-
                             correct_reporters.push(dispute.by.clone());
                         } else {
                             let (imbalance, _) = CurrencyOf::<T>::slash_reserved_named(
@@ -1706,19 +3833,42 @@ mod pallet {
                         }
                     }
 
-                    // fold all the imbalances into one and reward the correct reporters.
-                    let reward_per_each = overall_imbalance
-                        .peek()
-                        .checked_div(&correct_reporters.len().saturated_into())
-                        .ok_or(ArithmeticError::DivisionByZero)?;
-                    for correct_reporter in &correct_reporters {
-                        let (amount, leftover) = overall_imbalance.split(reward_per_each);
-                        CurrencyOf::<T>::resolve_creating(correct_reporter, amount);
+                    // with the median policy it is possible that no dispute matches the resolved
+                    // outcome, in which case the slashed bonds are handed to `Slash`.
+                    if correct_reporters.is_empty() {
+                        Self::handle_slashed_bonds(overall_imbalance);
+                    } else {
+                        let slash_share = T::SlashSplit::get().mul_floor(overall_imbalance.peek());
+                        let (slash_imbalance, leftover) = overall_imbalance.split(slash_share);
+                        Self::handle_slashed_bonds(slash_imbalance);
                         overall_imbalance = leftover;
+
+                        // fold all the imbalances into one and reward the correct reporters.
+                        let reward_per_each = overall_imbalance
+                            .peek()
+                            .checked_div(&correct_reporters.len().saturated_into())
+                            .ok_or(ArithmeticError::DivisionByZero)?;
+                        for correct_reporter in &correct_reporters {
+                            let (amount, leftover) = overall_imbalance.split(reward_per_each);
+                            CurrencyOf::<T>::resolve_creating(correct_reporter, amount);
+                            overall_imbalance = leftover;
+                        }
+                        // the rounding dust goes to the first correct reporter instead of being
+                        // burned.
+                        if let Some(first_correct_reporter) = correct_reporters.first() {
+                            CurrencyOf::<T>::resolve_creating(
+                                first_correct_reporter,
+                                overall_imbalance,
+                            );
+                        }
                     }
                 }
                 _ => (),
             };
+            Self::settle_global_dispute(market_id, &resolved_outcome);
+            MarketBonds::<T>::remove(market_id);
+            ResolutionFailures::<T>::remove(market_id);
+            MarketsByOracle::<T>::remove(&market.oracle, market_id);
             let to_stale_weight = Self::set_pool_to_stale(market, market_id, &resolved_outcome)?;
             total_weight = total_weight.saturating_add(to_stale_weight);
             T::LiquidityMining::distribute_market_incentives(market_id)?;
@@ -1735,11 +3885,12 @@ mod pallet {
                 total_categories = local_total_categories.saturated_into();
             }
 
+            Self::burn_market_account_winning_shares(market_id, market, &resolved_outcome)?;
             T::MarketCommons::mutate_market(market_id, |m| {
-                m.status = MarketStatus::Resolved;
                 m.resolved_outcome = Some(resolved_outcome.clone());
                 Ok(())
             })?;
+            Self::set_market_status(market_id, MarketStatus::Resolved)?;
             Self::deposit_event(Event::MarketResolved(
                 *market_id,
                 MarketStatus::Resolved,
@@ -1754,6 +3905,246 @@ mod pallet {
             )))
         }
 
+        // Returns the dispute bonds of all correct disputers of a resolved market that weren't
+        // claimed yet.
+        fn return_unclaimed_dispute_bonds(
+            market_id: &MarketIdOf<T>,
+            resolved_outcome: &OutcomeReport,
+        ) {
+            for (i, dispute) in Disputes::<T>::get(market_id).iter().enumerate() {
+                if dispute.outcome == *resolved_outcome
+                    && !DisputeBondClaims::<T>::contains_key(market_id, &dispute.by)
+                {
+                    let bond = default_dispute_bond::<T>(i);
+                    CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &dispute.by, bond);
+                }
+            }
+        }
+
+        // Hands bonds slashed on resolution to `Slash`, after topping up the pallet account to
+        // `ResolutionReward` to fund the reward of `resolve`.
+        fn handle_slashed_bonds(imbalance: NegativeImbalanceOf<T>) {
+            let pallet_account = T::PalletId::get().into_account();
+            let missing = T::ResolutionReward::get()
+                .saturating_sub(CurrencyOf::<T>::free_balance(&pallet_account));
+            let (reward_imbalance, leftover) = imbalance.split(missing);
+            CurrencyOf::<T>::resolve_creating(&pallet_account, reward_imbalance);
+            T::Slash::on_unbalanced(leftover);
+        }
+
+        // Burns the winning shares held by the account of `market_id` itself, e.g. after a pool was
+        // wound down. Redeeming them would only move collateral within the market account, while
+        // they'd still count towards the liability of the market.
+        fn burn_market_account_winning_shares(
+            market_id: &MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            resolved_outcome: &OutcomeReport,
+        ) -> DispatchResult {
+            let market_account = Self::market_account(*market_id);
+            let winning_assets =
+                Self::winning_assets(*market_id, market, &market_account, resolved_outcome)?;
+            for (asset, _, balance) in winning_assets {
+                if !balance.is_zero() {
+                    T::Shares::slash(asset, &market_account, balance);
+                }
+            }
+            Ok(())
+        }
+
+        // Like `on_resolution`, but reverts all changes if the resolution fails.
+        fn on_resolution_transactional(
+            market_id: &MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> Result<Weight, DispatchError> {
+            with_transaction(|| match Self::on_resolution(market_id, market) {
+                Ok(weight) => TransactionOutcome::Commit(Ok(weight)),
+                Err(err) => TransactionOutcome::Rollback(Err(err)),
+            })
+        }
+
+        /// Returns the oracle bond that was reserved from the creator of `market_id`.
+        pub fn oracle_bond(market_id: &MarketIdOf<T>) -> BalanceOf<T> {
+            MarketBonds::<T>::get(market_id).map_or_else(T::OracleBond::get, |bonds| bonds.oracle)
+        }
+
+        /// Returns the bonds that are still reserved from the creator of `market_id`.
+        pub fn market_bonds(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<BondBreakdown<BalanceOf<T>>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            if matches!(market.status, MarketStatus::InsufficientSubsidy | MarketStatus::Resolved) {
+                return Ok(BondBreakdown::default());
+            }
+
+            let mut bonds = Self::creation_bonds(market_id, &market.creation);
+            if market.status != MarketStatus::Proposed {
+                bonds.advisory = Zero::zero();
+            }
+            Ok(bonds)
+        }
+
+        /// Returns the pools of the markets `start..start + limit`, ordered by market id. Reads
+        /// at most `limit` markets, so the next page starts at `start + limit` regardless of how
+        /// many pools were returned.
+        pub fn market_pools_paged(
+            start: &MarketIdOf<T>,
+            limit: u32,
+        ) -> Vec<(MarketIdOf<T>, PoolId)> {
+            let start = *start;
+            let end = match T::MarketCommons::latest_market_id() {
+                Ok(latest) => {
+                    cmp::min(start.saturating_add(limit.into()), latest.saturating_add(1u32.into()))
+                }
+                Err(_) => return Vec::new(),
+            };
+            let mut pools = Vec::new();
+            let mut market_id = start;
+            while market_id < end {
+                if let Ok(pool_id) = T::MarketCommons::market_pool(&market_id) {
+                    pools.push((market_id, pool_id));
+                }
+                market_id = market_id.saturating_add(1u32.into());
+            }
+            pools
+        }
+
+        /// Returns the unresolved markets that `oracle` has to report, sorted by id.
+        pub fn markets_by_oracle(oracle: &T::AccountId) -> Vec<MarketIdOf<T>> {
+            let mut market_ids: Vec<_> =
+                MarketsByOracle::<T>::iter_prefix(oracle).map(|(market_id, _)| market_id).collect();
+            market_ids.sort();
+            market_ids
+        }
+
+        /// Returns the ids of all markets that end within `[from, to)`, in ascending order. The
+        /// end of markets that end at a block is estimated like in `estimated_end_timestamp`.
+        pub fn markets_ending_between(from: MomentOf<T>, to: MomentOf<T>) -> Vec<MarketIdOf<T>> {
+            let mut market_ids: Vec<_> = T::MarketCommons::markets()
+                .into_iter()
+                .filter(|(_, market)| (from..to).contains(&Self::end_timestamp(market)))
+                .map(|(market_id, _)| market_id)
+                .collect();
+            market_ids.sort();
+            market_ids
+        }
+
+        /// Returns the ids of all reported or disputed markets that aren't resolved yet, in
+        /// ascending order. The ids are collected from the resolution schedule, which may still
+        /// list markets that were resolved in the meantime, so the status of every market is
+        /// checked. Stalled markets are left out, since they're no longer scheduled.
+        ///
+        /// NOTE: Reads the entire resolution schedule.
+        pub fn pending_resolution_markets() -> Vec<MarketIdOf<T>> {
+            let mut market_ids: Vec<_> = MarketIdsPerReportBlock::<T>::iter_values()
+                .chain(MarketIdsPerDisputeBlock::<T>::iter_values())
+                .chain(MarketIdsPerGlobalDisputeEnd::<T>::iter_values())
+                .flatten()
+                .collect();
+            market_ids.sort();
+            market_ids.dedup();
+            market_ids.retain(|market_id| {
+                matches!(
+                    T::MarketCommons::market(market_id).map(|market| market.status),
+                    Ok(MarketStatus::Reported) | Ok(MarketStatus::Disputed)
+                )
+            });
+            market_ids
+        }
+
+        /// Returns `market_id` together with the outcome shares of `who` and, if the market is
+        /// resolved, the collateral that `who` can redeem.
+        pub fn my_market_view(
+            market_id: &MarketIdOf<T>,
+            who: &T::AccountId,
+        ) -> Result<
+            MarketView<T::AccountId, T::BlockNumber, MomentOf<T>, BalanceOf<T>>,
+            DispatchError,
+        > {
+            let market = T::MarketCommons::market(market_id)?;
+            let assets = Self::outcome_assets(*market_id, &market);
+            let free = assets.iter().map(|asset| T::Shares::free_balance(*asset, who)).collect();
+            let reserved =
+                assets.iter().map(|asset| T::Shares::reserved_balance(*asset, who)).collect();
+            let winnings = match market.resolved_outcome {
+                Some(ref outcome) if market.status == MarketStatus::Resolved => {
+                    Some(Self::hypothetical_winnings(market_id, who, outcome)?)
+                }
+                _ => None,
+            };
+            Ok(MarketView { market, free, reserved, winnings })
+        }
+
+        // Returns `oracle_bond` or `OracleBond` if none was specified. Fails if the bond is lower
+        // than `OracleBond`.
+        fn oracle_bond_or_default(
+            oracle_bond: Option<BalanceOf<T>>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let min_oracle_bond = T::OracleBond::get();
+            let oracle_bond = oracle_bond.unwrap_or(min_oracle_bond);
+            ensure!(oracle_bond >= min_oracle_bond, Error::<T>::OracleBondTooLow);
+            Ok(oracle_bond)
+        }
+
+        /// Returns the amount of collateral that would be paid out to the holders of winning
+        /// shares if `market_id` resolved to `outcome`. For scalar markets, `outcome` is the
+        /// hypothetical resolution value.
+        pub fn outcome_payout_liability(
+            market_id: &MarketIdOf<T>,
+            outcome: &OutcomeReport,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            Self::ensure_outcome_matches_market_type(&market, outcome)?;
+            Self::winning_shares_liability(*market_id, &market, outcome)
+        }
+
+        /// Returns the number of complete sets of `market_id` that are outstanding, which is the
+        /// lowest total issuance among the outcome assets of the market.
+        pub fn outstanding_complete_sets(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            Ok(Self::outcome_assets(*market_id, &market)
+                .into_iter()
+                .map(T::Shares::total_issuance)
+                .min()
+                .unwrap_or_else(Zero::zero))
+        }
+
+        /// Returns the collateral that is paid out for `BASE` units of each outcome asset of the
+        /// resolved market `market_id`, indexed like the outcome assets. Scalar markets list the
+        /// `Long` payout first. Empty if the market isn't resolved yet.
+        pub fn payout_table(
+            market_id: &MarketIdOf<T>,
+        ) -> Result<Vec<(u16, BalanceOf<T>)>, DispatchError> {
+            let market = T::MarketCommons::market(market_id)?;
+            let resolved_outcome = match market.resolved_outcome {
+                Some(ref outcome) if market.status == MarketStatus::Resolved => outcome,
+                _ => return Ok(Vec::new()),
+            };
+            let unit: BalanceOf<T> = BASE.saturated_into();
+            let payouts = match (resolved_outcome, &market.market_type) {
+                (OutcomeReport::Categorical(winner), _) => (0..market.outcomes())
+                    .map(|idx| if idx == *winner { unit } else { Zero::zero() })
+                    .collect(),
+                (OutcomeReport::Scalar(value), MarketType::Scalar(bounds)) => {
+                    let (long_percent, short_percent) = scalar_payout_percents(*value, bounds);
+                    vec![long_percent.mul_floor(unit), short_percent.mul_floor(unit)]
+                }
+                (OutcomeReport::Scalar(_), MarketType::Categorical(_)) => {
+                    return Err(Error::<T>::OutcomeMismatch.into());
+                }
+                (OutcomeReport::Invalid, _) => {
+                    let payout = Self::invalid_share_percent(&market).mul_floor(unit);
+                    vec![payout; usize::from(market.outcomes())]
+                }
+            };
+            Ok(payouts
+                .into_iter()
+                .enumerate()
+                .map(|(idx, payout)| (idx.saturated_into(), payout))
+                .collect())
+        }
+
         pub(crate) fn process_subsidy_collecting_markets(
             current_block: T::BlockNumber,
             current_time: MomentOf<T>,
@@ -1792,11 +4183,10 @@ mod pallet {
 
                             if result.result {
                                 // Sufficient subsidy, activate market.
-                                let mutate_result =
-                                    T::MarketCommons::mutate_market(&subsidy_info.market_id, |m| {
-                                        m.status = MarketStatus::Active;
-                                        Ok(())
-                                    });
+                                let mutate_result = Self::set_market_status(
+                                    &subsidy_info.market_id,
+                                    MarketStatus::Active,
+                                );
 
                                 total_weight =
                                     total_weight.saturating_add(one_read).saturating_add(one_write);
@@ -1835,14 +4225,14 @@ mod pallet {
                                     total_weight = total_weight.saturating_add(weight);
                                 }
 
+                                let oracle_bond = Self::oracle_bond(&subsidy_info.market_id);
+                                let validity_bond = Self::validity_bond(&subsidy_info.market_id);
                                 let market_result =
                                     T::MarketCommons::mutate_market(&subsidy_info.market_id, |m| {
-                                        m.status = MarketStatus::InsufficientSubsidy;
-
                                         // Unreserve funds reserved during market creation
                                         if m.creation == MarketCreation::Permissionless {
                                             let required_bond =
-                                                T::ValidityBond::get() + T::OracleBond::get();
+                                                validity_bond.saturating_add(oracle_bond);
                                             CurrencyOf::<T>::unreserve_named(
                                                 &RESERVE_ID,
                                                 &m.creator,
@@ -1854,7 +4244,7 @@ mod pallet {
                                             CurrencyOf::<T>::unreserve_named(
                                                 &RESERVE_ID,
                                                 &m.creator,
-                                                T::OracleBond::get(),
+                                                oracle_bond,
                                             );
                                         }
 
@@ -1862,6 +4252,12 @@ mod pallet {
                                             .saturating_add(dbweight.reads(2))
                                             .saturating_add(dbweight.writes(2));
                                         Ok(())
+                                    })
+                                    .and_then(|_| {
+                                        Self::set_market_status(
+                                            &subsidy_info.market_id,
+                                            MarketStatus::InsufficientSubsidy,
+                                        )
                                     });
 
                                 if let Err(err) = market_result {
@@ -1913,6 +4309,60 @@ mod pallet {
             weight_basis.saturating_add(total_weight)
         }
 
+        // Removes the proposed markets whose review deadline is `now`. Proposals beyond
+        // `MaxExpiredProposalsPerBlock` are postponed to the next block.
+        fn remove_expired_proposals(now: T::BlockNumber) -> Weight {
+            let mut total_weight = T::DbWeight::get().reads_writes(1, 1);
+            let mut market_ids = MarketIdsPerReviewDeadline::<T>::take(&now);
+            let max_removals: usize = T::MaxExpiredProposalsPerBlock::get().saturated_into();
+            if market_ids.len() > max_removals {
+                let postponed = market_ids.split_off(max_removals);
+                let next_block = now.saturating_add(One::one());
+                for market_id in postponed.iter() {
+                    ReviewDeadlines::<T>::insert(market_id, next_block);
+                }
+                total_weight = total_weight.saturating_add(
+                    T::DbWeight::get()
+                        .reads_writes(1, postponed.len().saturating_add(1).saturated_into()),
+                );
+                MarketIdsPerReviewDeadline::<T>::mutate(&next_block, |ids| {
+                    ids.extend(postponed);
+                });
+            }
+            for market_id in market_ids {
+                ReviewDeadlines::<T>::remove(&market_id);
+                let market = match T::MarketCommons::market(&market_id) {
+                    Ok(market) if market.status == MarketStatus::Proposed => market,
+                    _ => continue,
+                };
+                if Self::remove_expired_proposal(&market_id, &market).is_err() {
+                    continue;
+                }
+                total_weight = total_weight.saturating_add(T::WeightInfo::reject_market());
+            }
+            total_weight
+        }
+
+        // Removes the proposed market `market_id` after its review deadline passed and returns all
+        // of its bonds to the creator, who isn't at fault for the missing review.
+        fn remove_expired_proposal(
+            market_id: &MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> DispatchResult {
+            let bonds = Self::creation_bonds(market_id, &market.creation);
+            CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &market.creator, bonds.total());
+            Self::refund_market_account(market_id, &market.creator)?;
+            T::MarketCommons::remove_market(market_id)?;
+            MarketsByOracle::<T>::remove(&market.oracle, market_id);
+            MarketBonds::<T>::remove(market_id);
+            OutcomeDescriptors::<T>::remove(market_id);
+            OracleGracePeriods::<T>::remove(market_id);
+            Self::release_auto_deploy_pool_seed(market_id, &market.creator);
+            Self::clear_review_deadline(market_id);
+            Self::deposit_event(Event::MarketExpired(*market_id));
+            Ok(())
+        }
+
         fn remove_last_dispute_from_market_ids_per_dispute_block(
             disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
             market_id: &MarketIdOf<T>,
@@ -1926,6 +4376,94 @@ mod pallet {
             Ok(())
         }
 
+        // Moves `market_id` from the block of the last dispute to `curr_block_num`. If the last
+        // dispute was submitted in the same block, the market is already scheduled there and
+        // nothing is moved. Never schedules the market twice for the same block.
+        fn reschedule_dispute_block(
+            disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
+            market_id: &MarketIdOf<T>,
+            curr_block_num: T::BlockNumber,
+        ) -> DispatchResult {
+            if disputes.last().map(|dispute| dispute.at) == Some(curr_block_num) {
+                return Ok(());
+            }
+            Self::remove_last_dispute_from_market_ids_per_dispute_block(disputes, market_id)?;
+            MarketIdsPerDisputeBlock::<T>::mutate(curr_block_num, |ids| {
+                if !ids.contains(market_id) {
+                    ids.push(*market_id);
+                }
+            });
+            Ok(())
+        }
+
+        /// Returns the end of the reporting period of `market`, in the unit of its market period.
+        /// Until then, the oracle may correct its report. For timestamp markets,
+        /// `ReportingPeriod` is converted into milliseconds using `MillisecondsPerBlock`.
+        pub fn reporting_deadline(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> MarketEnd<T::BlockNumber, MomentOf<T>> {
+            Self::market_end_after(market, T::ReportingPeriod::get())
+        }
+
+        /// Returns the end of the oracle grace period of `market_id`, in the unit of its market
+        /// period. Until then, only the oracle may report. For timestamp markets, the grace period
+        /// is converted into milliseconds using `MillisecondsPerBlock`.
+        pub fn oracle_grace_deadline(
+            market_id: &MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        ) -> MarketEnd<T::BlockNumber, MomentOf<T>> {
+            let grace_period =
+                OracleGracePeriods::<T>::get(market_id).unwrap_or_else(T::OracleGracePeriod::get);
+            Self::market_end_after(market, grace_period)
+        }
+
+        // The end of `market` delayed by `blocks`, which are converted into milliseconds for
+        // timestamp markets.
+        fn market_end_after(
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            blocks: u32,
+        ) -> MarketEnd<T::BlockNumber, MomentOf<T>> {
+            match market.period {
+                MarketPeriod::Block(ref range) => {
+                    MarketEnd::Block(range.end.saturating_add(blocks.into()))
+                }
+                MarketPeriod::Timestamp(ref range) => {
+                    let blocks_in_ms = MomentOf::<T>::from(blocks)
+                        .saturating_mul(T::MillisecondsPerBlock::get().into());
+                    MarketEnd::Timestamp(range.end.saturating_add(blocks_in_ms))
+                }
+            }
+        }
+
+        /// Returns the block in which `market_id` is resolved automatically, i.e. `DisputePeriod`
+        /// blocks after its report or its last dispute, or the end of its global dispute. `None`
+        /// if the market doesn't exist or is neither reported nor disputed.
+        pub fn scheduled_resolution_block(market_id: &MarketIdOf<T>) -> Option<T::BlockNumber> {
+            let market = T::MarketCommons::market(market_id).ok()?;
+            let last_block = match market.status {
+                MarketStatus::Reported => market.report?.at,
+                MarketStatus::Disputed => {
+                    if let Some(end) = GlobalDisputeEnds::<T>::get(market_id) {
+                        return Some(end);
+                    }
+                    Disputes::<T>::get(market_id).last()?.at
+                }
+                _ => return None,
+            };
+            Some(last_block.saturating_add(T::DisputePeriod::get()))
+        }
+
+        /// Reserves `amount` of the outcome shares `asset` of `who`, e.g. to hold them in escrow
+        /// for an order. Reserved shares can't be sold or redeemed until they're unreserved.
+        pub fn reserve_shares(
+            asset: Asset<MarketIdOf<T>>,
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_outcome_asset_of_existing_market(&asset)?;
+            T::Shares::reserve(asset, who, amount)
+        }
+
         fn resolution_manager<F>(now: T::BlockNumber, mut cb: F) -> DispatchResult
         where
             F: FnMut(
@@ -1933,6 +4471,15 @@ mod pallet {
                 &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
             ) -> DispatchResult,
         {
+            // Resolve all markets whose global dispute ends.
+            let global_disputed_ids = MarketIdsPerGlobalDisputeEnd::<T>::take(&now);
+            for id in &global_disputed_ids {
+                let market = T::MarketCommons::market(id)?;
+                if let MarketStatus::Disputed = market.status {
+                    cb(id, &market)?;
+                }
+            }
+
             let dispute_period = T::DisputePeriod::get();
             if now <= dispute_period {
                 return Ok(());
@@ -1953,24 +4500,91 @@ mod pallet {
             let disputed_ids = MarketIdsPerDisputeBlock::<T>::get(&block);
             for id in &disputed_ids {
                 let market = T::MarketCommons::market(id)?;
-                cb(id, &market)?;
+                if market.status != MarketStatus::ResolutionStalled {
+                    cb(id, &market)?;
+                }
             }
 
             Ok(())
         }
 
-        // If the market is already disputed, does nothing.
-        fn set_market_as_disputed(
-            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+        // Resolves `market_id` if it has `status` and is still waiting for the resolution that was
+        // due for the reports or disputes of `block`. Returns whether the market has to stay in
+        // the block index, which is only the case if the resolution failed.
+        fn resolve_overdue_market(
+            block: &T::BlockNumber,
             market_id: &MarketIdOf<T>,
-        ) -> DispatchResult {
-            if market.status != MarketStatus::Disputed {
-                T::MarketCommons::mutate_market(market_id, |m| {
-                    m.status = MarketStatus::Disputed;
-                    Ok(())
-                })?;
+            status: MarketStatus,
+        ) -> (bool, Weight) {
+            let mut weight = T::DbWeight::get().reads(1);
+            let market = match T::MarketCommons::market(market_id) {
+                Ok(market) if market.status == status => market,
+                _ => return (false, weight),
+            };
+            let due_block = if status == MarketStatus::Disputed {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                Disputes::<T>::get(market_id).last().map(|dispute| dispute.at)
+            } else {
+                market.report.as_ref().map(|report| report.at)
+            };
+            if due_block.as_ref() != Some(block) {
+                return (false, weight);
             }
-            Ok(())
+
+            match Self::on_resolution_transactional(market_id, &market) {
+                Ok(resolve_weight) => (false, weight.saturating_add(resolve_weight)),
+                Err(err) => {
+                    log::error!("Market {:?} could not be resolved. Error: {:?}", market_id, err);
+                    let stalled = Self::record_failed_resolution(market_id);
+                    (!stalled, weight.saturating_add(T::DbWeight::get().reads_writes(1, 2)))
+                }
+            }
+        }
+
+        // Counts a failed automatic resolution of `market_id` and stalls the market once its
+        // resolution failed `MaxResolutionFailures` times. Returns whether the market was stalled.
+        fn record_failed_resolution(market_id: &MarketIdOf<T>) -> bool {
+            let failures = ResolutionFailures::<T>::mutate(market_id, |failures| {
+                *failures = failures.saturating_add(1);
+                *failures
+            });
+            if failures < T::MaxResolutionFailures::get() {
+                return false;
+            }
+            let stalled = Self::set_market_status(market_id, MarketStatus::ResolutionStalled);
+            if stalled.is_err() {
+                return false;
+            }
+            ResolutionFailures::<T>::remove(market_id);
+            Self::deposit_event(Event::MarketResolutionStalled(*market_id));
+            true
+        }
+
+        fn schedule_review_deadline(market_id: MarketIdOf<T>, deadline: T::BlockNumber) {
+            ReviewDeadlines::<T>::insert(market_id, deadline);
+            MarketIdsPerReviewDeadline::<T>::mutate(&deadline, |ids| {
+                ids.push(market_id);
+            });
+        }
+
+        // Sets the status of `market_id` to `new_status` and keeps everything derived from the
+        // status, i.e. `DisputedMarketsCount`, in sync. Every status change of an existing market
+        // must go through this function.
+        fn set_market_status(
+            market_id: &MarketIdOf<T>,
+            new_status: MarketStatus,
+        ) -> DispatchResult {
+            T::MarketCommons::mutate_market(market_id, |market| {
+                let was_disputed = market.status == MarketStatus::Disputed;
+                let is_disputed = new_status == MarketStatus::Disputed;
+                if !was_disputed && is_disputed {
+                    DisputedMarketsCount::<T>::mutate(|count| *count = count.saturating_add(1));
+                } else if was_disputed && !is_disputed {
+                    Self::decrement_disputed_markets_count();
+                }
+                market.status = new_status;
+                Ok(())
+            })
         }
 
         // If a market has a pool that is `Active`, then changes from `Active` to `Stale`. If
@@ -1995,13 +4609,51 @@ mod pallet {
             Ok(weight.saturating_add(T::DbWeight::get().reads(2)))
         }
 
+        // Returns the stakes of the global dispute voters of `market_id` that voted for
+        // `resolved_outcome` and rewards them with the slashed stakes of all other voters,
+        // proportional to their own stake. Does nothing if the market had no global dispute.
+        fn settle_global_dispute(market_id: &MarketIdOf<T>, resolved_outcome: &OutcomeReport) {
+            GlobalDisputeEnds::<T>::remove(market_id);
+            let votes: Vec<_> = GlobalDisputeVotes::<T>::drain_prefix(market_id).collect();
+
+            let mut winning_stake = BalanceOf::<T>::zero();
+            let mut overall_imbalance = NegativeImbalanceOf::<T>::zero();
+            for (voter, (outcome, stake)) in &votes {
+                if outcome == resolved_outcome {
+                    CurrencyOf::<T>::unreserve_named(&RESERVE_ID, voter, *stake);
+                    winning_stake = winning_stake.saturating_add(*stake);
+                } else {
+                    let (imbalance, _) =
+                        CurrencyOf::<T>::slash_reserved_named(&RESERVE_ID, voter, *stake);
+                    overall_imbalance.subsume(imbalance);
+                }
+            }
+
+            let total_reward = overall_imbalance.peek();
+            for (voter, (outcome, stake)) in &votes {
+                if outcome != resolved_outcome {
+                    continue;
+                }
+                let ratio = Perbill::from_rational(
+                    stake.saturated_into::<u128>(),
+                    winning_stake.saturated_into::<u128>(),
+                );
+                let (amount, leftover) = overall_imbalance.split(ratio.mul_floor(total_reward));
+                CurrencyOf::<T>::resolve_creating(voter, amount);
+                overall_imbalance = leftover;
+            }
+
+            // rounding leftovers or the stakes of a global dispute without winning voters
+            T::Slash::on_unbalanced(overall_imbalance);
+        }
+
         // Creates a pool for the market and registers the market in the list of markets
         // currently collecting subsidy.
         pub(crate) fn start_subsidy(
             market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
             market_id: MarketIdOf<T>,
         ) -> Result<Weight, DispatchError> {
-            ensure!(T::MarketCommons::market_pool(&market_id).is_err(), Error::<T>::SwapPoolExists);
+            Self::ensure_market_has_no_pool(&market_id)?;
             ensure!(
                 market.status == MarketStatus::CollectingSubsidy,
                 Error::<T>::MarketIsNotCollectingSubsidy
@@ -2030,20 +4682,220 @@ mod pallet {
             Ok(T::WeightInfo::start_subsidy(total_assets.saturated_into()))
         }
 
+        // Returns the outcome assets of `market_id` that `who` holds and that pay out if the market
+        // resolves to `outcome` as `(asset, payout, balance)`. The payout doesn't account for
+        // pro-rata redemption.
+        fn winning_assets(
+            market_id: MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            who: &T::AccountId,
+            outcome: &OutcomeReport,
+        ) -> Result<Vec<(Asset<MarketIdOf<T>>, BalanceOf<T>, BalanceOf<T>)>, DispatchError>
+        {
+            Ok(match *outcome {
+                OutcomeReport::Categorical(category_index) => {
+                    let winning_currency_id = Asset::CategoricalOutcome(market_id, category_index);
+                    if T::ZeroSupplyRefund::get()
+                        && T::Shares::total_issuance(winning_currency_id).is_zero()
+                    {
+                        return Ok(Self::zero_supply_refund_assets(market_id, market, who));
+                    }
+                    let winning_balance = T::Shares::free_balance(winning_currency_id, who);
+
+                    vec![(winning_currency_id, winning_balance, winning_balance)]
+                }
+                OutcomeReport::Scalar(value) => {
+                    let long_currency_id = Asset::ScalarOutcome(market_id, ScalarPosition::Long);
+                    let short_currency_id = Asset::ScalarOutcome(market_id, ScalarPosition::Short);
+                    let long_balance = T::Shares::free_balance(long_currency_id, who);
+                    let short_balance = T::Shares::free_balance(short_currency_id, who);
+
+                    let bound = if let MarketType::Scalar(ref range) = market.market_type {
+                        range
+                    } else {
+                        return Err(Error::<T>::InvalidMarketType.into());
+                    };
+
+                    let (long_percent, short_percent) = scalar_payout_percents(value, bound);
+
+                    let long_payout = long_percent.mul_floor(long_balance);
+                    let short_payout = short_percent.mul_floor(short_balance);
+
+                    vec![
+                        (long_currency_id, long_payout, long_balance),
+                        (short_currency_id, short_payout, short_balance),
+                    ]
+                }
+                OutcomeReport::Invalid => {
+                    let share_percent = Self::invalid_share_percent(market);
+                    Self::outcome_assets(market_id, market)
+                        .into_iter()
+                        .filter_map(|asset| {
+                            let balance = T::Shares::free_balance(asset, who);
+                            if balance.is_zero() {
+                                None
+                            } else {
+                                Some((asset, share_percent.mul_floor(balance), balance))
+                            }
+                        })
+                        .collect()
+                }
+            })
+        }
+
+        /// Splits the `gross` payout of a redemption into `(to_creator, to_treasury, to_redeemer)`.
+        /// The fees are rounded down, so the three parts always sum up to exactly `gross`.
+        pub(crate) fn split_redemption_payout(
+            gross: BalanceOf<T>,
+            creator_fee: Perbill,
+            treasury_fee: Perbill,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let to_creator = creator_fee.mul_floor(gross);
+            let to_treasury = treasury_fee.mul_floor(gross);
+            let fees = to_creator.checked_add(&to_treasury).ok_or(ArithmeticError::Overflow)?;
+            let to_redeemer = gross.checked_sub(&fees).ok_or(ArithmeticError::Underflow)?;
+            Ok((to_creator, to_treasury, to_redeemer))
+        }
+
+        // Pays out every outcome share of `who` with an equal part of the collateral in the market
+        // account, excluding its existential deposit. Used if the winning outcome of a
+        // categorical market has no supply.
+        fn zero_supply_refund_assets(
+            market_id: MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            who: &T::AccountId,
+        ) -> Vec<(Asset<MarketIdOf<T>>, BalanceOf<T>, BalanceOf<T>)> {
+            let assets = Self::outcome_assets(market_id, market);
+            let total_supply = assets.iter().fold(BalanceOf::<T>::zero(), |acc, asset| {
+                acc.saturating_add(T::Shares::total_issuance(*asset))
+            });
+            let escrow = CurrencyOf::<T>::free_balance(&Self::market_account(market_id))
+                .saturating_sub(CurrencyOf::<T>::minimum_balance());
+            assets
+                .into_iter()
+                .filter_map(|asset| {
+                    let balance = T::Shares::free_balance(asset, who);
+                    if balance.is_zero() {
+                        return None;
+                    }
+                    let share = Perbill::from_rational(
+                        balance.saturated_into::<u128>(),
+                        total_supply.saturated_into::<u128>(),
+                    );
+                    Some((asset, share.mul_floor(escrow), balance))
+                })
+                .collect()
+        }
+
+        // The total amount of collateral that is required to pay out every outstanding
+        // winning share of a market that resolved (or would resolve) to `resolved_outcome`.
+        fn winning_shares_liability(
+            market_id: MarketIdOf<T>,
+            market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
+            resolved_outcome: &OutcomeReport,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            match *resolved_outcome {
+                OutcomeReport::Categorical(category_index) => Ok(T::Shares::total_issuance(
+                    Asset::CategoricalOutcome(market_id, category_index),
+                )),
+                OutcomeReport::Scalar(value) => {
+                    let bound = if let MarketType::Scalar(ref range) = market.market_type {
+                        range
+                    } else {
+                        return Err(Error::<T>::InvalidMarketType.into());
+                    };
+                    let (long_percent, short_percent) = scalar_payout_percents(value, bound);
+                    let long_issuance = T::Shares::total_issuance(Asset::ScalarOutcome(
+                        market_id,
+                        ScalarPosition::Long,
+                    ));
+                    let short_issuance = T::Shares::total_issuance(Asset::ScalarOutcome(
+                        market_id,
+                        ScalarPosition::Short,
+                    ));
+                    Ok(long_percent
+                        .mul_floor(long_issuance)
+                        .saturating_add(short_percent.mul_floor(short_issuance)))
+                }
+                OutcomeReport::Invalid => {
+                    let total_issuance = Self::outcome_assets(market_id, market)
+                        .into_iter()
+                        .fold(BalanceOf::<T>::zero(), |acc, asset| {
+                            acc.saturating_add(T::Shares::total_issuance(asset))
+                        });
+                    Ok(Self::invalid_share_percent(market).mul_floor(total_issuance))
+                }
+            }
+        }
+
+        /// Unreserves up to `amount` of the outcome shares `asset` of `who`. Returns the amount
+        /// that couldn't be unreserved.
+        pub fn unreserve_shares(
+            asset: Asset<MarketIdOf<T>>,
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            T::Shares::unreserve(asset, who, amount)
+        }
+
+        // Adds `market_id` to the positions of `who` if `who` holds any of `assets` and removes
+        // it otherwise.
+        fn update_account_position(
+            who: &T::AccountId,
+            market_id: MarketIdOf<T>,
+            assets: &[Asset<MarketIdOf<T>>],
+        ) {
+            let has_position = !T::Shares::total_balance_of_assets(assets, who).is_zero();
+            let had_position = AccountPositions::<T>::contains_key(who, market_id);
+            if has_position && !had_position {
+                AccountPositions::<T>::insert(who, market_id, ());
+                Self::deposit_event(Event::PositionOpened(market_id, who.clone()));
+            } else if !has_position && had_position {
+                AccountPositions::<T>::remove(who, market_id);
+                Self::deposit_event(Event::PositionClosed(market_id, who.clone()));
+            }
+        }
+
+        // Returns the validity bond that was reserved from the creator of the permissionless
+        // market `market_id`.
+        fn validity_bond(market_id: &MarketIdOf<T>) -> BalanceOf<T> {
+            MarketBonds::<T>::get(market_id)
+                .map_or_else(T::ValidityBond::get, |bonds| bonds.validity)
+        }
+
+        // Returns `validity_bond` or `ValidityBond` if none was specified. Fails if the bond is
+        // lower than `ValidityBond`.
+        fn validity_bond_or_default(
+            validity_bond: Option<BalanceOf<T>>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let min_validity_bond = T::ValidityBond::get();
+            let validity_bond = validity_bond.unwrap_or(min_validity_bond);
+            ensure!(validity_bond >= min_validity_bond, Error::<T>::ValidityBondTooLow);
+            Ok(validity_bond)
+        }
+
         fn validate_dispute(
             disputes: &[MarketDispute<T::AccountId, T::BlockNumber>],
             market: &Market<T::AccountId, T::BlockNumber, MomentOf<T>>,
             num_disputes: u32,
             outcome: &OutcomeReport,
+            who: &T::AccountId,
         ) -> DispatchResult {
+            ensure!(
+                matches!(market.status, MarketStatus::Reported | MarketStatus::Disputed),
+                Error::<T>::MarketIsNotReportedOrDisputed
+            );
             ensure!(market.report.is_some(), Error::<T>::MarketNotReported);
+            ensure!(
+                T::AllowOracleSelfDispute::get() || who != &market.oracle,
+                Error::<T>::OracleCannotDispute
+            );
             Self::ensure_outcome_matches_market_type(market, outcome)?;
-            Self::ensure_can_not_dispute_the_same_outcome(
-                disputes,
-                (&market.report.as_ref()).ok_or(Error::<T>::MarketNotReported)?,
-                outcome,
-            )?;
-            Self::ensure_disputes_does_not_exceed_max_disputes(num_disputes)?;
+            let report = market.report.as_ref().ok_or(Error::<T>::MarketNotReported)?;
+            Self::ensure_can_not_dispute_the_same_outcome(disputes, report, outcome)?;
+            Self::ensure_scalar_dispute_is_in_step(disputes, report, outcome)?;
+            Self::ensure_disputes_does_not_exceed_max_disputes(market, num_disputes)?;
+            Self::ensure_account_disputes_do_not_exceed_max(disputes, who)?;
             Ok(())
         }
     }
@@ -2053,9 +4905,11 @@ mod pallet {
     where
         T: Config,
     {
-        T::DisputeBond::get().saturating_add(
-            T::DisputeFactor::get().saturating_mul(n.saturated_into::<u32>().into()),
-        )
+        T::DisputeBond::get()
+            .saturating_add(
+                T::DisputeFactor::get().saturating_mul(n.saturated_into::<u32>().into()),
+            )
+            .max(T::MinEffectiveDisputeBond::get())
     }
 
     fn remove_item<I: cmp::PartialEq>(items: &mut Vec<I>, item: &I) {
@@ -7,12 +7,13 @@
 use crate as prediction_markets;
 use frame_support::{
     construct_runtime, ord_parameter_types, parameter_types,
-    traits::{Everything, OnFinalize, OnInitialize},
+    traits::{Currency, Everything, OnFinalize, OnInitialize, OnUnbalanced},
 };
 use frame_system::EnsureSignedBy;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+    Perbill,
 };
 use substrate_fixed::{types::extra::U33, FixedI128, FixedU128};
 use zeitgeist_primitives::{
@@ -20,15 +21,17 @@ use zeitgeist_primitives::{
         AdvisoryBond, AuthorizedPalletId, BalanceFractionalDecimals, BlockHashCount,
         CourtCaseDuration, CourtPalletId, DisputeBond, DisputeFactor, DustAccountTest,
         ExistentialDeposit, ExistentialDeposits, ExitFee, GetNativeCurrencyId,
-        LiquidityMiningPalletId, MaxAssets, MaxCategories, MaxDisputes, MaxInRatio, MaxOutRatio,
-        MaxReserves, MaxSubsidyPeriod, MaxTotalWeight, MaxWeight, MinAssets, MinCategories,
-        MinLiquidity, MinSubsidy, MinSubsidyPeriod, MinWeight, MinimumPeriod, OracleBond,
-        PmPalletId, ReportingPeriod, SimpleDisputesPalletId, StakeWeight, SwapsPalletId,
-        TreasuryPalletId, ValidityBond, BASE,
+        LiquidityMiningPalletId, MaxAssets, MaxCategories, MaxDisputes, MaxDisputesPerAccount,
+        MaxInRatio, MaxOutRatio, MaxOutcomeLabelLength, MaxReserves, MaxSubsidyPeriod,
+        MaxTotalWeight, MaxWeight, MinAssets, MinCategories, MinLiquidity, MinSubsidy,
+        MinSubsidyPeriod, MinWeight, MinimumPeriod, PmPalletId, ReportingPeriod, ResolutionReward,
+        SimpleDisputesPalletId, StakeWeight, SwapsPalletId, TreasuryPalletId, ValidityBond, BASE,
     },
     types::{
         AccountIdTest, Amount, Asset, Balance, BasicCurrencyAdapter, BlockNumber, BlockTest,
-        CurrencyId, Hash, Index, MarketId, Moment, PoolId, SerdeWrapper, UncheckedExtrinsicTest,
+        BondBreakdown, CurrencyId, DisputeResolutionRule, Hash, Index, MarketEndKinds, MarketId,
+        MarketView, Moment, OutcomeReport, PoolId, Report, ScalarDisputeStep,
+        ScalarResolutionPolicy, SerdeWrapper, UncheckedExtrinsicTest, ValidityBondPolicy,
     },
 };
 use zrml_rikiddo::types::{EmaMarketVolume, FeeSigmoid, RikiddoSigmoidMV};
@@ -45,7 +48,74 @@ ord_parameter_types! {
     pub const Sudo: AccountIdTest = SUDO;
 }
 parameter_types! {
+    pub const AdvisoryReviewPeriod: BlockNumber = 50;
+    pub static AllowOracleSelfDispute: bool =
+        zeitgeist_primitives::constants::AllowOracleSelfDispute::get();
+    pub static AllowPublicReporting: bool = true;
+    pub static AllowedMarketEndKinds: MarketEndKinds =
+        zeitgeist_primitives::constants::AllowedMarketEndKinds::get();
+    pub static CancellationPenalty: Perbill =
+        zeitgeist_primitives::constants::CancellationPenalty::get();
+    pub static CompleteSetMintFee: Perbill =
+        zeitgeist_primitives::constants::CompleteSetMintFee::get();
     pub const DisputePeriod: BlockNumber = 10;
+    pub static DisputeResolution: DisputeResolutionRule = DisputeResolutionRule::LastDispute;
+    pub const GlobalDisputePeriod: BlockNumber = 20;
+    pub static MaxAdditionalMetadata: u32 =
+        zeitgeist_primitives::constants::MaxAdditionalMetadata::get();
+    pub static MaxConcurrentDisputedMarkets: u32 =
+        zeitgeist_primitives::constants::MaxConcurrentDisputedMarkets::get();
+    pub static MaxDestroyedShareAccounts: u32 =
+        zeitgeist_primitives::constants::MaxDestroyedShareAccounts::get();
+    pub static MaxExpiredProposalsPerBlock: u32 =
+        zeitgeist_primitives::constants::MaxExpiredProposalsPerBlock::get();
+    pub static MaxPoolDeploymentsPerBatch: u32 =
+        zeitgeist_primitives::constants::MaxPoolDeploymentsPerBatch::get();
+    pub static MaxResolutionFailures: u32 =
+        zeitgeist_primitives::constants::MaxResolutionFailures::get();
+    pub static MaxTotalMetadataBytes: u32 = 1024;
+    pub static MillisecondsPerBlock: u32 =
+        zeitgeist_primitives::constants::MillisecondsPerBlock::get();
+    pub static MinEffectiveDisputeBond: Balance =
+        zeitgeist_primitives::constants::MinEffectiveDisputeBond::get();
+    pub static MinGlobalDisputeInitialStake: Balance =
+        zeitgeist_primitives::constants::MinGlobalDisputeInitialStake::get();
+    pub static MinGlobalDisputeStake: Balance =
+        zeitgeist_primitives::constants::MinGlobalDisputeStake::get();
+    pub static MonotonicScalarDisputes: ScalarDisputeStep =
+        zeitgeist_primitives::constants::MonotonicScalarDisputes::get();
+    pub static OracleBond: Balance = zeitgeist_primitives::constants::OracleBond::get();
+    pub static OracleGracePeriod: u32 = zeitgeist_primitives::constants::OracleGracePeriod::get();
+    pub static PoolWeightsSum: Option<u128> =
+        zeitgeist_primitives::constants::PoolWeightsSum::get();
+    pub static ProRataRedemption: bool = true;
+    pub static RedemptionTreasuryFee: Perbill =
+        zeitgeist_primitives::constants::RedemptionTreasuryFee::get();
+    pub static ScalarResolution: ScalarResolutionPolicy = ScalarResolutionPolicy::LastDispute;
+    pub static SlashSplit: Perbill = zeitgeist_primitives::constants::SlashSplit::get();
+    pub static ValidityBondSettlement: ValidityBondPolicy =
+        zeitgeist_primitives::constants::ValidityBondSettlement::get();
+    pub static ZeroSupplyRefund: bool = zeitgeist_primitives::constants::ZeroSupplyRefund::get();
+}
+
+// Collects everything that is slashed by the pallet.
+pub struct Treasury;
+
+impl Treasury {
+    pub fn account() -> AccountIdTest {
+        TreasuryPalletId::get().into_account()
+    }
+
+    /// Returns the amount slashed by the pallet so far.
+    pub fn balance() -> Balance {
+        Balances::free_balance(&Self::account())
+    }
+}
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for Treasury {
+    fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+        Balances::resolve_creating(&Self::account(), amount);
+    }
 }
 
 construct_runtime!(
@@ -74,29 +144,60 @@ construct_runtime!(
 
 impl crate::Config for Runtime {
     type AdvisoryBond = AdvisoryBond;
+    type AdvisoryReviewPeriod = AdvisoryReviewPeriod;
+    type AllowOracleSelfDispute = AllowOracleSelfDispute;
+    type AllowPublicReporting = AllowPublicReporting;
+    type AllowedMarketEndKinds = AllowedMarketEndKinds;
     type ApprovalOrigin = EnsureSignedBy<Sudo, AccountIdTest>;
     type Authorized = Authorized;
+    type CancellationPenalty = CancellationPenalty;
+    type CompleteSetMintFee = CompleteSetMintFee;
     type Court = Court;
     type DisputeBond = DisputeBond;
     type DisputeFactor = DisputeFactor;
     type DisputePeriod = DisputePeriod;
+    type DisputeResolutionRule = DisputeResolution;
     type Event = Event;
+    type GlobalDisputePeriod = GlobalDisputePeriod;
     type LiquidityMining = LiquidityMining;
     type MarketCommons = MarketCommons;
+    type MaxAdditionalMetadata = MaxAdditionalMetadata;
     type MaxCategories = MaxCategories;
+    type MaxConcurrentDisputedMarkets = MaxConcurrentDisputedMarkets;
+    type MaxDestroyedShareAccounts = MaxDestroyedShareAccounts;
     type MaxDisputes = MaxDisputes;
+    type MaxDisputesPerAccount = MaxDisputesPerAccount;
+    type MaxExpiredProposalsPerBlock = MaxExpiredProposalsPerBlock;
+    type MaxOutcomeLabelLength = MaxOutcomeLabelLength;
+    type MaxPoolDeploymentsPerBatch = MaxPoolDeploymentsPerBatch;
+    type MaxResolutionFailures = MaxResolutionFailures;
     type MaxSubsidyPeriod = MaxSubsidyPeriod;
+    type MaxTotalMetadataBytes = MaxTotalMetadataBytes;
+    type MillisecondsPerBlock = MillisecondsPerBlock;
     type MinCategories = MinCategories;
+    type MinEffectiveDisputeBond = MinEffectiveDisputeBond;
+    type MinGlobalDisputeInitialStake = MinGlobalDisputeInitialStake;
+    type MinGlobalDisputeStake = MinGlobalDisputeStake;
     type MinSubsidyPeriod = MinSubsidyPeriod;
+    type MonotonicScalarDisputes = MonotonicScalarDisputes;
     type OracleBond = OracleBond;
+    type OracleGracePeriod = OracleGracePeriod;
     type PalletId = PmPalletId;
+    type PoolWeightsSum = PoolWeightsSum;
+    type ProRataRedemption = ProRataRedemption;
+    type RedemptionTreasuryFee = RedemptionTreasuryFee;
     type ReportingPeriod = ReportingPeriod;
+    type ResolutionReward = ResolutionReward;
+    type ScalarResolutionPolicy = ScalarResolution;
     type Shares = Tokens;
     type SimpleDisputes = SimpleDisputes;
-    type Slash = ();
+    type Slash = Treasury;
+    type SlashSplit = SlashSplit;
     type Swaps = Swaps;
     type ValidityBond = ValidityBond;
+    type ValidityBondPolicy = ValidityBondSettlement;
     type WeightInfo = prediction_markets::weights::WeightInfo<Runtime>;
+    type ZeroSupplyRefund = ZeroSupplyRefund;
 }
 
 impl frame_system::Config for Runtime {
@@ -286,9 +387,69 @@ pub fn run_to_block(n: BlockNumber) {
 }
 
 sp_api::mock_impl_runtime_apis! {
-    impl zrml_prediction_markets_runtime_api::PredictionMarketsApi<BlockTest<Runtime>, MarketId, Hash> for Runtime {
+    impl zrml_prediction_markets_runtime_api::PredictionMarketsApi<BlockTest<Runtime>, MarketId, Hash, Moment, Balance, AccountIdTest, BlockNumber> for Runtime {
+        fn all_market_pools() -> Vec<(MarketId, PoolId)> {
+            PredictionMarkets::all_market_pools()
+        }
+
+        fn current_report(market_id: MarketId) -> Option<Report<AccountIdTest, BlockNumber>> {
+            PredictionMarkets::current_report(&market_id)
+        }
+
+        fn dispute_bond_for(market_id: MarketId) -> Option<Balance> {
+            PredictionMarkets::dispute_bond_for(&market_id).ok()
+        }
+
+        fn dispute_preview(market_id: MarketId, outcome: OutcomeReport) -> Option<(Balance, Balance)> {
+            PredictionMarkets::dispute_preview(&market_id, &outcome).ok()
+        }
+
+        fn estimated_end_timestamp(market_id: MarketId) -> Option<Moment> {
+            PredictionMarkets::estimated_end_timestamp(&market_id).ok()
+        }
+
+        fn market_bonds(market_id: MarketId) -> Option<BondBreakdown<Balance>> {
+            PredictionMarkets::market_bonds(&market_id).ok()
+        }
+
+        fn hypothetical_winnings(market_id: MarketId, who: AccountIdTest, outcome: OutcomeReport) -> Option<Balance> {
+            PredictionMarkets::hypothetical_winnings(&market_id, &who, &outcome).ok()
+        }
+
+        fn markets_by_oracle(oracle: AccountIdTest) -> Vec<MarketId> {
+            PredictionMarkets::markets_by_oracle(&oracle)
+        }
+
+        fn markets_ending_between(from: Moment, to: Moment) -> Vec<MarketId> {
+            PredictionMarkets::markets_ending_between(from, to)
+        }
+
+        fn market_pools_paged(start: MarketId, limit: u32) -> Vec<(MarketId, PoolId)> {
+            PredictionMarkets::market_pools_paged(&start, limit)
+        }
+
         fn market_outcome_share_id(_: MarketId, _: u16) -> Asset<MarketId> {
             Asset::PoolShare(SerdeWrapper(1))
         }
+
+        fn my_market_view(market_id: MarketId, who: AccountIdTest) -> Option<MarketView<AccountIdTest, BlockNumber, Moment, Balance>> {
+            PredictionMarkets::my_market_view(&market_id, &who).ok()
+        }
+
+        fn outcome_payout_liability(market_id: MarketId, outcome: OutcomeReport) -> Option<Balance> {
+            PredictionMarkets::outcome_payout_liability(&market_id, &outcome).ok()
+        }
+
+        fn outstanding_complete_sets(market_id: MarketId) -> Option<Balance> {
+            PredictionMarkets::outstanding_complete_sets(&market_id).ok()
+        }
+
+        fn payout_table(market_id: MarketId) -> Option<Vec<(u16, Balance)>> {
+            PredictionMarkets::payout_table(&market_id).ok()
+        }
+
+        fn scheduled_resolution_block(market_id: MarketId) -> Option<BlockNumber> {
+            PredictionMarkets::scheduled_resolution_block(&market_id)
+        }
     }
 }
@@ -1,21 +1,35 @@
 #![cfg(all(feature = "mock", test))]
 
-use crate::{mock::*, Config, Error, MarketIdsPerDisputeBlock, MarketIdsPerReportBlock};
-use core::{cell::RefCell, ops::Range};
+use crate::{
+    default_dispute_bond, mock::*, weights::WeightInfoZeitgeist, AccountPositions,
+    AdditionalMetadata, AutoDeployPools, CleanupCursor, Config, DisputeBondClaims,
+    DisputedMarketsCount, Error, GlobalDisputeVotes, MarketIdsPerDisputeBlock,
+    MarketIdsPerGlobalDisputeEnd, MarketIdsPerReportBlock, MarketIdsPerReviewDeadline,
+    OutcomeDescriptors, ResolutionFailures, ReviewDeadlines,
+};
+use core::{cell::RefCell, convert::TryFrom, ops::Range};
 use frame_support::{
     assert_err, assert_noop, assert_ok,
-    dispatch::{DispatchError, DispatchResult},
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     storage_root,
-    traits::Get,
+    traits::{Get, Hooks},
 };
 
-use orml_traits::MultiCurrency;
-use sp_runtime::traits::AccountIdConversion;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{traits::AccountIdConversion, ArithmeticError, Perbill};
 use zeitgeist_primitives::{
-    constants::{AdvisoryBond, DisputeBond, DisputeFactor, OracleBond, ValidityBond, BASE, CENT},
+    constants::{
+        AdvisoryBond, DisputeBond, DisputeFactor, ExistentialDeposit, MaxMetadataLength,
+        MinLiquidity, ResolutionReward, ValidityBond, BASE, CENT,
+    },
+    traits::{Swaps as _, ZeitgeistMultiReservableCurrency},
     types::{
-        Asset, Market, MarketCreation, MarketDisputeMechanism, MarketPeriod, MarketStatus,
-        MarketType, MultiHash, OutcomeReport, ScalarPosition, ScoringRule,
+        AccountIdTest, Asset, BondBreakdown, BoundedMetadata, DisputeResolutionRule, Market,
+        MarketCreation, MarketCreationOptions, MarketDispute, MarketDisputeMechanism, MarketEnd,
+        MarketEndKinds, MarketKind, MarketPeriod, MarketStatus, MarketType, MultiHash,
+        OutcomeDescriptor, OutcomeReport, Report, ScalarDisputeStep, ScalarPosition,
+        ScalarResolutionPolicy, ScoringRule, ValidityBondPolicy,
     },
 };
 use zrml_market_commons::MarketCommonsPalletApi;
@@ -27,10 +41,25 @@ fn gen_metadata(byte: u8) -> MultiHash {
     MultiHash::Sha3_384(metadata)
 }
 
+fn bounded(bytes: &[u8]) -> BoundedMetadata {
+    BoundedMetadata::try_from(bytes.to_vec()).unwrap()
+}
+
+// The optional arguments of `simple_create_categorical_market`. Markets have `MinCategories`
+// categories unless `categories` is set.
+#[derive(Default)]
+struct CategoricalMarketOptions {
+    categories: Option<u16>,
+    outcome_descriptors: Option<Vec<OutcomeDescriptor>>,
+    auto_deploy_pool: Option<Vec<u128>>,
+    oracle_grace_period: Option<u32>,
+}
+
 fn simple_create_categorical_market<T: crate::Config>(
     creation: MarketCreation,
     period: Range<u64>,
     scoring_rule: ScoringRule,
+    options: CategoricalMarketOptions,
 ) {
     assert_ok!(PredictionMarkets::create_categorical_market(
         Origin::signed(ALICE),
@@ -38,10 +67,99 @@ fn simple_create_categorical_market<T: crate::Config>(
         MarketPeriod::Block(period),
         gen_metadata(2),
         creation,
-        T::MinCategories::get(),
+        options.categories.unwrap_or_else(T::MinCategories::get),
+        MarketDisputeMechanism::SimpleDisputes,
+        scoring_rule,
+        MarketCreationOptions {
+            outcome_descriptors: options.outcome_descriptors,
+            auto_deploy_pool: options.auto_deploy_pool,
+            oracle_grace_period: options.oracle_grace_period,
+            ..Default::default()
+        }
+    ));
+}
+
+fn create_and_dispute_scalar_market(reported_value: u128, disputed_values: &[u128]) {
+    assert_ok!(PredictionMarkets::create_scalar_market(
+        Origin::signed(ALICE),
+        BOB,
+        MarketPeriod::Block(0..1),
+        gen_metadata(2),
+        MarketCreation::Permissionless,
+        10..=30,
+        MarketDisputeMechanism::SimpleDisputes,
+        ScoringRule::CPMM,
+        Default::default()
+    ));
+    run_to_block(2);
+    assert_ok!(PredictionMarkets::report(
+        Origin::signed(BOB),
+        0,
+        OutcomeReport::Scalar(reported_value)
+    ));
+    for (disputant, value) in [CHARLIE, DAVE, EVE, FRED].iter().zip(disputed_values) {
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(*disputant),
+            0,
+            OutcomeReport::Scalar(*value)
+        ));
+    }
+    run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+}
+
+fn create_and_dispute_categorical_market(
+    categories: u16,
+    reported_outcome: u16,
+    disputed_outcomes: &[u16],
+) {
+    assert_ok!(PredictionMarkets::create_categorical_market(
+        Origin::signed(ALICE),
+        BOB,
+        MarketPeriod::Block(0..1),
+        gen_metadata(2),
+        MarketCreation::Permissionless,
+        categories,
         MarketDisputeMechanism::SimpleDisputes,
-        scoring_rule
+        ScoringRule::CPMM,
+        Default::default()
     ));
+    run_to_block(2);
+    assert_ok!(PredictionMarkets::report(
+        Origin::signed(BOB),
+        0,
+        OutcomeReport::Categorical(reported_outcome)
+    ));
+    for (disputant, outcome) in [CHARLIE, DAVE, EVE, FRED, SUDO].iter().zip(disputed_outcomes) {
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(*disputant),
+            0,
+            OutcomeReport::Categorical(*outcome)
+        ));
+    }
+    run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+}
+
+// Creates a categorical market, disputes it `MaxDisputes` times and starts a global dispute at
+// block 2.
+fn create_and_globally_dispute_categorical_market() {
+    simple_create_categorical_market::<Runtime>(
+        MarketCreation::Permissionless,
+        0..1,
+        ScoringRule::CPMM,
+        Default::default(),
+    );
+    run_to_block(2);
+    assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(0)));
+    let disputants = [CHARLIE, DAVE, EVE];
+    let max_disputes: u32 = <Runtime as Config>::MaxDisputes::get();
+    for i in 0..max_disputes {
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(disputants[i as usize % disputants.len()]),
+            0,
+            OutcomeReport::Categorical(((i + 1) % 2) as u16)
+        ));
+    }
+    assert_ok!(PredictionMarkets::global_dispute(Origin::signed(CHARLIE), 0));
 }
 
 #[test]
@@ -51,6 +169,7 @@ fn it_creates_binary_markets() {
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
 
         // check the correct amount was reserved
@@ -62,6 +181,7 @@ fn it_creates_binary_markets() {
             MarketCreation::Advised,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
 
         let new_alice_reserved = Balances::reserved_balance(&ALICE);
@@ -74,850 +194,6215 @@ fn it_creates_binary_markets() {
 }
 
 #[test]
-fn it_does_not_create_market_with_too_few_categories() {
-    ExtBuilder::default().build().execute_with(|| {
-        assert_noop!(
-            PredictionMarkets::create_categorical_market(
-                Origin::signed(ALICE),
-                BOB,
-                MarketPeriod::Block(0..100),
-                gen_metadata(2),
-                MarketCreation::Advised,
-                <Runtime as Config>::MinCategories::get() - 1,
-                MarketDisputeMechanism::SimpleDisputes,
-                ScoringRule::CPMM
-            ),
-            Error::<Runtime>::NotEnoughCategories
-        );
-    });
-}
-
-#[test]
-fn it_does_not_create_market_with_too_many_categories() {
+fn it_creates_markets_with_the_default_oracle_bond() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_noop!(
-            PredictionMarkets::create_categorical_market(
-                Origin::signed(ALICE),
-                BOB,
-                MarketPeriod::Block(0..100),
-                gen_metadata(2),
-                MarketCreation::Advised,
-                <Runtime as Config>::MaxCategories::get() + 1,
-                MarketDisputeMechanism::SimpleDisputes,
-                ScoringRule::CPMM
-            ),
-            Error::<Runtime>::TooManyCategories
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
         );
+        assert_eq!(PredictionMarkets::oracle_bond(&0), OracleBond::get());
+        let bonds = crate::MarketBonds::<Runtime>::get(0).unwrap();
+        assert_eq!(bonds.oracle, OracleBond::get());
     });
 }
 
 #[test]
-fn it_allows_sudo_to_destroy_markets() {
+fn it_returns_the_bonds_reserved_at_creation_after_the_config_changed() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates an advised market.
         simple_create_categorical_market::<Runtime>(
-            MarketCreation::Advised,
+            MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
+        let oracle_bond = OracleBond::get();
+        assert_eq!(Balances::reserved_balance(&ALICE), ValidityBond::get() + oracle_bond);
+        OracleBond::set(oracle_bond / 2);
+        assert_eq!(PredictionMarkets::oracle_bond(&0), oracle_bond);
 
-        // destroy the market
-        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
-
-        assert_noop!(
-            MarketCommons::market(&0),
-            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
-        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 1_000 * BASE - ExistentialDeposit::get());
     });
 }
 
 #[test]
-fn it_allows_advisory_origin_to_approve_markets() {
+fn it_creates_markets_with_an_elevated_oracle_bond() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates an advised market.
-        simple_create_categorical_market::<Runtime>(
-            MarketCreation::Advised,
-            0..1,
+        let oracle_bond = OracleBond::get() + 10 * BASE;
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
             ScoringRule::CPMM,
-        );
-
-        // make sure it's in status proposed
-        let market = MarketCommons::market(&0);
-        assert_eq!(market.unwrap().status, MarketStatus::Proposed);
-
-        // Make sure it fails from the random joe
-        assert_noop!(
-            PredictionMarkets::approve_market(Origin::signed(BOB), 0),
-            DispatchError::BadOrigin
-        );
-
-        // Now it should work from SUDO
-        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+            MarketCreationOptions { oracle_bond: Some(oracle_bond), ..Default::default() }
+        ));
+        assert_eq!(Balances::reserved_balance(&ALICE), ValidityBond::get() + oracle_bond);
+        assert_eq!(PredictionMarkets::oracle_bond(&0), oracle_bond);
 
-        let after_market = MarketCommons::market(&0);
-        assert_eq!(after_market.unwrap().status, MarketStatus::Active);
+        // The elevated bond is returned once the oracle reported in time.
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
     });
 }
 
 #[test]
-fn it_allows_the_advisory_origin_to_reject_markets() {
+fn markets_record_the_block_and_time_of_their_creation() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates an advised market.
+        run_to_block(5);
+        Timestamp::set_timestamp(42_000);
         simple_create_categorical_market::<Runtime>(
-            MarketCreation::Advised,
-            0..1,
+            MarketCreation::Permissionless,
+            5..10,
             ScoringRule::CPMM,
+            Default::default(),
         );
+        run_to_block(7);
+        Timestamp::set_timestamp(66_000);
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(7..10),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
 
-        // make sure it's in status proposed
-        let market = MarketCommons::market(&0);
-        assert_eq!(market.unwrap().status, MarketStatus::Proposed);
-
-        // Now it should work from SUDO
-        assert_ok!(PredictionMarkets::reject_market(Origin::signed(SUDO), 0));
-
-        assert_noop!(
-            MarketCommons::market(&0),
-            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
-        );
+        let categorical_market = MarketCommons::market(&0).unwrap();
+        assert_eq!(categorical_market.created_at, 5);
+        assert_eq!(categorical_market.created_at_timestamp, 42_000);
+        let scalar_market = MarketCommons::market(&1).unwrap();
+        assert_eq!(scalar_market.created_at, 7);
+        assert_eq!(scalar_market.created_at_timestamp, 66_000);
     });
 }
 
 #[test]
-fn it_allows_to_buy_a_complete_set() {
+fn market_creation_sets_the_kind_of_the_market() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            3,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
 
-        // Allows someone to generate a complete set
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
-
-        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(MarketCommons::market(&0).unwrap().kind, MarketKind::Binary);
+        assert_eq!(MarketCommons::market(&1).unwrap().kind, MarketKind::Categorical);
+        assert_eq!(MarketCommons::market(&2).unwrap().kind, MarketKind::Scalar);
+    });
+}
 
-        // Check the outcome balances
-        let assets = PredictionMarkets::outcome_assets(0, &market);
-        for asset in assets.iter() {
-            let bal = Tokens::free_balance(*asset, &BOB);
-            assert_eq!(bal, CENT);
+#[test]
+fn create_cpmm_market_and_deploy_assets_sets_the_kind_of_the_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        let min_liquidity = <Runtime as zrml_swaps::Config>::MinLiquidity::get();
+        let min_weight = <Runtime as zrml_swaps::Config>::MinWeight::get();
+        let market_types = vec![
+            MarketType::Categorical(2),
+            MarketType::Categorical(3),
+            MarketType::Scalar(10..=30),
+        ];
+        for (market_id, market_type) in market_types.into_iter().enumerate() {
+            let outcomes = usize::from(market_type.outcome_count());
+            let expected_kind = market_type.kind();
+            assert_ok!(PredictionMarkets::create_cpmm_market_and_deploy_assets(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..42),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                market_type,
+                MarketDisputeMechanism::SimpleDisputes,
+                min_liquidity,
+                vec![min_liquidity; outcomes],
+                vec![min_weight; outcomes + 1],
+                vec![0; outcomes],
+                None,
+                None
+            ));
+            let market = MarketCommons::market(&(market_id as u128)).unwrap();
+            assert_eq!(market.kind, expected_kind);
         }
-
-        // also check native balance
-        let bal = Balances::free_balance(&BOB);
-        assert_eq!(bal, 1_000 * BASE - CENT);
-
-        let market_account = PredictionMarkets::market_account(0);
-        let market_bal = Balances::free_balance(market_account);
-        assert_eq!(market_bal, CENT);
+        assert_eq!(MarketCommons::market(&0).unwrap().kind, MarketKind::Binary);
     });
 }
 
 #[test]
-fn it_allows_to_deploy_a_pool() {
+fn it_creates_markets_with_an_elevated_validity_bond() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
+        let validity_bond = ValidityBond::get() + 10 * BASE;
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
-
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE,));
-
-        assert_ok!(Balances::transfer(
+        assert_ok!(PredictionMarkets::create_categorical_market(
             Origin::signed(BOB),
-            <Runtime as crate::Config>::PalletId::get().into_account(),
-            100 * BASE
+            CHARLIE,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            MarketCreationOptions { validity_bond: Some(validity_bond), ..Default::default() }
         ));
-        assert_ok!(Tokens::deposit(Asset::Ztg, &BOB, 100 * BASE));
+        assert_eq!(Balances::reserved_balance(&ALICE), ValidityBond::get() + OracleBond::get());
+        assert_eq!(Balances::reserved_balance(&BOB), validity_bond + OracleBond::get());
+        assert_eq!(PredictionMarkets::market_bonds(&1).unwrap().validity, validity_bond);
 
-        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
-            Origin::signed(BOB),
-            0,
-            vec![BASE, BASE, BASE]
+        // The elevated bond is returned if the market resolves to a valid outcome.
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(CHARLIE),
+            1,
+            OutcomeReport::Categorical(1)
         ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Balances::reserved_balance(&BOB), 0);
+        assert_eq!(Balances::free_balance(&BOB), 1_000 * BASE);
     });
 }
 
 #[test]
-fn it_allows_to_sell_a_complete_set() {
+fn invalid_markets_slash_the_elevated_validity_bond() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
-        simple_create_categorical_market::<Runtime>(
+        let validity_bond = ValidityBond::get() + 10 * BASE;
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
             MarketCreation::Permissionless,
-            0..1,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
             ScoringRule::CPMM,
-        );
-
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
-
-        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, CENT));
-
-        let market = MarketCommons::market(&0).unwrap();
+            MarketCreationOptions { validity_bond: Some(validity_bond), ..Default::default() }
+        ));
 
-        // Check the outcome balances
-        let assets = PredictionMarkets::outcome_assets(0, &market);
-        for asset in assets.iter() {
-            let bal = Tokens::free_balance(*asset, &BOB);
-            assert_eq!(bal, 0);
-        }
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Invalid));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
 
-        // also check native balance
-        let bal = Balances::free_balance(&BOB);
-        assert_eq!(bal, 1_000 * BASE);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(
+            Balances::free_balance(&ALICE),
+            1_000 * BASE - validity_bond - ExistentialDeposit::get()
+        );
+        // The first slashed bonds fund the `ResolutionReward` of the pallet account.
+        assert_eq!(Treasury::balance(), validity_bond - ResolutionReward::get());
     });
 }
 
-#[test]
-fn it_allows_to_report_the_outcome_of_a_market() {
+// Resolves a permissionless market to `outcome` under `policy` and checks whether the validity
+// bond was returned to the creator.
+fn check_validity_bond_policy(policy: ValidityBondPolicy, outcome: OutcomeReport, returned: bool) {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
+        ValidityBondSettlement::set(policy);
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
-            0..100,
+            0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
-
-        run_to_block(100);
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, outcome.clone()));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
 
         let market = MarketCommons::market(&0).unwrap();
-        assert_eq!(market.status, MarketStatus::Active);
-        assert_eq!(market.report.is_none(), true);
-
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(BOB),
-            0,
-            OutcomeReport::Categorical(1)
-        ));
-
-        let market_after = MarketCommons::market(&0).unwrap();
-        let report = market_after.report.unwrap();
-        assert_eq!(market_after.status, MarketStatus::Reported);
-        assert_eq!(report.outcome, OutcomeReport::Categorical(1));
-        assert_eq!(report.by, market_after.oracle);
+        assert_eq!(market.resolved_outcome, Some(outcome));
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        let slashed = if returned { 0 } else { ValidityBond::get() };
+        assert_eq!(
+            Balances::free_balance(&ALICE),
+            1_000 * BASE - slashed - ExistentialDeposit::get()
+        );
+        assert_eq!(Treasury::balance(), slashed.saturating_sub(ResolutionReward::get()));
+    });
+}
 
-        // Reset and report again as approval origin
-        let _ = MarketCommons::mutate_market(&0, |market| {
-            market.status = MarketStatus::Closed;
-            market.report = None;
-            Ok(())
-        });
+#[test]
+fn return_if_valid_policy_only_slashes_the_validity_bond_of_invalid_markets() {
+    check_validity_bond_policy(
+        ValidityBondPolicy::ReturnIfValid,
+        OutcomeReport::Categorical(0),
+        true,
+    );
+    check_validity_bond_policy(ValidityBondPolicy::ReturnIfValid, OutcomeReport::Invalid, false);
+}
 
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(SUDO),
-            0,
-            OutcomeReport::Categorical(1)
-        ));
+#[test]
+fn always_burn_policy_slashes_the_validity_bond() {
+    check_validity_bond_policy(
+        ValidityBondPolicy::AlwaysBurn,
+        OutcomeReport::Categorical(0),
+        false,
+    );
+    check_validity_bond_policy(ValidityBondPolicy::AlwaysBurn, OutcomeReport::Invalid, false);
+}
 
-        // Reset and report again as unauthorized origin
-        let _ = MarketCommons::mutate_market(&0, |market| {
-            market.status = MarketStatus::Closed;
-            market.report = None;
-            Ok(())
-        });
+#[test]
+fn always_return_policy_returns_the_validity_bond() {
+    check_validity_bond_policy(
+        ValidityBondPolicy::AlwaysReturn,
+        OutcomeReport::Categorical(0),
+        true,
+    );
+    check_validity_bond_policy(ValidityBondPolicy::AlwaysReturn, OutcomeReport::Invalid, true);
+}
 
+#[test]
+fn it_does_not_create_markets_with_a_validity_bond_below_the_minimum() {
+    ExtBuilder::default().build().execute_with(|| {
         assert_noop!(
-            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
-            Error::<Runtime>::ReporterNotOracle
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                10..=30,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    validity_bond: Some(ValidityBond::get() - 1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Runtime>::ValidityBondTooLow
         );
     });
 }
 
 #[test]
-fn it_allows_to_dispute_the_outcome_of_a_market() {
+fn it_does_not_create_markets_if_the_creator_cannot_cover_the_bonds() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
-        simple_create_categorical_market::<Runtime>(
-            MarketCreation::Permissionless,
-            0..1,
-            ScoringRule::CPMM,
+        let bonds = ValidityBond::get() + OracleBond::get();
+        assert_ok!(Balances::transfer(Origin::signed(ALICE), CHARLIE, 1_000 * BASE - bonds + 1));
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::InsufficientBalanceForBond
         );
+    });
+}
 
-        // Run to the end of the trading phase.
-        run_to_block(100);
-
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(BOB),
-            0,
-            OutcomeReport::Categorical(1)
-        ));
-
-        // Dispute phase is 10 blocks... so only run 5 of them.
-        run_to_block(105);
-
-        assert_ok!(PredictionMarkets::dispute(
-            Origin::signed(CHARLIE),
-            0,
-            OutcomeReport::Categorical(0)
-        ));
-
-        let market = MarketCommons::market(&0).unwrap();
-        assert_eq!(market.status, MarketStatus::Disputed);
-
-        let disputes = crate::Disputes::<Runtime>::get(&0);
-        assert_eq!(disputes.len(), 1);
-        let dispute = &disputes[0];
-        assert_eq!(dispute.at, 105);
-        assert_eq!(dispute.by, CHARLIE);
-        assert_eq!(dispute.outcome, OutcomeReport::Categorical(0));
-
-        let market_ids = MarketIdsPerDisputeBlock::<Runtime>::get(&105);
-        assert_eq!(market_ids.len(), 1);
-        assert_eq!(market_ids[0], 0);
+#[test]
+fn it_does_not_create_markets_with_an_oracle_bond_below_the_minimum() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                10..=30,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    oracle_bond: Some(OracleBond::get() - 1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Runtime>::OracleBondTooLow
+        );
     });
 }
 
 #[test]
-fn it_allows_anyone_to_report_an_unreported_market() {
+fn it_creates_markets_with_metadata_up_to_the_limit() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
+        // `gen_metadata` creates a 50 byte multihash.
+        MaxTotalMetadataBytes::set(50);
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
+    });
+}
 
-        // Just skip to waaaay overdue.
-        run_to_block(9000);
-
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(ALICE), // alice reports her own market now
-            0,
-            OutcomeReport::Categorical(1),
-        ));
-
-        let market = MarketCommons::market(&0).unwrap();
-        assert_eq!(market.status, MarketStatus::Reported);
-        assert_eq!(market.report.unwrap().by, ALICE);
-        // but oracle was bob
-        assert_eq!(market.oracle, BOB);
-
-        // make sure it still resolves
-        run_to_block(9011);
-
-        let market_after = MarketCommons::market(&0).unwrap();
-        assert_eq!(market_after.status, MarketStatus::Resolved);
+#[test]
+fn it_does_not_create_markets_with_metadata_above_the_limit() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxTotalMetadataBytes::set(49);
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::MetadataTooLong
+        );
+        assert_noop!(
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                10..=30,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::MetadataTooLong
+        );
     });
 }
 
 #[test]
-fn it_correctly_resolves_a_market_that_was_reported_on() {
+fn it_stores_the_outcome_descriptors_of_a_categorical_market() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
+        let cid_v0 = [&[0x12, 0x20][..], &[7; 32]].concat();
+        let cid_v1 = [&[0x01, 0x55, 0x12, 0x20][..], &[7; 32]].concat();
+        let descriptors = vec![
+            OutcomeDescriptor::Cid(bounded(&cid_v0)),
+            OutcomeDescriptor::Cid(bounded(&cid_v1)),
+            OutcomeDescriptor::Label(bounded("Ja, natürlich".as_bytes())),
+            OutcomeDescriptor::Numeric(42),
+        ];
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                categories: Some(4),
+                outcome_descriptors: Some(descriptors.clone()),
+                ..Default::default()
+            },
         );
+        assert_eq!(OutcomeDescriptors::<Runtime>::get(0), Some(descriptors));
 
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
-
-        run_to_block(100);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(OutcomeDescriptors::<Runtime>::get(1), None);
+    });
+}
 
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(BOB),
-            0,
-            OutcomeReport::Categorical(1)
-        ));
+#[test]
+fn market_metadata_and_outcome_descriptors_share_the_metadata_length_limit() {
+    let max_len = MaxMetadataLength::get() as usize;
+    assert!(BoundedMetadata::try_from(vec![7; max_len]).is_ok());
+    assert!(BoundedMetadata::try_from(vec![7; max_len + 1]).is_err());
+
+    // Market metadata is a `BoundedMetadata`, so it's decoded just like the descriptors.
+    let too_long = vec![7u8; max_len + 1].encode();
+    assert!(BoundedMetadata::decode(&mut &too_long[..]).is_err());
+    for variant in [0u8, 1] {
+        let descriptor = [&[variant][..], &too_long].concat();
+        assert!(OutcomeDescriptor::decode(&mut &descriptor[..]).is_err());
+        let descriptor = [&[variant][..], &vec![7u8; max_len].encode()].concat();
+        assert!(OutcomeDescriptor::decode(&mut &descriptor[..]).is_ok());
+    }
+}
 
-        let reported_ids = MarketIdsPerReportBlock::<Runtime>::get(&100);
-        assert_eq!(reported_ids.len(), 1);
-        let id = reported_ids[0];
-        assert_eq!(id, 0);
+#[test]
+fn it_does_not_create_markets_with_malformed_outcome_descriptors() {
+    ExtBuilder::default().build().execute_with(|| {
+        let label = |bytes: &[u8]| OutcomeDescriptor::Label(bounded(bytes));
+        let create_market = |categories, descriptors| {
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..1),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                categories,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    outcome_descriptors: Some(descriptors),
+                    ..Default::default()
+                },
+            )
+        };
+        assert_noop!(
+            create_market(3, vec![label(b"A"), label(b"B")]),
+            Error::<Runtime>::OutcomeDescriptorsLengthMismatch
+        );
 
-        run_to_block(111);
+        let max_label_len = <Runtime as Config>::MaxOutcomeLabelLength::get() as usize;
+        let malformed = [
+            OutcomeDescriptor::Cid(bounded(&[0x12, 0x20, 7])),
+            OutcomeDescriptor::Cid(bounded(&[&[0x01, 0x55, 0x12, 0x20][..], &[7; 31]].concat())),
+            label(b""),
+            label(&[0xff, 0xfe]),
+            label(&vec![b'A'; max_label_len + 1]),
+        ];
+        for descriptor in malformed {
+            assert_noop!(
+                create_market(2, vec![label(b"A"), descriptor]),
+                Error::<Runtime>::InvalidOutcomeDescriptor
+            );
+        }
 
-        let market = MarketCommons::market(&0);
-        assert_eq!(market.unwrap().status, MarketStatus::Resolved);
+        // The descriptors count towards the metadata of the market.
+        MaxTotalMetadataBytes::set(52);
+        assert_ok!(create_market(2, vec![label(b"A"), label(b"B")]));
+        assert_noop!(
+            create_market(2, vec![label(b"A"), label(b"BC")]),
+            Error::<Runtime>::MetadataTooLong
+        );
+    });
+}
 
-        // check to make sure all but the winning share was deleted
-        let share_a = Asset::CategoricalOutcome(0, 0);
-        let share_a_total = Tokens::total_issuance(share_a);
-        assert_eq!(share_a_total, 0);
-        let share_a_bal = Tokens::free_balance(share_a, &CHARLIE);
-        assert_eq!(share_a_bal, 0);
+#[test]
+fn scalar_markets_cannot_be_created_with_outcome_descriptors() {
+    ExtBuilder::default().build().execute_with(|| {
+        let label = OutcomeDescriptor::Label(bounded(b"A"));
+        assert_noop!(
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                10..=30,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    outcome_descriptors: Some(vec![label]),
+                    ..Default::default()
+                },
+            ),
+            Error::<Runtime>::InvalidOutcomeDescriptor
+        );
+    });
+}
 
-        let share_b = Asset::CategoricalOutcome(0, 1);
-        let share_b_total = Tokens::total_issuance(share_b);
-        assert_eq!(share_b_total, CENT);
-        let share_b_bal = Tokens::free_balance(share_b, &CHARLIE);
-        assert_eq!(share_b_bal, CENT);
+#[test]
+fn it_does_not_create_market_with_too_few_categories() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Advised,
+                <Runtime as Config>::MinCategories::get() - 1,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::NotEnoughCategories
+        );
+    });
+}
 
-        let share_c = Asset::CategoricalOutcome(0, 2);
-        let share_c_total = Tokens::total_issuance(share_c);
-        assert_eq!(share_c_total, 0);
-        let share_c_bal = Tokens::free_balance(share_c, &CHARLIE);
-        assert_eq!(share_c_bal, 0);
+#[test]
+fn categorical_markets_need_at_least_two_categories() {
+    ExtBuilder::default().build().execute_with(|| {
+        let create = |categories| {
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                categories,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default(),
+            )
+        };
+        assert_noop!(create(0), Error::<Runtime>::NotEnoughCategories);
+        assert_noop!(create(1), Error::<Runtime>::NotEnoughCategories);
+        assert_ok!(create(2));
+        assert_eq!(MarketCommons::market(&0).unwrap().outcomes(), 2);
     });
 }
 
 #[test]
-fn it_resolves_a_disputed_market() {
+fn invalid_is_resolvable_but_has_no_outcome_share() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.outcomes(), 2);
+        assert_eq!(market.resolvable_outcomes(), 3);
 
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
-
-        run_to_block(100);
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        let assets = PredictionMarkets::outcome_assets(0, &market);
+        assert_eq!(assets.len(), usize::from(market.outcomes()));
+        for asset in assets {
+            assert_eq!(Tokens::free_balance(asset, &CHARLIE), 10 * BASE);
+        }
+        assert_eq!(
+            Tokens::free_balance(Asset::CategoricalOutcome(0, market.outcomes()), &CHARLIE),
+            0
+        );
 
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(BOB),
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Invalid));
+        assert_eq!(
+            MarketCommons::market(&0).unwrap().report.unwrap().outcome,
+            OutcomeReport::Invalid
+        );
+    });
+}
+
+#[test]
+fn report_fails_on_outcomes_the_market_cant_resolve_to() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(2)),
+            Error::<Runtime>::OutcomeOutOfRange
+        );
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Scalar(1)),
+            Error::<Runtime>::OutcomeMismatch
+        );
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+    });
+}
+
+#[test]
+fn create_market_succeeds_with_an_allowed_end_kind() {
+    ExtBuilder::default().build().execute_with(|| {
+        AllowedMarketEndKinds::set(MarketEndKinds::BlocksOnly);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..100));
+    });
+}
+
+#[test]
+fn create_market_fails_with_a_disallowed_end_kind() {
+    ExtBuilder::default().build().execute_with(|| {
+        AllowedMarketEndKinds::set(MarketEndKinds::TimestampsOnly);
+        assert_noop!(
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                10..=30,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::MarketEndKindNotAllowed
+        );
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000_000),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+    });
+}
+
+#[test]
+fn outcome_count_matches_the_number_of_outcome_assets() {
+    assert_eq!(MarketType::Categorical(0).outcome_count(), 0);
+    assert_eq!(MarketType::Categorical(5).outcome_count(), 5);
+    assert_eq!(MarketType::Scalar(0..=100).outcome_count(), 2);
+
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        for market_id in [0, 1] {
+            let market = MarketCommons::market(&market_id).unwrap();
+            let assets = PredictionMarkets::outcome_assets(market_id, &market);
+            assert_eq!(usize::from(market.market_type.outcome_count()), assets.len());
+            assert_eq!(market.outcomes(), market.market_type.outcome_count());
+        }
+    });
+}
+
+#[test]
+fn it_does_not_create_market_with_too_many_categories() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Advised,
+                <Runtime as Config>::MaxCategories::get() + 1,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ),
+            Error::<Runtime>::TooManyCategories
+        );
+    });
+}
+
+#[test]
+fn it_allows_sudo_to_destroy_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates an advised market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // destroy the market
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
+
+        assert_noop!(
+            MarketCommons::market(&0),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn removed_markets_return_the_existential_deposit_of_the_market_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        for _ in 0..2 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Advised,
+                0..1,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
+        }
+        let market_account = PredictionMarkets::market_account(0);
+        assert_eq!(Balances::free_balance(&market_account), ExistentialDeposit::get());
+
+        let alice_free = Balances::free_balance(&ALICE);
+        assert_ok!(PredictionMarkets::reject_market(Origin::signed(SUDO), 0));
+        assert_eq!(Balances::free_balance(&market_account), 0);
+        assert_eq!(Balances::free_balance(&ALICE), alice_free + ExistentialDeposit::get());
+
+        let alice_free = Balances::free_balance(&ALICE);
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 1));
+        assert_eq!(Balances::free_balance(&PredictionMarkets::market_account(1)), 0);
+        assert_eq!(Balances::free_balance(&ALICE), alice_free + ExistentialDeposit::get());
+    });
+}
+
+#[test]
+fn admin_destroy_market_unreserves_global_dispute_stakes() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(FRED),
+            0,
+            OutcomeReport::Categorical(1),
+            30 * BASE
+        ));
+        assert_eq!(Balances::reserved_balance(&FRED), 30 * BASE);
+
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
+        assert_eq!(Balances::reserved_balance(&FRED), 0);
+        assert_eq!(Balances::free_balance(&FRED), 1_000 * BASE);
+        assert_eq!(GlobalDisputeVotes::<Runtime>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn admin_destroy_market_clears_account_positions_and_dispute_bond_claims() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+        assert!(AccountPositions::<Runtime>::contains_key(BOB, 0));
+        DisputeBondClaims::<Runtime>::insert(0, CHARLIE, ());
+
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
+        assert!(!AccountPositions::<Runtime>::contains_key(BOB, 0));
+        assert_eq!(DisputeBondClaims::<Runtime>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn admin_destroy_market_returns_unclaimed_dispute_bonds() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(Balances::reserved_balance(&CHARLIE), DisputeBond::get());
+
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
+        assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+    });
+}
+
+#[test]
+fn it_does_not_destroy_markets_with_too_many_share_holders() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        // Two holders of both outcomes of a binary market make four share accounts.
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
+
+        MaxDestroyedShareAccounts::set(3);
+        assert_noop!(
+            PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0),
+            Error::<Runtime>::TooManyHoldersToDestroy
+        );
+
+        MaxDestroyedShareAccounts::set(4);
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 0));
+        assert!(MarketCommons::market(&0).is_err());
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &BOB), 0);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &CHARLIE), 0);
+    });
+}
+
+#[test]
+fn close_market_early_allows_to_report_the_market_immediately() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(5);
+        assert_noop!(
+            PredictionMarkets::close_market_early(Origin::signed(BOB), 0),
+            Error::<Runtime>::SenderNotCreator
+        );
+        assert_ok!(PredictionMarkets::close_market_early(Origin::signed(ALICE), 0));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..5));
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
+        assert_noop!(
+            PredictionMarkets::close_market_early(Origin::signed(ALICE), 0),
+            Error::<Runtime>::MarketIsNotActive
+        );
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Reported);
+    });
+}
+
+#[test]
+fn the_creator_can_attach_evidence_to_active_and_closed_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(5);
+        assert_ok!(PredictionMarkets::attach_market_evidence(
+            Origin::signed(ALICE),
+            0,
+            gen_metadata(3)
+        ));
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketEvidenceAttached(
+            0,
+            gen_metadata(3),
+        )));
+
+        // The market has ended, but isn't reported yet.
+        assert_ok!(PredictionMarkets::close_market_early(Origin::signed(ALICE), 0));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..5));
+        assert_ok!(PredictionMarkets::attach_market_evidence(
+            Origin::signed(ALICE),
+            0,
+            gen_metadata(4)
+        ));
+
+        let MultiHash::Sha3_384(first) = gen_metadata(3);
+        let MultiHash::Sha3_384(second) = gen_metadata(4);
+        assert_eq!(AdditionalMetadata::<Runtime>::get(0), vec![bounded(&first), bounded(&second)]);
+        // The original metadata is left untouched.
+        let MultiHash::Sha3_384(metadata) = gen_metadata(2);
+        assert_eq!(MarketCommons::market(&0).unwrap().metadata, bounded(&metadata));
+    });
+}
+
+#[test]
+fn attach_market_evidence_counts_towards_the_total_metadata_limit() {
+    ExtBuilder::default().build().execute_with(|| {
+        // The metadata of the market and a single piece of evidence are 50 bytes each.
+        MaxTotalMetadataBytes::set(100);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::attach_market_evidence(
+            Origin::signed(ALICE),
+            0,
+            gen_metadata(3)
+        ));
+        assert_noop!(
+            PredictionMarkets::attach_market_evidence(Origin::signed(ALICE), 0, gen_metadata(4)),
+            Error::<Runtime>::MetadataTooLong
+        );
+    });
+}
+
+#[test]
+fn attach_market_evidence_fails_for_non_creators_resolved_markets_and_above_the_limit() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxAdditionalMetadata::set(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_noop!(
+            PredictionMarkets::attach_market_evidence(Origin::signed(BOB), 0, gen_metadata(3)),
+            Error::<Runtime>::SenderNotCreator
+        );
+        assert_ok!(PredictionMarkets::attach_market_evidence(
+            Origin::signed(ALICE),
+            0,
+            gen_metadata(3)
+        ));
+        assert_noop!(
+            PredictionMarkets::attach_market_evidence(Origin::signed(ALICE), 0, gen_metadata(4)),
+            Error::<Runtime>::TooMuchAdditionalMetadata
+        );
+
+        MaxAdditionalMetadata::set(2);
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_noop!(
+            PredictionMarkets::attach_market_evidence(Origin::signed(ALICE), 0, gen_metadata(4)),
+            Error::<Runtime>::MarketIsNotActiveOrClosed
+        );
+    });
+}
+
+#[test]
+fn admin_move_market_to_closed_ends_an_open_market_at_the_current_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        run_to_block(10);
+        assert_ok!(PredictionMarkets::admin_move_market_to_closed(Origin::signed(SUDO), 0));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..10));
+    });
+}
+
+#[test]
+fn admin_set_market_end_corrects_the_end_of_a_block_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(10);
+        assert_noop!(
+            PredictionMarkets::admin_set_market_end(Origin::signed(BOB), 0, MarketEnd::Block(50)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PredictionMarkets::admin_set_market_end(Origin::signed(SUDO), 0, MarketEnd::Block(0)),
+            Error::<Runtime>::InvalidMarketEnd
+        );
+        assert_noop!(
+            PredictionMarkets::admin_set_market_end(
+                Origin::signed(SUDO),
+                0,
+                MarketEnd::Timestamp(50_000)
+            ),
+            Error::<Runtime>::InvalidMarketEnd
+        );
+
+        assert_ok!(PredictionMarkets::admin_set_market_end(
+            Origin::signed(SUDO),
+            0,
+            MarketEnd::Block(50)
+        ));
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketEndSet(
+            0,
+            MarketEnd::Block(50),
+        )));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..50));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        assert_ok!(PredictionMarkets::admin_set_market_end(
+            Origin::signed(SUDO),
+            0,
+            MarketEnd::Block(5)
+        ));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..5));
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
+    });
+}
+
+#[test]
+fn admin_set_market_end_reopens_an_ended_timestamp_market_until_it_is_reported() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        run_to_block(20);
+        Timestamp::set_timestamp(200_000);
+
+        assert_ok!(PredictionMarkets::admin_set_market_end(
+            Origin::signed(SUDO),
+            0,
+            MarketEnd::Timestamp(500_000)
+        ));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Timestamp(0..500_000));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        Timestamp::set_timestamp(500_000);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_noop!(
+            PredictionMarkets::admin_set_market_end(
+                Origin::signed(SUDO),
+                0,
+                MarketEnd::Timestamp(600_000)
+            ),
+            Error::<Runtime>::MarketIsNotActive
+        );
+    });
+}
+
+fn market_accounting_mismatch_was_deposited() -> bool {
+    System::events().iter().any(|record| {
+        matches!(record.event, Event::PredictionMarkets(crate::Event::MarketAccountingMismatch(..)))
+    })
+}
+
+#[test]
+fn closing_a_market_leaves_its_escrow_reconciled() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(10);
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 10 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 5 * BASE));
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, 3 * BASE));
+
+        assert_ok!(PredictionMarkets::admin_move_market_to_closed(Origin::signed(SUDO), 0));
+        assert_eq!(PredictionMarkets::outstanding_complete_sets(&0), Ok(12 * BASE));
+        assert!(!market_accounting_mismatch_was_deposited());
+    });
+}
+
+#[test]
+fn closing_a_market_flags_escrow_that_does_not_match_the_outstanding_sets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(10);
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 10 * BASE));
+        let market_account = PredictionMarkets::market_account(0);
+        assert_ok!(Balances::transfer(Origin::signed(CHARLIE), market_account, BASE));
+
+        assert_ok!(PredictionMarkets::close_market_early(Origin::signed(ALICE), 0));
+        System::assert_last_event(Event::PredictionMarkets(
+            crate::Event::MarketAccountingMismatch(0, 11 * BASE, 10 * BASE),
+        ));
+    });
+}
+
+#[test]
+fn admin_move_market_to_closed_does_not_extend_an_ended_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+
+        run_to_block(20);
+        Timestamp::set_timestamp(200_000);
+        assert_ok!(PredictionMarkets::admin_move_market_to_closed(Origin::signed(SUDO), 0));
+        assert_ok!(PredictionMarkets::admin_move_market_to_closed(Origin::signed(SUDO), 1));
+        assert_eq!(MarketCommons::market(&0).unwrap().period, MarketPeriod::Block(0..10));
+        assert_eq!(MarketCommons::market(&1).unwrap().period, MarketPeriod::Timestamp(0..100_000));
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
+    });
+}
+
+#[test]
+fn it_allows_advisory_origin_to_approve_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates an advised market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // make sure it's in status proposed
+        let market = MarketCommons::market(&0);
+        assert_eq!(market.unwrap().status, MarketStatus::Proposed);
+
+        // Make sure it fails from the random joe
+        assert_noop!(
+            PredictionMarkets::approve_market(Origin::signed(BOB), 0),
+            DispatchError::BadOrigin
+        );
+
+        // Now it should work from SUDO
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+
+        let after_market = MarketCommons::market(&0);
+        assert_eq!(after_market.unwrap().status, MarketStatus::Active);
+    });
+}
+
+#[test]
+fn it_deploys_the_requested_pool_of_a_permissionless_market_at_creation() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+        let pool_id = MarketCommons::market_pool(&0).unwrap();
+        let pool_account = Swaps::pool_account_id(pool_id);
+        assert_eq!(Balances::free_balance(&pool_account), MinLiquidity::get());
+        assert_eq!(
+            Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &pool_account),
+            MinLiquidity::get()
+        );
+        assert!(AutoDeployPools::<Runtime>::get(0).is_none());
+        assert_eq!(Balances::reserved_balance(&ALICE), ValidityBond::get() + OracleBond::get());
+    });
+}
+
+#[test]
+fn it_deploys_the_requested_pool_of_an_advised_market_on_approval() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+        assert!(MarketCommons::market_pool(&0).is_err());
+        let seed = 2 * MinLiquidity::get();
+        assert_eq!(AutoDeployPools::<Runtime>::get(0), Some((seed, vec![BASE; 3])));
+        assert_eq!(
+            Balances::reserved_balance(&ALICE),
+            AdvisoryBond::get() + OracleBond::get() + seed
+        );
+
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Active);
+        assert_ok!(MarketCommons::market_pool(&0));
+        assert!(AutoDeployPools::<Runtime>::get(0).is_none());
+        assert_eq!(Balances::reserved_balance(&ALICE), OracleBond::get());
+    });
+}
+
+#[test]
+fn it_returns_the_seed_of_the_requested_pool_if_the_market_is_rejected() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+        let reserved = Balances::reserved_balance(&ALICE);
+        assert_ok!(PredictionMarkets::reject_market(Origin::signed(SUDO), 0));
+        assert!(AutoDeployPools::<Runtime>::get(0).is_none());
+        assert_eq!(
+            Balances::reserved_balance(&ALICE),
+            reserved - AdvisoryBond::get() - 2 * MinLiquidity::get()
+        );
+    });
+}
+
+#[test]
+fn it_does_not_create_markets_with_an_invalid_pool_to_deploy() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..100),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    auto_deploy_pool: Some(vec![BASE; 2]),
+                    ..Default::default()
+                }
+            ),
+            Error::<Runtime>::WeightsLengthMismatch
+        );
+        assert_noop!(
+            PredictionMarkets::create_scalar_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Timestamp(100_000_000..200_000_000),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                0..=100,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::RikiddoSigmoidFeeMarketEma,
+                MarketCreationOptions {
+                    auto_deploy_pool: Some(vec![BASE; 3]),
+                    ..Default::default()
+                }
+            ),
+            Error::<Runtime>::InvalidScoringRule
+        );
+    });
+}
+
+#[test]
+fn market_pools_list_the_pools_of_all_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert!(PredictionMarkets::all_market_pools().is_empty());
+        assert!(PredictionMarkets::market_pools_paged(&0, 10).is_empty());
+
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                auto_deploy_pool: Some(vec![BASE; 3]),
+                ..Default::default()
+            },
+        );
+
+        let mut stored_pools: Vec<_> = zrml_market_commons::MarketPool::<Runtime>::iter().collect();
+        stored_pools.sort_unstable();
+        assert_eq!(PredictionMarkets::all_market_pools(), stored_pools);
+        assert_eq!(PredictionMarkets::all_market_pools(), vec![(0, 0), (2, 1), (3, 2)]);
+
+        assert_eq!(PredictionMarkets::market_pools_paged(&0, 2), vec![(0, 0)]);
+        assert_eq!(PredictionMarkets::market_pools_paged(&2, 2), vec![(2, 1), (3, 2)]);
+        assert!(PredictionMarkets::market_pools_paged(&4, 2).is_empty());
+        assert_eq!(PredictionMarkets::market_pools_paged(&0, 10), stored_pools);
+    });
+}
+
+#[test]
+fn approve_and_reject_market_record_the_deciding_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Events aren't recorded in the genesis block.
+        run_to_block(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            1..2,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            1..2,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketApproved(
+            0,
+            MarketStatus::Active,
+            Some(SUDO),
+        )));
+
+        assert_ok!(PredictionMarkets::reject_market(Origin::signed(SUDO), 1));
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketRejected(
+            1,
+            Some(SUDO),
+        )));
+    });
+}
+
+#[test]
+fn proposed_markets_are_removed_at_their_review_deadline() {
+    ExtBuilder::default().build().execute_with(|| {
+        run_to_block(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), AdvisoryBond::get() + OracleBond::get());
+        let deadline = 1 + <Runtime as Config>::AdvisoryReviewPeriod::get();
+        assert_eq!(ReviewDeadlines::<Runtime>::get(0), Some(deadline));
+
+        run_to_block(deadline - 1);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Proposed);
+
+        run_to_block(deadline);
+        assert_noop!(
+            MarketCommons::market(&0),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(ReviewDeadlines::<Runtime>::get(0), None);
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketExpired(0)));
+    });
+}
+
+#[test]
+fn expired_proposals_beyond_the_limit_are_removed_in_the_next_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxExpiredProposalsPerBlock::set(1);
+        run_to_block(1);
+        for _ in 0..2 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Advised,
+                0..100,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
+        }
+        let deadline = 1 + <Runtime as Config>::AdvisoryReviewPeriod::get();
+
+        run_to_block(deadline);
+        assert!(MarketCommons::market(&0).is_err());
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Proposed);
+        assert_eq!(ReviewDeadlines::<Runtime>::get(1), Some(deadline + 1));
+
+        run_to_block(deadline + 1);
+        assert!(MarketCommons::market(&1).is_err());
+        assert_eq!(ReviewDeadlines::<Runtime>::get(1), None);
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketExpired(1)));
+    });
+}
+
+#[test]
+fn claim_expired_proposal_fails_before_the_review_deadline() {
+    ExtBuilder::default().build().execute_with(|| {
+        run_to_block(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let deadline = 1 + <Runtime as Config>::AdvisoryReviewPeriod::get();
+        run_to_block(deadline - 1);
+        assert_noop!(
+            PredictionMarkets::claim_expired_proposal(Origin::signed(ALICE), 0),
+            Error::<Runtime>::ProposalNotExpired
+        );
+        assert_noop!(
+            PredictionMarkets::claim_expired_proposal(Origin::signed(BOB), 0),
+            Error::<Runtime>::SenderNotCreator
+        );
+    });
+}
+
+#[test]
+fn claim_expired_proposal_removes_a_proposal_that_was_not_removed_automatically() {
+    ExtBuilder::default().build().execute_with(|| {
+        run_to_block(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        // Proposals without a review deadline, e.g. from before review deadlines existed, expire
+        // `AdvisoryReviewPeriod` blocks after their creation, but aren't removed automatically.
+        let deadline = 1 + <Runtime as Config>::AdvisoryReviewPeriod::get();
+        ReviewDeadlines::<Runtime>::remove(0);
+        MarketIdsPerReviewDeadline::<Runtime>::remove(deadline);
+
+        run_to_block(deadline - 1);
+        assert_noop!(
+            PredictionMarkets::claim_expired_proposal(Origin::signed(ALICE), 0),
+            Error::<Runtime>::ProposalNotExpired
+        );
+        run_to_block(deadline);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Proposed);
+
+        assert_ok!(PredictionMarkets::claim_expired_proposal(Origin::signed(ALICE), 0));
+        assert_noop!(
+            MarketCommons::market(&0),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::MarketExpired(0)));
+    });
+}
+
+#[test]
+fn approved_markets_are_not_removed_at_their_review_deadline() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+        assert_eq!(ReviewDeadlines::<Runtime>::get(0), None);
+
+        run_to_block(<Runtime as Config>::AdvisoryReviewPeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Active);
+    });
+}
+
+#[test]
+fn cancelled_markets_are_removed_from_the_review_deadlines() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::cancel_pending_market(Origin::signed(ALICE), 0));
+        let deadline = <Runtime as Config>::AdvisoryReviewPeriod::get();
+        assert_eq!(ReviewDeadlines::<Runtime>::get(0), None);
+        assert!(MarketIdsPerReviewDeadline::<Runtime>::get(deadline).is_empty());
+    });
+}
+
+#[test]
+fn cancel_pending_market_returns_the_full_advisory_bond_without_penalty() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let alice_free = Balances::free_balance(&ALICE);
+        let alice_reserved = Balances::reserved_balance(&ALICE);
+        let treasury = Treasury::balance();
+
+        assert_ok!(PredictionMarkets::cancel_pending_market(Origin::signed(ALICE), 0));
+
+        assert_eq!(
+            Balances::free_balance(&ALICE),
+            alice_free + AdvisoryBond::get() + ExistentialDeposit::get()
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), alice_reserved - AdvisoryBond::get());
+        assert_eq!(Treasury::balance(), treasury);
+    });
+}
+
+#[test]
+fn cancel_pending_market_slashes_the_cancellation_penalty() {
+    ExtBuilder::default().build().execute_with(|| {
+        CancellationPenalty::set(Perbill::from_percent(40));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let alice_free = Balances::free_balance(&ALICE);
+        let alice_reserved = Balances::reserved_balance(&ALICE);
+        let treasury = Treasury::balance();
+
+        assert_ok!(PredictionMarkets::cancel_pending_market(Origin::signed(ALICE), 0));
+
+        let penalty = Perbill::from_percent(40).mul_floor(AdvisoryBond::get());
+        assert_eq!(
+            Balances::free_balance(&ALICE),
+            alice_free + AdvisoryBond::get() + ExistentialDeposit::get() - penalty
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), alice_reserved - AdvisoryBond::get());
+        assert_eq!(Treasury::balance(), treasury + penalty);
+    });
+}
+
+#[test]
+fn admin_extend_review_postpones_the_review_deadline() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let deadline = <Runtime as Config>::AdvisoryReviewPeriod::get();
+        let new_deadline = deadline + 10;
+        assert_noop!(
+            PredictionMarkets::admin_extend_review(Origin::signed(ALICE), 0, new_deadline),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PredictionMarkets::admin_extend_review(Origin::signed(SUDO), 0, deadline),
+            Error::<Runtime>::ReviewDeadlineNotExtended
+        );
+        assert_ok!(PredictionMarkets::admin_extend_review(Origin::signed(SUDO), 0, new_deadline));
+        assert_eq!(ReviewDeadlines::<Runtime>::get(0), Some(new_deadline));
+        assert!(MarketIdsPerReviewDeadline::<Runtime>::get(deadline).is_empty());
+
+        run_to_block(deadline);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Proposed);
+
+        run_to_block(new_deadline);
+        assert_noop!(
+            MarketCommons::market(&0),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn admin_extend_review_requires_a_proposed_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_noop!(
+            PredictionMarkets::admin_extend_review(Origin::signed(SUDO), 0, 100),
+            Error::<Runtime>::MarketIsNotProposed
+        );
+    });
+}
+
+#[test]
+fn it_allows_the_advisory_origin_to_reject_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates an advised market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // make sure it's in status proposed
+        let market = MarketCommons::market(&0);
+        assert_eq!(market.unwrap().status, MarketStatus::Proposed);
+
+        // Now it should work from SUDO
+        assert_ok!(PredictionMarkets::reject_market(Origin::signed(SUDO), 0));
+        assert_eq!(Treasury::balance(), AdvisoryBond::get());
+
+        assert_noop!(
+            MarketCommons::market(&0),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_buy_or_sell_zero_complete_sets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..2,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 0),
+            Error::<Runtime>::ZeroAmount
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+        assert_noop!(
+            PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, 0),
+            Error::<Runtime>::ZeroAmount
+        );
+        assert_noop!(
+            PredictionMarkets::sell_complete_set_for(Origin::signed(BOB), CHARLIE, 0, 0),
+            Error::<Runtime>::ZeroAmount
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_redeem_without_winning_shares() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_noop!(
+            PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::NoWinningBalance
+        );
+    });
+}
+
+#[test]
+fn it_allows_to_buy_a_complete_set() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // Allows someone to generate a complete set
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        let market = MarketCommons::market(&0).unwrap();
+
+        // Check the outcome balances
+        let assets = PredictionMarkets::outcome_assets(0, &market);
+        for asset in assets.iter() {
+            let bal = Tokens::free_balance(*asset, &BOB);
+            assert_eq!(bal, CENT);
+        }
+
+        // also check native balance
+        let bal = Balances::free_balance(&BOB);
+        assert_eq!(bal, 1_000 * BASE - CENT);
+
+        let market_account = PredictionMarkets::market_account(0);
+        let market_bal = Balances::free_balance(market_account);
+        assert_eq!(market_bal, ExistentialDeposit::get() + CENT);
+    });
+}
+
+#[test]
+fn it_allows_to_deploy_a_pool() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE,));
+
+        assert_ok!(Balances::transfer(
+            Origin::signed(BOB),
+            <Runtime as crate::Config>::PalletId::get().into_account(),
+            100 * BASE
+        ));
+        assert_ok!(Tokens::deposit(Asset::Ztg, &BOB, 100 * BASE));
+
+        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+            Origin::signed(BOB),
+            0,
+            vec![BASE, BASE, BASE]
+        ));
+    });
+}
+
+fn deploy_seeded_pool_for_categorical_market() {
+    simple_create_categorical_market::<Runtime>(
+        MarketCreation::Permissionless,
+        0..10,
+        ScoringRule::CPMM,
+        Default::default(),
+    );
+    assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE));
+    assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+        Origin::signed(BOB),
+        0,
+        vec![BASE, BASE, BASE]
+    ));
+}
+
+#[test]
+fn buy_complete_set_via_pool_assembles_sets_from_the_pool() {
+    ExtBuilder::default().build().execute_with(|| {
+        deploy_seeded_pool_for_categorical_market();
+        let charlie_balance = Balances::free_balance(&CHARLIE);
+
+        // Each outcome costs one ZTG in the seeded pool, so 10 ZTG buy a bit less than five sets.
+        assert_ok!(PredictionMarkets::buy_complete_set_via_pool(
+            Origin::signed(CHARLIE),
+            0,
+            10 * BASE,
+            4 * BASE
+        ));
+
+        // The shares that don't complete a set are sold back to the pool.
+        let sets = Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &CHARLIE);
+        assert!(sets >= 4 * BASE && sets < 5 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &CHARLIE), sets);
+        let collateral_in = charlie_balance - Balances::free_balance(&CHARLIE);
+        assert!(collateral_in > 9 * BASE && collateral_in < 10 * BASE);
+        System::assert_last_event(Event::PredictionMarkets(
+            crate::Event::BoughtCompleteSetViaPool(0, sets, CHARLIE),
+        ));
+    });
+}
+
+#[test]
+fn buy_complete_set_via_pool_fails_if_min_sets_out_is_not_met() {
+    ExtBuilder::default().build().execute_with(|| {
+        deploy_seeded_pool_for_categorical_market();
+        let charlie_balance = Balances::free_balance(&CHARLIE);
+
+        assert_noop!(
+            PredictionMarkets::buy_complete_set_via_pool(
+                Origin::signed(CHARLIE),
+                0,
+                10 * BASE,
+                5 * BASE
+            ),
+            Error::<Runtime>::SlippageExceeded
+        );
+
+        assert_eq!(Balances::free_balance(&CHARLIE), charlie_balance);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &CHARLIE), 0);
+    });
+}
+
+#[test]
+fn buy_complete_set_via_pool_requires_an_active_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        deploy_seeded_pool_for_categorical_market();
+        // The market is still within its period, but trading is paused.
+        assert_ok!(MarketCommons::mutate_market(&0, |market| {
+            market.status = MarketStatus::Suspended;
+            Ok(())
+        }));
+
+        assert_noop!(
+            PredictionMarkets::buy_complete_set_via_pool(
+                Origin::signed(CHARLIE),
+                0,
+                10 * BASE,
+                4 * BASE
+            ),
+            Error::<Runtime>::MarketIsNotActive
+        );
+    });
+}
+
+#[test]
+fn deploy_swap_pools_batch_deploys_each_entry_independently() {
+    ExtBuilder::default().build().execute_with(|| {
+        run_to_block(1);
+        for market_id in 0..3 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Permissionless,
+                0..10,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
+            assert_ok!(PredictionMarkets::buy_complete_set(
+                Origin::signed(BOB),
+                market_id,
+                100 * BASE
+            ));
+        }
+        let weights = vec![BASE, BASE, BASE];
+        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+            Origin::signed(BOB),
+            1,
+            weights.clone()
+        ));
+        let existing_pool_id = MarketCommons::market_pool(&1).unwrap();
+
+        assert_ok!(PredictionMarkets::deploy_swap_pools_batch(
+            Origin::signed(BOB),
+            vec![(0, weights.clone()), (1, weights.clone()), (2, weights)]
+        ));
+        let pool_0 = MarketCommons::market_pool(&0).unwrap();
+        let pool_2 = MarketCommons::market_pool(&2).unwrap();
+        assert!(Swaps::pool_exists(pool_0));
+        assert!(Swaps::pool_exists(pool_2));
+        assert_eq!(MarketCommons::market_pool(&1), Ok(existing_pool_id));
+
+        let has_event = |event| System::events().iter().any(|record| record.event == event);
+        assert!(has_event(Event::PredictionMarkets(crate::Event::PoolDeployed(0, pool_0))));
+        assert!(has_event(Event::PredictionMarkets(crate::Event::PoolDeploymentFailed(
+            1,
+            Error::<Runtime>::SwapPoolExists.into()
+        ))));
+        assert!(has_event(Event::PredictionMarkets(crate::Event::PoolDeployed(2, pool_2))));
+    });
+}
+
+#[test]
+fn deploy_swap_pools_batch_fails_if_the_batch_is_too_large() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxPoolDeploymentsPerBatch::set(1);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE));
+        let weights = vec![BASE, BASE, BASE];
+        assert_noop!(
+            PredictionMarkets::deploy_swap_pools_batch(
+                Origin::signed(BOB),
+                vec![(0, weights.clone()), (0, weights)]
+            ),
+            Error::<Runtime>::TooManyPoolDeployments
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_deploy_a_pool_on_an_inactive_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        // A proposed market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        // A market that is still `Active`, but whose end has passed.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Active);
+
+        for market_id in [0, 1] {
+            assert_noop!(
+                PredictionMarkets::deploy_swap_pool_for_market(
+                    Origin::signed(ALICE),
+                    market_id,
+                    vec![BASE, BASE, BASE]
+                ),
+                Error::<Runtime>::MarketIsNotActive
+            );
+        }
+    });
+}
+
+#[test]
+fn deploy_swap_pool_rejects_oversized_weights_early() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        let weights = vec![BASE; 10_000];
+        assert_noop!(
+            PredictionMarkets::deploy_swap_pool_for_market(
+                Origin::signed(ALICE),
+                0,
+                weights.clone()
+            ),
+            Error::<Runtime>::WeightsLengthMismatch
+        );
+        assert_noop!(
+            PredictionMarkets::deploy_swap_pool_and_additional_liquidity(
+                Origin::signed(ALICE),
+                0,
+                BASE,
+                vec![BASE, BASE],
+                weights,
+                vec![0, 0],
+            ),
+            Error::<Runtime>::WeightsLengthMismatch
+        );
+    });
+}
+
+#[test]
+fn deploy_swap_pool_rejects_weights_that_do_not_sum_to_pool_weights_sum() {
+    ExtBuilder::default().build().execute_with(|| {
+        PoolWeightsSum::set(Some(10 * BASE));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE));
+        assert_noop!(
+            PredictionMarkets::deploy_swap_pool_for_market(
+                Origin::signed(BOB),
+                0,
+                vec![BASE, BASE, BASE]
+            ),
+            Error::<Runtime>::PoolWeightsSumInvalid
+        );
+        assert_noop!(
+            PredictionMarkets::deploy_swap_pool_for_market(
+                Origin::signed(BOB),
+                0,
+                vec![2 * BASE, 3 * BASE, 6 * BASE]
+            ),
+            Error::<Runtime>::PoolWeightsSumInvalid
+        );
+    });
+}
+
+#[test]
+fn deploy_swap_pool_accepts_weights_that_sum_to_pool_weights_sum() {
+    ExtBuilder::default().build().execute_with(|| {
+        PoolWeightsSum::set(Some(10 * BASE));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 100 * BASE));
+        assert_ok!(Balances::transfer(
+            Origin::signed(BOB),
+            <Runtime as crate::Config>::PalletId::get().into_account(),
+            100 * BASE
+        ));
+        assert_ok!(Tokens::deposit(Asset::Ztg, &BOB, 100 * BASE));
+        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+            Origin::signed(BOB),
+            0,
+            vec![2 * BASE, 3 * BASE, 5 * BASE]
+        ));
+        assert_ok!(MarketCommons::market_pool(&0));
+    });
+}
+
+#[test]
+fn deployed_pools_contain_the_outcome_assets_and_the_base_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            5,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+
+        for (market_id, expected_len) in [(0, 6), (1, 3)] {
+            assert_ok!(PredictionMarkets::buy_complete_set(
+                Origin::signed(BOB),
+                market_id,
+                100 * BASE
+            ));
+            assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+                Origin::signed(BOB),
+                market_id,
+                vec![BASE; expected_len]
+            ));
+            let pool_id = MarketCommons::market_pool(&market_id).unwrap();
+            let pool = Swaps::pool(pool_id).unwrap();
+            assert_eq!(pool.assets.len(), expected_len);
+            assert!(pool.assets.contains(&Asset::Ztg));
+        }
+    });
+}
+
+#[test]
+fn it_allows_to_sell_a_complete_set() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, CENT));
+
+        let market = MarketCommons::market(&0).unwrap();
+
+        // Check the outcome balances
+        let assets = PredictionMarkets::outcome_assets(0, &market);
+        for asset in assets.iter() {
+            let bal = Tokens::free_balance(*asset, &BOB);
+            assert_eq!(bal, 0);
+        }
+
+        // also check native balance
+        let bal = Balances::free_balance(&BOB);
+        assert_eq!(bal, 1_000 * BASE);
+    });
+}
+
+#[test]
+fn account_positions_are_opened_on_buy_and_closed_after_selling_everything() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert!(!AccountPositions::<Runtime>::contains_key(BOB, 0));
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * CENT));
+        assert!(AccountPositions::<Runtime>::contains_key(BOB, 0));
+
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, CENT));
+        assert!(AccountPositions::<Runtime>::contains_key(BOB, 0));
+
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, CENT));
+        assert!(!AccountPositions::<Runtime>::contains_key(BOB, 0));
+    });
+}
+
+#[test]
+fn account_positions_are_closed_after_redeeming_shares() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        // Only the losing shares were destroyed, so the position is still open.
+        assert!(AccountPositions::<Runtime>::contains_key(CHARLIE, 0));
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert!(!AccountPositions::<Runtime>::contains_key(CHARLIE, 0));
+    });
+}
+
+#[test]
+fn total_balance_of_assets_sums_free_and_reserved_shares() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let market = MarketCommons::market(&0).unwrap();
+        let assets = PredictionMarkets::outcome_assets(0, &market);
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        assert_eq!(Tokens::total_balance_of_assets(&assets, &CHARLIE), 20 * BASE);
+
+        // A partial position.
+        assert_ok!(Tokens::withdraw(Asset::CategoricalOutcome(0, 0), &CHARLIE, 10 * BASE));
+        assert_ok!(Tokens::reserve(Asset::CategoricalOutcome(0, 1), &CHARLIE, 4 * BASE));
+        assert_eq!(Tokens::total_balance_of_assets(&assets, &CHARLIE), 10 * BASE);
+        assert_eq!(Tokens::total_balance_of_assets(&assets, &DAVE), 0);
+    });
+}
+
+#[test]
+fn outstanding_complete_sets_is_the_lowest_outcome_supply() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(DAVE), 0, 20 * BASE));
+        assert_eq!(PredictionMarkets::outstanding_complete_sets(&0), Ok(120 * BASE));
+
+        // Transfers don't change the number of redeemable sets.
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            EVE,
+            Asset::CategoricalOutcome(0, 0),
+            60 * BASE
+        ));
+        assert_eq!(PredictionMarkets::outstanding_complete_sets(&0), Ok(120 * BASE));
+
+        // Removing shares of a single outcome does.
+        assert_ok!(Tokens::withdraw(Asset::CategoricalOutcome(0, 1), &DAVE, 15 * BASE));
+        assert_eq!(PredictionMarkets::outstanding_complete_sets(&0), Ok(105 * BASE));
+    });
+}
+
+#[test]
+fn market_bonds_follow_the_market_status() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let bonds = PredictionMarkets::market_bonds(&0).unwrap();
+        assert_eq!(
+            bonds,
+            BondBreakdown { advisory: AdvisoryBond::get(), validity: 0, oracle: OracleBond::get() }
+        );
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 0));
+        let bonds = PredictionMarkets::market_bonds(&0).unwrap();
+        assert_eq!(bonds, BondBreakdown { advisory: 0, validity: 0, oracle: OracleBond::get() });
+
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let bonds = PredictionMarkets::market_bonds(&1).unwrap();
+        assert_eq!(
+            bonds,
+            BondBreakdown { advisory: 0, validity: ValidityBond::get(), oracle: OracleBond::get() }
+        );
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            1,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::market_bonds(&1), Ok(BondBreakdown::default()));
+    });
+}
+
+#[test]
+fn outcome_payout_liability_is_the_supply_of_the_winning_shares() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(Tokens::withdraw(Asset::CategoricalOutcome(0, 1), &CHARLIE, 40 * BASE));
+        assert_eq!(
+            PredictionMarkets::outcome_payout_liability(&0, &OutcomeReport::Categorical(0)),
+            Ok(100 * BASE)
+        );
+        assert_eq!(
+            PredictionMarkets::outcome_payout_liability(&0, &OutcomeReport::Categorical(1)),
+            Ok(60 * BASE)
+        );
+        assert_noop!(
+            PredictionMarkets::outcome_payout_liability(&0, &OutcomeReport::Categorical(2)),
+            Error::<Runtime>::OutcomeOutOfRange
+        );
+    });
+}
+
+#[test]
+fn outcome_payout_liability_of_scalar_markets_depends_on_the_value() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(Tokens::deposit(
+            Asset::ScalarOutcome(0, ScalarPosition::Long),
+            &CHARLIE,
+            50 * BASE
+        ));
+        let liability =
+            |value| PredictionMarkets::outcome_payout_liability(&0, &OutcomeReport::Scalar(value));
+        assert_eq!(liability(10), Ok(100 * BASE));
+        assert_eq!(liability(20), Ok(125 * BASE));
+        assert_eq!(liability(30), Ok(150 * BASE));
+        assert_noop!(liability(31), Error::<Runtime>::OutcomeOutOfRange);
+    });
+}
+
+#[test]
+fn hypothetical_winnings_depend_on_the_position_of_the_holder() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            EVE,
+            Asset::CategoricalOutcome(0, 1),
+            40 * BASE
+        ));
+        let winnings = |who, outcome| PredictionMarkets::hypothetical_winnings(&0, &who, &outcome);
+
+        assert_eq!(winnings(CHARLIE, OutcomeReport::Categorical(0)), Ok(100 * BASE));
+        assert_eq!(winnings(CHARLIE, OutcomeReport::Categorical(1)), Ok(60 * BASE));
+        assert_eq!(winnings(CHARLIE, OutcomeReport::Invalid), Ok(80 * BASE));
+        assert_eq!(winnings(EVE, OutcomeReport::Categorical(0)), Ok(0));
+        assert_eq!(winnings(EVE, OutcomeReport::Categorical(1)), Ok(40 * BASE));
+        assert_eq!(winnings(EVE, OutcomeReport::Invalid), Ok(20 * BASE));
+        assert_noop!(
+            winnings(CHARLIE, OutcomeReport::Categorical(2)),
+            Error::<Runtime>::OutcomeOutOfRange
+        );
+    });
+}
+
+#[test]
+fn hypothetical_winnings_of_scalar_markets_depend_on_the_value() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            EVE,
+            Asset::ScalarOutcome(0, ScalarPosition::Short),
+            50 * BASE
+        ));
+        let winnings = |value| {
+            PredictionMarkets::hypothetical_winnings(&0, &CHARLIE, &OutcomeReport::Scalar(value))
+        };
+        assert_eq!(winnings(10), Ok(50 * BASE));
+        assert_eq!(winnings(20), Ok(75 * BASE));
+        assert_eq!(winnings(30), Ok(100 * BASE));
+        assert_noop!(winnings(31), Error::<Runtime>::OutcomeOutOfRange);
+    });
+}
+
+#[test]
+fn reserved_shares_can_not_be_sold_until_they_are_unreserved() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        let asset = Asset::CategoricalOutcome(0, 1);
+
+        assert_ok!(PredictionMarkets::reserve_shares(asset, &CHARLIE, 4 * BASE));
+        assert_eq!(Tokens::free_balance(asset, &CHARLIE), 6 * BASE);
+        assert_eq!(Tokens::reserved_balance(asset, &CHARLIE), 4 * BASE);
+        assert_noop!(
+            PredictionMarkets::sell_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE),
+            Error::<Runtime>::InsufficientShareBalance
+        );
+
+        assert_eq!(PredictionMarkets::unreserve_shares(asset, &CHARLIE, 5 * BASE), BASE);
+        assert_eq!(Tokens::reserved_balance(asset, &CHARLIE), 0);
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+    });
+}
+
+#[test]
+fn my_market_view_shows_the_position_of_an_account_in_an_active_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            DAVE,
+            Asset::CategoricalOutcome(0, 0),
+            3 * BASE
+        ));
+        assert_ok!(PredictionMarkets::reserve_shares(
+            Asset::CategoricalOutcome(0, 1),
+            &CHARLIE,
+            4 * BASE
+        ));
+
+        let view = PredictionMarkets::my_market_view(&0, &CHARLIE).unwrap();
+        assert_eq!(view.market, MarketCommons::market(&0).unwrap());
+        assert_eq!(view.free, vec![7 * BASE, 6 * BASE]);
+        assert_eq!(view.reserved, vec![0, 4 * BASE]);
+        assert_eq!(view.winnings, None);
+
+        let view = PredictionMarkets::my_market_view(&0, &DAVE).unwrap();
+        assert_eq!(view.free, vec![3 * BASE, 0]);
+        assert_eq!(view.reserved, vec![0, 0]);
+    });
+}
+
+#[test]
+fn my_market_view_shows_the_winnings_of_a_resolved_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            DAVE,
+            Asset::CategoricalOutcome(0, 0),
+            10 * BASE
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+
+        let view = PredictionMarkets::my_market_view(&0, &CHARLIE).unwrap();
+        assert_eq!(view.market.status, MarketStatus::Resolved);
+        assert_eq!(view.free, vec![0, 10 * BASE]);
+        assert_eq!(view.winnings, Some(10 * BASE));
+        assert_eq!(PredictionMarkets::my_market_view(&0, &DAVE).unwrap().winnings, Some(0));
+
+        let balance_before = Balances::free_balance(&CHARLIE);
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), balance_before + 10 * BASE);
+    });
+}
+
+#[test]
+fn reserve_shares_only_accepts_outcome_assets_of_existing_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PredictionMarkets::reserve_shares(Asset::Ztg, &CHARLIE, BASE),
+            Error::<Runtime>::NotAnOutcomeAsset
+        );
+        assert_noop!(
+            PredictionMarkets::reserve_shares(Asset::CategoricalOutcome(0, 0), &CHARLIE, BASE),
+            zrml_market_commons::Error::<Runtime>::MarketDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn buy_complete_set_charges_the_mint_fee_on_top_of_the_collateral() {
+    ExtBuilder::default().build().execute_with(|| {
+        CompleteSetMintFee::set(Perbill::from_percent(1));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 995 * BASE),
+            Error::<Runtime>::NotEnoughBalance
+        );
+
+        let market_account = PredictionMarkets::market_account(0);
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - 100 * BASE - BASE);
+        assert_eq!(Treasury::balance(), BASE);
+        assert_eq!(Balances::free_balance(&market_account), 100 * BASE + ExistentialDeposit::get());
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &CHARLIE), 100 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &CHARLIE), 100 * BASE);
+    });
+}
+
+#[test]
+fn round_tripping_a_complete_set_costs_exactly_the_mint_fee() {
+    ExtBuilder::default().build().execute_with(|| {
+        CompleteSetMintFee::set(Perbill::from_percent(1));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - BASE);
+        assert_eq!(
+            Balances::free_balance(&PredictionMarkets::market_account(0)),
+            ExistentialDeposit::get()
+        );
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &CHARLIE), 0);
+    });
+}
+
+#[test]
+fn sell_complete_set_does_not_reap_the_market_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * CENT));
+        let market_account = PredictionMarkets::market_account(0);
+
+        // The existential deposit that the market account was funded with at creation keeps it
+        // alive, no matter how many complete sets are sold.
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, 2 * CENT - 1));
+        assert_ok!(PredictionMarkets::sell_complete_set(Origin::signed(BOB), 0, 1));
+        assert_eq!(Balances::free_balance(&market_account), ExistentialDeposit::get());
+        assert_eq!(Balances::free_balance(&BOB), 1_000 * BASE);
+    });
+}
+
+#[test]
+fn market_accounts_are_funded_with_the_existential_deposit_at_creation() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let market_account = PredictionMarkets::market_account(0);
+        assert_eq!(Balances::free_balance(&market_account), ExistentialDeposit::get());
+
+        // The first complete set may be smaller than the existential deposit.
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 1));
+        assert_eq!(Balances::free_balance(&market_account), ExistentialDeposit::get() + 1);
+    });
+}
+
+#[test]
+fn sell_complete_set_for_sends_the_collateral_to_the_beneficiary() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * CENT));
+        let charlie_balance = Balances::free_balance(&CHARLIE);
+
+        assert_ok!(PredictionMarkets::sell_complete_set_for(Origin::signed(BOB), CHARLIE, 0, CENT));
+
+        let market = MarketCommons::market(&0).unwrap();
+        for asset in PredictionMarkets::outcome_assets(0, &market).iter() {
+            assert_eq!(Tokens::free_balance(*asset, &BOB), CENT);
+            assert_eq!(Tokens::free_balance(*asset, &CHARLIE), 0);
+        }
+        assert_eq!(Balances::free_balance(&BOB), 1_000 * BASE - 2 * CENT);
+        assert_eq!(Balances::free_balance(&CHARLIE), charlie_balance + CENT);
+
+        // The beneficiary's shares are never touched.
+        assert_noop!(
+            PredictionMarkets::sell_complete_set_for(Origin::signed(CHARLIE), BOB, 0, CENT),
+            Error::<Runtime>::InsufficientShareBalance
+        );
+    });
+}
+
+#[test]
+fn it_allows_to_buy_and_sell_a_conditional_set() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * CENT));
+
+        assert_ok!(PredictionMarkets::buy_conditional_set(Origin::signed(BOB), 0, 1, 1, CENT));
+        let conditional_shares =
+            [Asset::CombinatorialShare(0, 1, 1, 0), Asset::CombinatorialShare(0, 1, 1, 1)];
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &BOB), CENT);
+        for asset in conditional_shares.iter() {
+            assert_eq!(Tokens::free_balance(*asset, &BOB), CENT);
+        }
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(1, 0), &BOB), 0);
+        // The parent shares are held in escrow, so the parent market is still fully backed.
+        let escrow_account = PredictionMarkets::market_account(1);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &escrow_account), CENT);
+        assert_eq!(PredictionMarkets::outstanding_complete_sets(&0), Ok(2 * CENT));
+
+        assert_ok!(PredictionMarkets::sell_conditional_set(Origin::signed(BOB), 0, 1, 1, CENT));
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &BOB), 2 * CENT);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &escrow_account), 0);
+        for asset in conditional_shares.iter() {
+            assert_eq!(Tokens::free_balance(*asset, &BOB), 0);
+        }
+    });
+}
+
+#[test]
+fn it_allows_to_redeem_conditional_shares_of_a_resolved_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 2 * CENT));
+        assert_ok!(PredictionMarkets::buy_conditional_set(
+            Origin::signed(CHARLIE),
+            0,
+            1,
+            1,
+            2 * CENT
+        ));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            DAVE,
+            Asset::CombinatorialShare(0, 1, 1, 1),
+            2 * CENT
+        ));
+        assert_noop!(
+            PredictionMarkets::redeem_conditional_shares(Origin::signed(CHARLIE), 0, 1, 1),
+            Error::<Runtime>::MarketIsNotResolved
+        );
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            1,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+
+        assert_ok!(PredictionMarkets::redeem_conditional_shares(Origin::signed(CHARLIE), 0, 1, 1));
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &CHARLIE), 2 * CENT);
+        assert_eq!(Tokens::free_balance(Asset::CombinatorialShare(0, 1, 1, 0), &CHARLIE), 0);
+        let escrow_account = PredictionMarkets::market_account(1);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &escrow_account), 0);
+        assert_noop!(
+            PredictionMarkets::redeem_conditional_shares(Origin::signed(DAVE), 0, 1, 1),
+            Error::<Runtime>::NoWinningBalance
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_invalid_conditional_sets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        assert_noop!(
+            PredictionMarkets::buy_conditional_set(Origin::signed(BOB), 0, 1, 1, 2 * CENT),
+            Error::<Runtime>::InsufficientShareBalance
+        );
+        assert_noop!(
+            PredictionMarkets::buy_conditional_set(Origin::signed(BOB), 0, 1, 1, 0),
+            Error::<Runtime>::ZeroAmount
+        );
+        assert_noop!(
+            PredictionMarkets::sell_conditional_set(Origin::signed(BOB), 0, 1, 1, 0),
+            Error::<Runtime>::ZeroAmount
+        );
+        assert_noop!(
+            PredictionMarkets::buy_conditional_set(Origin::signed(BOB), 0, 1, 0, CENT),
+            Error::<Runtime>::CannotConditionOnSameMarket
+        );
+        assert_noop!(
+            PredictionMarkets::buy_conditional_set(Origin::signed(BOB), 0, 2, 1, CENT),
+            Error::<Runtime>::OutcomeOutOfRange
+        );
+        assert_noop!(
+            PredictionMarkets::sell_conditional_set(Origin::signed(BOB), 0, 1, 1, CENT),
+            Error::<Runtime>::InsufficientShareBalance
+        );
+    });
+}
+
+#[test]
+fn it_allows_to_report_the_outcome_of_a_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        run_to_block(100);
+
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Active);
+        assert_eq!(market.report.is_none(), true);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        let market_after = MarketCommons::market(&0).unwrap();
+        let report = market_after.report.unwrap();
+        assert_eq!(market_after.status, MarketStatus::Reported);
+        assert_eq!(report.outcome, OutcomeReport::Categorical(1));
+        assert_eq!(report.by, market_after.oracle);
+
+        // Reset and report again as approval origin
+        let _ = MarketCommons::mutate_market(&0, |market| {
+            market.status = MarketStatus::Closed;
+            market.report = None;
+            Ok(())
+        });
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(SUDO),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        // Reset and report again as unauthorized origin
+        let _ = MarketCommons::mutate_market(&0, |market| {
+            market.status = MarketStatus::Closed;
+            market.report = None;
+            Ok(())
+        });
+
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+    });
+}
+
+#[test]
+fn block_and_timestamp_markets_have_equivalent_reporting_deadlines() {
+    ExtBuilder::default().build().execute_with(|| {
+        let ms_per_block: u64 = MillisecondsPerBlock::get().into();
+        for period in [MarketPeriod::Block(0..10), MarketPeriod::Timestamp(0..10 * ms_per_block)] {
+            assert_ok!(PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                period,
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ));
+        }
+        let deadline_block = 10 + u64::from(<Runtime as Config>::ReportingPeriod::get());
+        let block_market = MarketCommons::market(&0).unwrap();
+        let timestamp_market = MarketCommons::market(&1).unwrap();
+        assert_eq!(
+            PredictionMarkets::reporting_deadline(&block_market),
+            MarketEnd::Block(deadline_block)
+        );
+        assert_eq!(
+            PredictionMarkets::reporting_deadline(&timestamp_market),
+            MarketEnd::Timestamp(deadline_block * ms_per_block)
+        );
+
+        System::set_block_number(deadline_block);
+        Timestamp::set_timestamp(deadline_block * ms_per_block);
+        for market_id in [0, 1] {
+            assert_noop!(
+                PredictionMarkets::report(
+                    Origin::signed(CHARLIE),
+                    market_id,
+                    OutcomeReport::Categorical(1)
+                ),
+                Error::<Runtime>::ReporterNotOracle
+            );
+        }
+
+        System::set_block_number(deadline_block + 1);
+        Timestamp::set_timestamp((deadline_block + 1) * ms_per_block);
+        for market_id in [0, 1] {
+            assert_ok!(PredictionMarkets::report(
+                Origin::signed(CHARLIE),
+                market_id,
+                OutcomeReport::Categorical(1)
+            ));
+        }
+    });
+}
+
+#[test]
+fn only_the_oracle_can_report_during_the_oracle_grace_period_of_the_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions { oracle_grace_period: Some(5), ..Default::default() },
+        );
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(PredictionMarkets::oracle_grace_deadline(&0, &market), MarketEnd::Block(15));
+        assert_eq!(
+            PredictionMarkets::reporting_deadline(&market),
+            MarketEnd::Block(10 + u64::from(<Runtime as Config>::ReportingPeriod::get()))
+        );
+
+        System::set_block_number(15);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+        System::set_block_number(16);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+    });
+}
+
+#[test]
+fn markets_without_an_oracle_grace_period_use_the_configured_one() {
+    ExtBuilder::default().build().execute_with(|| {
+        OracleGracePeriod::set(3);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        System::set_block_number(13);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+        System::set_block_number(14);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+    });
+}
+
+#[test]
+fn the_oracle_can_correct_its_report_after_the_oracle_grace_period() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions { oracle_grace_period: Some(5), ..Default::default() },
+        );
+        System::set_block_number(11);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        System::set_block_number(17);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        let report = MarketCommons::market(&0).unwrap().report.unwrap();
+        assert_eq!(report.at, 17);
+        assert_eq!(report.outcome, OutcomeReport::Categorical(1));
+    });
+}
+
+#[test]
+fn a_report_of_a_fallback_reporter_cannot_be_corrected_after_the_oracle_was_reassigned() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions { oracle_grace_period: Some(5), ..Default::default() },
+        );
+        System::set_block_number(16);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert!(!MarketCommons::market(&0).unwrap().report.unwrap().by_oracle);
+
+        assert_ok!(MarketCommons::mutate_market(&0, |market| {
+            market.oracle = CHARLIE;
+            Ok(())
+        }));
+        System::set_block_number(17);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MarketAlreadyReported
+        );
+    });
+}
+
+#[test]
+fn it_does_not_create_markets_with_an_oracle_grace_period_above_the_reporting_period() {
+    ExtBuilder::default().build().execute_with(|| {
+        let reporting_period = <Runtime as Config>::ReportingPeriod::get();
+        assert_noop!(
+            PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                MarketPeriod::Block(0..10),
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                MarketCreationOptions {
+                    oracle_grace_period: Some(reporting_period + 1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Runtime>::InvalidOracleGracePeriod
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..10,
+            ScoringRule::CPMM,
+            CategoricalMarketOptions {
+                oracle_grace_period: Some(reporting_period),
+                ..Default::default()
+            },
+        );
+    });
+}
+
+#[test]
+fn timestamp_reporting_deadlines_follow_the_configured_block_time() {
+    ExtBuilder::default().build().execute_with(|| {
+        let ms_per_block = 6_000;
+        assert_ne!(u64::from(MillisecondsPerBlock::get()), ms_per_block);
+        MillisecondsPerBlock::set(ms_per_block as u32);
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..10 * ms_per_block),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        let reporting_period = u64::from(<Runtime as Config>::ReportingPeriod::get());
+        let deadline = (10 + reporting_period) * ms_per_block;
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(PredictionMarkets::reporting_deadline(&market), MarketEnd::Timestamp(deadline));
+
+        Timestamp::set_timestamp(deadline);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+        Timestamp::set_timestamp(deadline + 1);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+    });
+}
+
+#[test]
+fn it_honors_the_oracle_status_of_the_reporter_after_the_oracle_was_reassigned() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert!(MarketCommons::market(&0).unwrap().report.unwrap().by_oracle);
+
+        assert_ok!(MarketCommons::mutate_market(&0, |market| {
+            market.oracle = CHARLIE;
+            Ok(())
+        }));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&ALICE), 1_000 * BASE - ExistentialDeposit::get());
+        assert_eq!(Balances::free_balance(&BOB), 1_000 * BASE);
+    });
+}
+
+#[test]
+fn it_allows_the_oracle_to_correct_its_report_within_the_reporting_period() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        run_to_block(5);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(CHARLIE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        let report = MarketCommons::market(&0).unwrap().report.unwrap();
+        assert_eq!(report.at, 5);
+        assert_eq!(report.outcome, OutcomeReport::Categorical(1));
+        assert!(!MarketIdsPerReportBlock::<Runtime>::get(2).contains(&0));
+        assert_eq!(MarketIdsPerReportBlock::<Runtime>::get(5), vec![0]);
+
+        run_to_block(5 + <Runtime as Config>::DisputePeriod::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_correct_a_report_on_its_resolution_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        // Skip the resolution to keep the market in the `Reported` state.
+        System::set_block_number(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ResolutionDue
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_correct_a_report_after_the_reporting_period() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        // Skip the resolution to keep the market in the `Reported` state.
+        let reporting_period: u64 = <Runtime as Config>::ReportingPeriod::get().into();
+        System::set_block_number(1 + reporting_period + 1);
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MarketAlreadyReported
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_to_correct_a_disputed_report() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MarketAlreadyReported
+        );
+    });
+}
+
+#[test]
+fn it_refunds_complete_sets_if_a_market_is_disputed_to_invalid() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(DAVE), 0, 50 * BASE));
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(EVE), 0, OutcomeReport::Invalid));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Invalid));
+
+        // The oracle bond is slashed in favor of the correct disputant and the validity bond is
+        // slashed because the market is invalid.
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(
+            Balances::free_balance(&ALICE),
+            1_000 * BASE - OracleBond::get() - ValidityBond::get() - ExistentialDeposit::get()
+        );
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0));
+        assert_eq!(Balances::free_balance(&EVE), 1_000 * BASE + OracleBond::get());
+
+        // Nothing was destroyed on resolution and every complete set is refunded.
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(DAVE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE);
+        assert_eq!(Balances::free_balance(&DAVE), 1_000 * BASE);
+        for asset in PredictionMarkets::outcome_assets(0, &market) {
+            assert_eq!(Tokens::total_issuance(asset), 0);
+        }
+        assert_noop!(
+            PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::NoWinningBalance
+        );
+    });
+}
+
+#[test]
+fn it_allows_to_dispute_the_outcome_of_a_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // Run to the end of the trading phase.
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        // Dispute phase is 10 blocks... so only run 5 of them.
+        run_to_block(105);
+
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Disputed);
+
+        let disputes = crate::Disputes::<Runtime>::get(&0);
+        assert_eq!(disputes.len(), 1);
+        let dispute = &disputes[0];
+        assert_eq!(dispute.at, 105);
+        assert_eq!(dispute.by, CHARLIE);
+        assert_eq!(dispute.outcome, OutcomeReport::Categorical(0));
+
+        let market_ids = MarketIdsPerDisputeBlock::<Runtime>::get(&105);
+        assert_eq!(market_ids.len(), 1);
+        assert_eq!(market_ids[0], 0);
+    });
+}
+
+#[test]
+fn every_dispute_deposits_its_index_and_bond() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        for (index, (disputant, outcome)) in
+            vec![(CHARLIE, 0), (DAVE, 1), (EVE, 0)].into_iter().enumerate()
+        {
+            assert_ok!(PredictionMarkets::dispute(
+                Origin::signed(disputant),
+                0,
+                OutcomeReport::Categorical(outcome)
+            ));
+            System::assert_last_event(Event::PredictionMarkets(crate::Event::DisputeEscalated(
+                0,
+                index as u32,
+                OutcomeReport::Categorical(outcome),
+                DisputeBond::get() + index as u128 * DisputeFactor::get(),
+            )));
+        }
+    });
+}
+
+#[test]
+fn current_report_returns_the_report_of_a_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(PredictionMarkets::current_report(&0), None);
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_eq!(PredictionMarkets::current_report(&0), None);
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Scalar(25)));
+        assert_eq!(
+            PredictionMarkets::current_report(&0),
+            Some(Report { at: 2, by: BOB, outcome: OutcomeReport::Scalar(25), by_oracle: true })
+        );
+    });
+}
+
+#[test]
+fn collateral_asset_returns_the_native_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            PredictionMarkets::collateral_asset(&0),
+            Err(zrml_market_commons::Error::<Runtime>::MarketDoesNotExist.into())
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(PredictionMarkets::collateral_asset(&0), Ok(Asset::Ztg));
+
+        // Complete sets are paid for with the collateral asset.
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, BASE));
+        assert_eq!(Balances::free_balance(&BOB), 1_000 * BASE - BASE);
+    });
+}
+
+#[test]
+fn scheduled_resolution_block_matches_the_resolution_of_a_reported_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), None);
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        let end = 2 + <Runtime as Config>::DisputePeriod::get();
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), Some(end));
+
+        run_to_block(end - 1);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Reported);
+        run_to_block(end);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), None);
+    });
+}
+
+#[test]
+fn scheduled_resolution_block_matches_the_resolution_of_a_disputed_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(5);
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        let end = 5 + <Runtime as Config>::DisputePeriod::get();
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), Some(end));
+
+        run_to_block(end - 1);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
+        run_to_block(end);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), None);
+    });
+}
+
+#[test]
+fn scheduled_resolution_block_is_the_end_of_a_global_dispute() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        let end = 2 + <Runtime as Config>::GlobalDisputePeriod::get();
+        assert_eq!(PredictionMarkets::scheduled_resolution_block(&0), Some(end));
+
+        run_to_block(end - 1);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
+        run_to_block(end);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+    });
+}
+
+#[test]
+fn markets_by_oracle_lists_the_unresolved_markets_of_an_oracle() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            CHARLIE,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_eq!(PredictionMarkets::markets_by_oracle(&BOB), vec![0, 1]);
+        assert_eq!(PredictionMarkets::markets_by_oracle(&CHARLIE), vec![2]);
+        assert!(PredictionMarkets::markets_by_oracle(&DAVE).is_empty());
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::markets_by_oracle(&BOB), vec![1]);
+
+        assert_ok!(PredictionMarkets::admin_destroy_market(Origin::signed(SUDO), 2));
+        assert!(PredictionMarkets::markets_by_oracle(&CHARLIE).is_empty());
+    });
+}
+
+#[test]
+fn payout_table_of_a_resolved_categorical_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_eq!(PredictionMarkets::payout_table(&0), Ok(vec![]));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(PredictionMarkets::payout_table(&0), Ok(vec![]));
+
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(PredictionMarkets::payout_table(&0), Ok(vec![(0, 0), (1, BASE)]));
+    });
+}
+
+#[test]
+fn payout_table_of_a_resolved_scalar_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_dispute_scalar_market(25, &[]);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::payout_table(&0), Ok(vec![(0, 3 * BASE / 4), (1, BASE / 4)]));
+    });
+}
+
+#[test]
+fn the_oracle_cannot_dispute_its_market_by_default() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(BOB), 0, OutcomeReport::Categorical(0)),
+            Error::<Runtime>::OracleCannotDispute
+        );
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(BOB), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::OracleCannotDispute
+        );
+    });
+}
+
+#[test]
+fn the_oracle_can_dispute_its_market_if_allowed() {
+    ExtBuilder::default().build().execute_with(|| {
+        AllowOracleSelfDispute::set(true);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
+    });
+}
+
+#[test]
+fn dispute_bond_for_returns_the_bond_of_the_next_dispute() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(DisputeBond::get()));
+
+        for (n, who) in [CHARLIE, DAVE, EVE].iter().enumerate() {
+            let outcome = OutcomeReport::Categorical(n as u16 % 2);
+            assert_ok!(PredictionMarkets::dispute(Origin::signed(*who), 0, outcome));
+            let next_bond = DisputeBond::get() + (n as u128 + 1) * DisputeFactor::get();
+            assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(next_bond));
+        }
+    });
+}
+
+#[test]
+fn dispute_outcomes_flag_the_disputes_that_match_the_resolved_outcome() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_dispute_categorical_market(3, 0, &[1, 2, 1]);
+        assert_eq!(
+            MarketCommons::market(&0).unwrap().resolved_outcome,
+            Some(OutcomeReport::Categorical(1))
+        );
+        let flags: Vec<_> = PredictionMarkets::dispute_outcomes(&0)
+            .unwrap()
+            .into_iter()
+            .map(|(dispute, was_correct)| (dispute.by, was_correct))
+            .collect();
+        assert_eq!(flags, vec![(CHARLIE, true), (DAVE, false), (EVE, true)]);
+    });
+}
+
+#[test]
+fn dispute_outcomes_are_not_flagged_before_resolution() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            PredictionMarkets::dispute_outcomes(&0),
+            Err(zrml_market_commons::Error::<Runtime>::MarketDoesNotExist.into())
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(PredictionMarkets::dispute_outcomes(&0), Ok(vec![]));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        let dispute = MarketDispute { at: 2, by: CHARLIE, outcome: OutcomeReport::Categorical(0) };
+        assert_eq!(PredictionMarkets::dispute_outcomes(&0), Ok(vec![(dispute, false)]));
+    });
+}
+
+#[test]
+fn dispute_bond_schedule_returns_the_bond_of_every_dispute() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            PredictionMarkets::dispute_bond_schedule(&0),
+            Err(zrml_market_commons::Error::<Runtime>::MarketDoesNotExist.into())
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let max_disputes = <Runtime as Config>::MaxDisputes::get();
+        let expected: Vec<_> = (0..max_disputes)
+            .map(|i| DisputeBond::get() + i as u128 * DisputeFactor::get())
+            .collect();
+        assert_eq!(PredictionMarkets::dispute_bond_schedule(&0), Ok(expected));
+
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        let schedule = PredictionMarkets::dispute_bond_schedule(&0).unwrap();
+        for (n, who) in [CHARLIE, DAVE, EVE].iter().enumerate() {
+            assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(schedule[n]));
+            let outcome = OutcomeReport::Categorical(n as u16 % 2);
+            assert_ok!(PredictionMarkets::dispute(Origin::signed(*who), 0, outcome));
+        }
+    });
+}
+
+#[test]
+fn dispute_bond_schedule_respects_the_dispute_limit_of_the_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            MarketCreationOptions { max_disputes: Some(2), ..Default::default() }
+        ));
+        assert_eq!(
+            PredictionMarkets::dispute_bond_schedule(&0),
+            Ok(vec![DisputeBond::get(), DisputeBond::get() + DisputeFactor::get()])
+        );
+    });
+}
+
+#[test]
+fn can_dispute_is_true_only_during_the_dispute_window() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert!(!PredictionMarkets::can_dispute(&0));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert!(!PredictionMarkets::can_dispute(&0));
+
+        run_to_block(2);
+        assert!(!PredictionMarkets::can_dispute(&0));
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert!(PredictionMarkets::can_dispute(&0));
+
+        let resolution_block = 2 + <Runtime as Config>::DisputePeriod::get();
+        run_to_block(resolution_block - 1);
+        assert!(PredictionMarkets::can_dispute(&0));
+        run_to_block(resolution_block);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert!(!PredictionMarkets::can_dispute(&0));
+    });
+}
+
+#[test]
+fn can_dispute_is_false_once_the_dispute_limit_is_reached() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            MarketCreationOptions { max_disputes: Some(2), ..Default::default() }
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert!(PredictionMarkets::can_dispute(&0));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert!(!PredictionMarkets::can_dispute(&0));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(EVE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MaxDisputesReached
+        );
+    });
+}
+
+#[test]
+fn dispute_preview_matches_the_bond_and_reward_of_a_dispute() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        let yes = OutcomeReport::Categorical(0);
+        let no = OutcomeReport::Categorical(1);
+        assert_noop!(
+            PredictionMarkets::dispute_preview(&0, &no),
+            Error::<Runtime>::MarketNotReported
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, yes.clone()));
+
+        // No disputes yet: disputing the report would only earn the oracle bond.
+        let (bond, reward) = PredictionMarkets::dispute_preview(&0, &no).unwrap();
+        assert_eq!(bond, DisputeBond::get());
+        assert_eq!(reward, OracleBond::get());
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(CHARLIE), 0, no.clone()));
+        assert_eq!(Balances::reserved_balance(&CHARLIE), bond);
+
+        // One dispute: siding with the report would earn the bond of the first dispute.
+        let (bond, reward) = PredictionMarkets::dispute_preview(&0, &yes).unwrap();
+        assert_eq!(bond, PredictionMarkets::dispute_bond_for(&0).unwrap());
+        assert_eq!(reward, DisputeBond::get());
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(DAVE), 0, yes));
+        assert_eq!(Balances::reserved_balance(&DAVE), bond);
+
+        // Two disputes: the oracle bond and the bond of the second dispute are shared with the
+        // first disputant.
+        let (bond, reward) = PredictionMarkets::dispute_preview(&0, &no).unwrap();
+        let dave_bond = Balances::reserved_balance(&DAVE);
+        assert_eq!(bond, PredictionMarkets::dispute_bond_for(&0).unwrap());
+        assert_eq!(reward, (OracleBond::get() + dave_bond) / 2);
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(EVE), 0, no));
+        assert_eq!(Balances::reserved_balance(&EVE), bond);
+
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&EVE), 1_000 * BASE + reward);
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE + reward);
+    });
+}
+
+#[test]
+fn dispute_bonds_are_at_least_the_min_effective_dispute_bond() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        let min_bond = DisputeBond::get() + 2 * DisputeFactor::get();
+        MinEffectiveDisputeBond::set(min_bond);
+        assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(min_bond));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(Balances::reserved_balance(&CHARLIE), min_bond);
+
+        assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(min_bond));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(PredictionMarkets::dispute_bond_for(&0), Ok(min_bond));
+
+        // Once the dispute bonds grow above the floor, the factor applies again.
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(EVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(
+            PredictionMarkets::dispute_bond_for(&0),
+            Ok(DisputeBond::get() + 3 * DisputeFactor::get())
+        );
+    });
+}
+
+#[test]
+fn it_does_not_allow_an_account_to_exceed_its_dispute_limit() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        let max_disputes_per_account = <Runtime as Config>::MaxDisputesPerAccount::get();
+        assert!(max_disputes_per_account < <Runtime as Config>::MaxDisputes::get());
+        // Alternate between the two outcomes, starting with the one that wasn't reported.
+        let outcome = |i: u32| OutcomeReport::Categorical(((i + 1) % 2) as u16);
+        for i in 0..max_disputes_per_account {
+            assert_ok!(PredictionMarkets::dispute(Origin::signed(CHARLIE), 0, outcome(i)));
+        }
+        let next_outcome = outcome(max_disputes_per_account);
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(CHARLIE), 0, next_outcome.clone()),
+            Error::<Runtime>::AccountDisputeLimitReached
+        );
+
+        // Other accounts can still dispute.
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(DAVE), 0, next_outcome));
+    });
+}
+
+#[test]
+fn it_allows_anyone_to_report_an_unreported_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // Just skip to waaaay overdue.
+        run_to_block(9000);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(ALICE), // alice reports her own market now
+            0,
+            OutcomeReport::Categorical(1),
+        ));
+
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Reported);
+        assert_eq!(market.report.unwrap().by, ALICE);
+        // but oracle was bob
+        assert_eq!(market.oracle, BOB);
+
+        // make sure it still resolves
+        run_to_block(9011);
+
+        let market_after = MarketCommons::market(&0).unwrap();
+        assert_eq!(market_after.status, MarketStatus::Resolved);
+    });
+}
+
+#[test]
+fn only_the_oracle_can_report_if_public_reporting_is_disabled() {
+    ExtBuilder::default().build().execute_with(|| {
+        AllowPublicReporting::set(false);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        // Skip past the reporting period.
+        System::set_block_number(9000);
+
+        assert_noop!(
+            PredictionMarkets::report(Origin::signed(ALICE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::ReporterNotOracle
+        );
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(MarketCommons::market(&0).unwrap().report.unwrap().by, BOB);
+    });
+}
+
+#[test]
+fn it_correctly_resolves_a_market_that_was_reported_on() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
+
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        let reported_ids = MarketIdsPerReportBlock::<Runtime>::get(&100);
+        assert_eq!(reported_ids.len(), 1);
+        let id = reported_ids[0];
+        assert_eq!(id, 0);
+
+        run_to_block(111);
+
+        let market = MarketCommons::market(&0);
+        assert_eq!(market.unwrap().status, MarketStatus::Resolved);
+
+        // check to make sure all but the winning share was deleted
+        let share_a = Asset::CategoricalOutcome(0, 0);
+        let share_a_total = Tokens::total_issuance(share_a);
+        assert_eq!(share_a_total, 0);
+        let share_a_bal = Tokens::free_balance(share_a, &CHARLIE);
+        assert_eq!(share_a_bal, 0);
+
+        let share_b = Asset::CategoricalOutcome(0, 1);
+        let share_b_total = Tokens::total_issuance(share_b);
+        assert_eq!(share_b_total, CENT);
+        let share_b_bal = Tokens::free_balance(share_b, &CHARLIE);
+        assert_eq!(share_b_bal, CENT);
+
+        let share_c = Asset::CategoricalOutcome(0, 2);
+        let share_c_total = Tokens::total_issuance(share_c);
+        assert_eq!(share_c_total, 0);
+        let share_c_bal = Tokens::free_balance(share_c, &CHARLIE);
+        assert_eq!(share_c_bal, 0);
+    });
+}
+
+#[test]
+fn it_schedules_a_market_disputed_twice_in_one_block_once() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(100);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        run_to_block(102);
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(MarketIdsPerDisputeBlock::<Runtime>::get(&102), vec![0]);
+
+        run_to_block(103);
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(EVE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert!(MarketIdsPerDisputeBlock::<Runtime>::get(&102).is_empty());
+        assert_eq!(MarketIdsPerDisputeBlock::<Runtime>::get(&103), vec![0]);
+
+        run_to_block(103 + <Runtime as Config>::DisputePeriod::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
+    });
+}
+
+#[test]
+fn it_does_not_dispute_more_than_max_concurrent_disputed_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxConcurrentDisputedMarkets::set(1);
+        for _ in 0..2 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Permissionless,
+                0..1,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
+        }
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        // Markets that are already disputed can still be disputed again.
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 1);
+
+        run_to_block(5);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            1,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(CHARLIE), 1, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::TooManyDisputedMarkets
+        );
+
+        // Resolving market 0 frees the slot.
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 0);
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            1,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 1);
+    });
+}
+
+fn assert_disputed_markets_count_is_consistent() {
+    let disputed = MarketCommons::markets()
+        .iter()
+        .filter(|(_, market)| market.status == MarketStatus::Disputed)
+        .count();
+    assert_eq!(DisputedMarketsCount::<Runtime>::get() as usize, disputed);
+}
+
+#[test]
+fn status_transitions_keep_the_disputed_markets_count_consistent() {
+    ExtBuilder::default().build().execute_with(|| {
+        for _ in 0..2 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Permissionless,
+                0..1,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
+        }
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::approve_market(Origin::signed(SUDO), 2));
+        assert_disputed_markets_count_is_consistent();
+
+        run_to_block(2);
+        for market_id in 0..3 {
+            assert_ok!(PredictionMarkets::report(
+                Origin::signed(BOB),
+                market_id,
+                OutcomeReport::Categorical(0)
+            ));
+            assert_disputed_markets_count_is_consistent();
+        }
+        for (market_id, who, outcome) in vec![(0, CHARLIE, 1), (0, DAVE, 0), (1, CHARLIE, 1)] {
+            let outcome = OutcomeReport::Categorical(outcome);
+            assert_ok!(PredictionMarkets::dispute(Origin::signed(who), market_id, outcome));
+            assert_disputed_markets_count_is_consistent();
+        }
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 2);
+
+        assert_ok!(PredictionMarkets::admin_move_market_to_resolved(Origin::signed(SUDO), 1));
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Resolved);
+        assert_disputed_markets_count_is_consistent();
+
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        for market_id in 0..3 {
+            assert_eq!(MarketCommons::market(&market_id).unwrap().status, MarketStatus::Resolved);
+        }
+        assert_disputed_markets_count_is_consistent();
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 0);
+    });
+}
+
+#[test]
+fn stalling_a_disputed_market_keeps_the_disputed_markets_count_consistent() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxResolutionFailures::set(1);
+        // The authorized account doesn't submit an outcome in time, so the resolution fails.
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::Authorized(SUDO),
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 1);
+
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::ResolutionStalled);
+        assert_disputed_markets_count_is_consistent();
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 0);
+
+        assert_ok!(Authorized::authorize_market_outcome(
+            Origin::signed(SUDO),
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::admin_move_market_to_resolved(Origin::signed(SUDO), 0));
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_disputed_markets_count_is_consistent();
+        assert_eq!(DisputedMarketsCount::<Runtime>::get(), 0);
+    });
+}
+
+#[test]
+fn it_resolves_a_disputed_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
+
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        run_to_block(102);
+
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        run_to_block(103);
+
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+
+        run_to_block(104);
+
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(EVE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Disputed);
+
+        // check everyone's deposits
+        let charlie_reserved = Balances::reserved_balance(&CHARLIE);
+        assert_eq!(charlie_reserved, DisputeBond::get());
+
+        let dave_reserved = Balances::reserved_balance(&DAVE);
+        assert_eq!(dave_reserved, DisputeBond::get() + DisputeFactor::get());
+
+        let eve_reserved = Balances::reserved_balance(&EVE);
+        assert_eq!(eve_reserved, DisputeBond::get() + 2 * DisputeFactor::get());
+
+        // check disputes length
+        let disputes = crate::Disputes::<Runtime>::get(&0);
+        assert_eq!(disputes.len(), 3);
+
+        // make sure the old mappings of market id per dispute block are erased
+        let market_ids_1 = MarketIdsPerDisputeBlock::<Runtime>::get(&102);
+        assert_eq!(market_ids_1.len(), 0);
+
+        let market_ids_2 = MarketIdsPerDisputeBlock::<Runtime>::get(&103);
+        assert_eq!(market_ids_2.len(), 0);
+
+        let market_ids_3 = MarketIdsPerDisputeBlock::<Runtime>::get(&104);
+        assert_eq!(market_ids_3.len(), 1);
+
+        run_to_block(115);
+
+        let market_after = MarketCommons::market(&0).unwrap();
+        assert_eq!(market_after.status, MarketStatus::Resolved);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(CHARLIE), 0));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0));
+
+        // make sure rewards are right
+        //
+        // slashed amounts
+        // ---------------------------
+        // - OracleBond: 50 * CENT
+        // - Dave's reserve: DisputeBond::get() + DisputeFactor::get()
+        // Total: 50 * CENT + DisputeBond::get() + DisputeFactor::get()
+        // Per each: 25 * CENT + (DisputeBond::get() + DisputeFactor::get()) / 2
+
+        let dave_reserved = DisputeBond::get() + DisputeFactor::get();
+
+        let charlie_balance = Balances::free_balance(&CHARLIE);
+        assert_eq!(charlie_balance, 1_000 * BASE + 25 * CENT + dave_reserved / 2);
+        let charlie_reserved_2 = Balances::reserved_balance(&CHARLIE);
+        assert_eq!(charlie_reserved_2, 0);
+        let eve_balance = Balances::free_balance(&EVE);
+        assert_eq!(eve_balance, 1_000 * BASE + 25 * CENT + dave_reserved / 2);
+
+        let dave_balance = Balances::free_balance(&DAVE);
+        assert_eq!(dave_balance, 1_000 * BASE - dave_reserved);
+
+        let alice_balance = Balances::free_balance(&ALICE);
+        assert_eq!(alice_balance, 1_000 * BASE - 50 * CENT - ExistentialDeposit::get());
+
+        // bob kinda gets away scot-free since Alice is held responsible
+        // for her designated reporter
+        let bob_balance = Balances::free_balance(&BOB);
+        assert_eq!(bob_balance, 1_000 * BASE);
+    });
+}
+
+#[test]
+fn it_allows_to_redeem_shares() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        let bal = Balances::free_balance(&CHARLIE);
+        assert_eq!(bal, 1_000 * BASE);
+    });
+}
+
+#[test]
+fn redeem_shares_pays_out_pro_rata_if_the_market_account_is_insolvent() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(DAVE), 0, 300 * BASE));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+
+        // Only half of the collateral remains in the market account.
+        let market_account = PredictionMarkets::market_account(0);
+        assert_ok!(Balances::transfer(
+            Origin::signed(market_account),
+            EVE,
+            200 * BASE + ExistentialDeposit::get()
+        ));
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - 100 * BASE + 50 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &CHARLIE), 0);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(DAVE), 0));
+        assert_eq!(Balances::free_balance(&DAVE), 1_000 * BASE - 300 * BASE + 150 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &DAVE), 0);
+
+        assert_eq!(Balances::free_balance(&market_account), 0);
+    });
+}
+
+#[test]
+fn redeem_shares_fails_if_the_market_account_is_insolvent_and_pro_rata_is_disabled() {
+    ExtBuilder::default().build().execute_with(|| {
+        ProRataRedemption::set(false);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+
+        let market_account = PredictionMarkets::market_account(0);
+        assert_ok!(Balances::transfer(Origin::signed(market_account), EVE, 50 * BASE));
+
+        assert_noop!(
+            PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::InsufficientFundsInMarketAccount
+        );
+    });
+}
+
+#[test]
+fn redeem_shares_refunds_the_collateral_pro_rata_if_the_winning_outcome_has_no_supply() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(DAVE), 0, 30 * BASE));
+        // Nobody holds any shares of the outcome that is going to win.
+        let winning_asset = Asset::CategoricalOutcome(0, 1);
+        assert_ok!(Tokens::withdraw(winning_asset, &CHARLIE, 10 * BASE));
+        assert_ok!(Tokens::withdraw(winning_asset, &DAVE, 30 * BASE));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &CHARLIE), 0);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(DAVE), 0));
+        assert_eq!(Balances::free_balance(&DAVE), 1_000 * BASE);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &DAVE), 0);
+
+        let market_account = PredictionMarkets::market_account(0);
+        assert_eq!(Balances::free_balance(&market_account), ExistentialDeposit::get());
+    });
+}
+
+#[test]
+fn redeem_shares_fails_if_the_winning_outcome_has_no_supply_and_refunds_are_disabled() {
+    ExtBuilder::default().build().execute_with(|| {
+        ZeroSupplyRefund::set(false);
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 10 * BASE));
+        assert_ok!(Tokens::withdraw(Asset::CategoricalOutcome(0, 1), &CHARLIE, 10 * BASE));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+
+        assert_noop!(
+            PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::NoWinningBalance
+        );
+    });
+}
+
+#[test]
+fn resolution_burns_the_winning_shares_held_by_the_market_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        let market_account = PredictionMarkets::market_account(0);
+        let winning_asset = Asset::CategoricalOutcome(0, 1);
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            market_account,
+            winning_asset,
+            40 * BASE
+        ));
+        run_to_block(100);
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(Tokens::free_balance(winning_asset, &market_account), 0);
+        assert_eq!(Tokens::total_issuance(winning_asset), 60 * BASE);
+
+        // Only the shares held by CHARLIE are paid out of the escrow.
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - 40 * BASE);
+        assert_eq!(Balances::free_balance(&market_account), 40 * BASE + ExistentialDeposit::get());
+    });
+}
+
+#[test]
+fn redeem_shares_pays_the_creator_and_treasury_fees() {
+    ExtBuilder::default().build().execute_with(|| {
+        RedemptionTreasuryFee::set(Perbill::from_percent(10));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(MarketCommons::mutate_market(&0, |market| {
+            market.creator_fee = 5;
+            Ok(())
+        }));
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        run_to_block(100);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        run_to_block(111);
+
+        let alice_balance = Balances::free_balance(&ALICE);
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - 100 * BASE + 85 * BASE);
+        assert_eq!(Balances::free_balance(&ALICE), alice_balance + 5 * BASE);
+        assert_eq!(Treasury::balance(), 10 * BASE);
+        assert_eq!(
+            Balances::free_balance(&PredictionMarkets::market_account(0)),
+            ExistentialDeposit::get()
+        );
+    });
+}
+
+#[test]
+fn split_redemption_payout_sums_up_to_the_gross_payout() {
+    ExtBuilder::default().build().execute_with(|| {
+        let fees = [0, 1, 3, 33, 50, 99, 100];
+        let mut payouts: Vec<u128> = (0..1_000).collect();
+        payouts.extend((1..=30).map(|exp| 3u128.pow(exp) + 7));
+        payouts.extend(vec![BASE - 1, BASE, 123_456_789 * BASE + 1, u128::MAX / 2, u128::MAX]);
+        for creator_fee in fees.iter() {
+            for treasury_fee in fees.iter().filter(|fee| *creator_fee + **fee <= 100) {
+                for gross in payouts.iter() {
+                    let (to_creator, to_treasury, to_redeemer) =
+                        PredictionMarkets::split_redemption_payout(
+                            *gross,
+                            Perbill::from_percent(*creator_fee),
+                            Perbill::from_percent(*treasury_fee),
+                        )
+                        .unwrap();
+                    assert_eq!(to_creator + to_treasury + to_redeemer, *gross);
+                    assert!(to_creator <= Perbill::from_percent(*creator_fee) * *gross);
+                    assert!(to_treasury <= Perbill::from_percent(*treasury_fee) * *gross);
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn split_redemption_payout_fails_if_the_fees_exceed_the_gross_payout() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            PredictionMarkets::split_redemption_payout(
+                100 * BASE,
+                Perbill::from_percent(60),
+                Perbill::from_percent(50),
+            ),
+            Err(ArithmeticError::Underflow.into())
+        );
+    });
+}
+
+#[test]
+fn create_market_and_deploy_assets_is_identical_to_sequential_calls() {
+    let oracle = ALICE;
+    let period = MarketPeriod::Block(0..42);
+    let metadata = gen_metadata(42);
+    let creation = MarketCreation::Permissionless;
+    let category_count = 4;
+    let assets = MarketType::Categorical(category_count);
+    let extra_amount = 20 * BASE;
+    let keep_amount = 10 * BASE;
+    let min_liqudity = <Runtime as zrml_swaps::Config>::MinLiquidity::get();
+    let amount = min_liqudity + extra_amount;
+    let weights = vec![<Runtime as zrml_swaps::Config>::MinWeight::get(); 5];
+    let amount_base_asset = amount;
+    let amounts = vec![amount - extra_amount, amount, amount, amount];
+    let keep = vec![keep_amount, 0, 0, 0];
+    let pool_id = 0;
+
+    let first_state: RefCell<Vec<u8>> = RefCell::new(vec![]);
+    let second_state = RefCell::new(vec![]);
+
+    // Execute the combined convenience function
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_cpmm_market_and_deploy_assets(
+            Origin::signed(ALICE),
+            oracle,
+            period.clone(),
+            metadata.clone(),
+            creation.clone(),
+            assets,
+            MarketDisputeMechanism::SimpleDisputes,
+            amount_base_asset.clone(),
+            amounts.clone(),
+            weights.clone(),
+            keep.clone(),
+            None,
+            None
+        ));
+
+        *first_state.borrow_mut() = storage_root();
+    });
+
+    // Execute every command included in the convencience function one-by-one
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            oracle,
+            period.clone(),
+            metadata,
+            creation,
+            category_count,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(ALICE), 0, amount));
+        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
+            Origin::signed(ALICE),
+            pool_id,
+            weights
+        ));
+
+        for (idx, amount) in amounts.into_iter().enumerate() {
+            assert_ok!(Swaps::pool_join_with_exact_asset_amount(
+                Origin::signed(ALICE),
+                pool_id,
+                Asset::CategoricalOutcome(0, idx as u16),
+                amount - min_liqudity,
+                0
+            ));
+        }
+
+        assert_ok!(Swaps::pool_join_with_exact_asset_amount(
+            Origin::signed(ALICE),
+            pool_id,
+            Asset::Ztg,
+            amount_base_asset - min_liqudity,
+            0
+        ));
+
+        assert_ok!(Swaps::swap_exact_amount_in(
+            Origin::signed(ALICE),
+            pool_id,
+            Asset::CategoricalOutcome(0, 0),
+            amount - min_liqudity - keep[0],
+            Asset::Ztg,
+            0,
+            u128::MAX,
+        ));
+
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &ALICE), keep_amount);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &ALICE), 0);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 2), &ALICE), 0);
+        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 3), &ALICE), 0);
+        *second_state.borrow_mut() = storage_root();
+    });
+
+    // Compare resulting state
+    assert_eq!(*first_state.borrow(), *second_state.borrow());
+}
+
+#[test]
+fn process_subsidy_collecting_market_creates_or_destroys_markets_properly() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Create permissionless categorical market using Rikiddo.
+        // One will have enough subsidy, one insufficient and the last not ready for activation.
+        let min_sub_period = <Runtime as crate::Config>::MinSubsidyPeriod::get();
+        let max_sub_period = <Runtime as crate::Config>::MaxSubsidyPeriod::get();
+
+        for _ in 0..2 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Permissionless,
+                min_sub_period..max_sub_period,
+                ScoringRule::RikiddoSigmoidFeeMarketEma,
+                Default::default(),
+            );
+        }
+
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            min_sub_period + 42..max_sub_period,
+            ScoringRule::RikiddoSigmoidFeeMarketEma,
+            Default::default(),
+        );
+
+        let market_enough_subsidy = 0;
+        let market_insufficient_subsidy = 1;
+        let market_not_ready = 2;
+        let min_subsidy = <Runtime as zrml_swaps::Config>::MinSubsidy::get();
+
+        // Give alice enough funds and subsidize one market
+        assert_ok!(Tokens::deposit(Asset::Ztg, &ALICE, min_subsidy));
+        assert_ok!(Swaps::pool_join_subsidy(
+            Origin::signed(ALICE),
+            market_enough_subsidy,
+            min_subsidy
+        ));
+
+        // Run to block where 2 markets are ready and process all markets.
+        run_to_block(min_sub_period);
+        let subsidy_queue = crate::MarketsCollectingSubsidy::<Runtime>::get();
+        assert!(subsidy_queue.len() == 1);
+        assert!(subsidy_queue[0].market_id == market_not_ready);
+        assert!(
+            MarketCommons::market(&market_enough_subsidy).unwrap().status == MarketStatus::Active
+        );
+        assert!(
+            MarketCommons::market(&market_insufficient_subsidy).unwrap().status
+                == MarketStatus::InsufficientSubsidy
+        );
+    });
+}
+
+#[test]
+fn start_subsidy_creates_pool_and_starts_subsidy() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Create advised categorical market using Rikiddo.
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Advised,
+            1337..1337,
+            ScoringRule::RikiddoSigmoidFeeMarketEma,
+            Default::default(),
+        );
+        let market_id = 0;
+        let mut market = MarketCommons::market(&market_id).unwrap();
+
+        // Ensure and set correct market status.
+        assert_err!(
+            PredictionMarkets::start_subsidy(&market, market_id),
+            crate::Error::<Runtime>::MarketIsNotCollectingSubsidy
+        );
+        assert_ok!(MarketCommons::mutate_market(&market_id, |market_inner| {
+            market_inner.status = MarketStatus::CollectingSubsidy;
+            market = market_inner.clone();
+            Ok(())
+        }));
+
+        // Pool was created and market was registered for state transition into active.
+        assert_ok!(PredictionMarkets::start_subsidy(&market, market_id));
+        assert_ok!(MarketCommons::market_pool(&market_id));
+        let mut inserted = false;
+
+        for market in crate::MarketsCollectingSubsidy::<Runtime>::get() {
+            if market.market_id == market_id {
+                inserted = true;
+            }
+        }
+
+        assert!(inserted);
+    });
+}
+
+#[test]
+fn the_entire_market_lifecycle_works_with_timestamps() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Creates a permissionless market.
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000_000),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+
+        // is ok
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
+
+        // set the timestamp
+        Timestamp::set_timestamp(123_456_789);
+
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive,
+        );
+
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+    });
+}
+
+#[test]
+fn estimated_end_timestamp_extrapolates_block_based_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..100,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+
+        run_to_block(10);
+        Timestamp::set_timestamp(30_000);
+        let ms_per_block: u64 = MillisecondsPerBlock::get().into();
+        assert_eq!(
+            PredictionMarkets::estimated_end_timestamp(&0).unwrap(),
+            30_000 + 90 * ms_per_block
+        );
+
+        run_to_block(102);
+        Timestamp::set_timestamp(30_000 + 92 * ms_per_block);
+        assert_eq!(
+            PredictionMarkets::estimated_end_timestamp(&0).unwrap(),
+            30_000 + 90 * ms_per_block
+        );
+    });
+}
+
+#[test]
+fn estimated_end_timestamp_returns_the_end_of_timestamp_based_markets() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000_000),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+
+        Timestamp::set_timestamp(42_000);
+        assert_eq!(PredictionMarkets::estimated_end_timestamp(&0).unwrap(), 100_000_000);
+    });
+}
+
+#[test]
+fn markets_ending_between_normalizes_block_and_timestamp_ends() {
+    ExtBuilder::default().build().execute_with(|| {
+        let ms_per_block: u64 = MillisecondsPerBlock::get().into();
+        let create = |period| {
+            assert_ok!(PredictionMarkets::create_categorical_market(
+                Origin::signed(ALICE),
+                BOB,
+                period,
+                gen_metadata(2),
+                MarketCreation::Permissionless,
+                2,
+                MarketDisputeMechanism::SimpleDisputes,
+                ScoringRule::CPMM,
+                Default::default()
+            ));
+        };
+        create(MarketPeriod::Block(0..10));
+        create(MarketPeriod::Timestamp(0..20 * ms_per_block));
+        create(MarketPeriod::Block(0..30));
+        create(MarketPeriod::Timestamp(0..1_000 * ms_per_block));
+
+        let ending_between = |from_block: u64, to_block: u64| {
+            PredictionMarkets::markets_ending_between(
+                from_block * ms_per_block,
+                to_block * ms_per_block,
+            )
+        };
+        assert_eq!(ending_between(0, 10), Vec::<u128>::new());
+        assert_eq!(ending_between(10, 30), vec![0, 1]);
+        assert_eq!(ending_between(20, 31), vec![1, 2]);
+        assert_eq!(ending_between(20, 1_001), vec![1, 2, 3]);
+    });
+}
+
+#[test]
+fn full_scalar_market_lifecycle() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Timestamp(0..100_000_000),
+            gen_metadata(3),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE,));
+
+        // check balances
+        let assets = PredictionMarkets::outcome_assets(0, &MarketCommons::market(&0).unwrap());
+        assert_eq!(assets.len(), 2);
+        for asset in assets.iter() {
+            let bal = Tokens::free_balance(*asset, &CHARLIE);
+            assert_eq!(bal, 100 * BASE);
+        }
+
+        Timestamp::set_timestamp(123_456_789);
+        run_to_block(100);
+
+        // report
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Scalar(100)));
+
+        let market_after_report = MarketCommons::market(&0).unwrap();
+        assert_eq!(market_after_report.report.is_some(), true);
+        let report = market_after_report.report.unwrap();
+        assert_eq!(report.at, 100);
+        assert_eq!(report.by, BOB);
+        assert_eq!(report.outcome, OutcomeReport::Scalar(100));
+
+        // dispute
+        assert_ok!(PredictionMarkets::dispute(Origin::signed(DAVE), 0, OutcomeReport::Scalar(20)));
+        let disputes = crate::Disputes::<Runtime>::get(&0);
+        assert_eq!(disputes.len(), 1);
+
+        run_to_block(150);
+
+        let market_after_resolve = MarketCommons::market(&0).unwrap();
+        assert_eq!(market_after_resolve.status, MarketStatus::Resolved);
+
+        // give EVE some shares
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            EVE,
+            Asset::ScalarOutcome(0, ScalarPosition::Short),
+            100 * BASE
+        ));
+
+        assert_eq!(
+            Tokens::free_balance(Asset::ScalarOutcome(0, ScalarPosition::Short), &CHARLIE),
+            0
+        );
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        for asset in assets.iter() {
+            let bal = Tokens::free_balance(*asset, &CHARLIE);
+            assert_eq!(bal, 0);
+        }
+
+        // check payouts is right for each CHARLIE and EVE
+        let ztg_bal_charlie = Balances::free_balance(&CHARLIE);
+        let ztg_bal_eve = Balances::free_balance(&EVE);
+        assert_eq!(ztg_bal_charlie, 950 * BASE);
+        assert_eq!(ztg_bal_eve, 1000 * BASE);
+
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(EVE), 0));
+        let ztg_bal_eve_after = Balances::free_balance(&EVE);
+        assert_eq!(ztg_bal_eve_after, 1050 * BASE);
+    })
+}
+
+#[test]
+fn disputers_can_claim_their_settled_dispute_bonds_once() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
             0,
-            OutcomeReport::Categorical(0)
+            OutcomeReport::Categorical(1)
         ));
-
-        run_to_block(102);
-
         assert_ok!(PredictionMarkets::dispute(
             Origin::signed(CHARLIE),
             0,
-            OutcomeReport::Categorical(1)
+            OutcomeReport::Categorical(0)
         ));
-
-        run_to_block(103);
-
         assert_ok!(PredictionMarkets::dispute(
             Origin::signed(DAVE),
             0,
-            OutcomeReport::Categorical(0)
+            OutcomeReport::Categorical(1)
         ));
+        assert_noop!(
+            PredictionMarkets::claim_dispute_bond(Origin::signed(DAVE), 0),
+            Error::<Runtime>::MarketIsNotResolved
+        );
 
-        run_to_block(104);
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        // DAVE's bond stays reserved until it's claimed, CHARLIE's bond is already slashed.
+        let dave_bond = DisputeBond::get() + DisputeFactor::get();
+        assert_eq!(Balances::reserved_balance(&DAVE), dave_bond);
+        assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+        let dave_balance = Balances::free_balance(&DAVE);
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(DAVE), 0));
+        assert_eq!(Balances::reserved_balance(&DAVE), 0);
+        assert_eq!(Balances::free_balance(&DAVE), dave_balance + dave_bond);
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::DisputeBondClaimed(
+            0,
+            DAVE,
+            DisputeBond::get() + DisputeFactor::get(),
+            0,
+        )));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(CHARLIE), 0));
+        System::assert_last_event(Event::PredictionMarkets(crate::Event::DisputeBondClaimed(
+            0,
+            CHARLIE,
+            0,
+            DisputeBond::get(),
+        )));
+        assert_noop!(
+            PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0),
+            Error::<Runtime>::NoDisputeBond
+        );
 
-        assert_ok!(PredictionMarkets::dispute(
-            Origin::signed(EVE),
+        let event_count = System::events().len();
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(DAVE), 0));
+        assert_eq!(System::events().len(), event_count);
+        assert_eq!(Balances::free_balance(&DAVE), dave_balance + dave_bond);
+    });
+}
+
+#[test]
+fn disputed_scalar_market_resolves_to_the_last_dispute_by_default() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_dispute_scalar_market(20, &[28, 12, 26, 30]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Scalar(30)));
+    });
+}
+
+#[test]
+fn scalar_market_stores_resolved_values_beyond_the_category_range() {
+    ExtBuilder::default().build().execute_with(|| {
+        let resolved_value = u128::from(u16::MAX) + 84_465;
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            0..=200_000,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE));
+        assert_ok!(Tokens::transfer(
+            Origin::signed(CHARLIE),
+            EVE,
+            Asset::ScalarOutcome(0, ScalarPosition::Short),
+            100 * BASE
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
             0,
-            OutcomeReport::Categorical(1)
+            OutcomeReport::Scalar(resolved_value)
         ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
 
         let market = MarketCommons::market(&0).unwrap();
-        assert_eq!(market.status, MarketStatus::Disputed);
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Scalar(resolved_value)));
 
-        // check everyone's deposits
-        let charlie_reserved = Balances::reserved_balance(&CHARLIE);
-        assert_eq!(charlie_reserved, DisputeBond::get());
+        // The long position pays out 75% of the collateral, the short position 25%.
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(EVE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 975 * BASE);
+        assert_eq!(Balances::free_balance(&EVE), 1_025 * BASE);
+    });
+}
 
-        let dave_reserved = Balances::reserved_balance(&DAVE);
-        assert_eq!(dave_reserved, DisputeBond::get() + DisputeFactor::get());
+#[test]
+fn disputed_scalar_market_resolves_to_the_median_if_configured() {
+    ExtBuilder::default().build().execute_with(|| {
+        ScalarResolution::set(ScalarResolutionPolicy::Median);
+        create_and_dispute_scalar_market(20, &[28, 12, 26, 30]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Scalar(26)));
+        // EVE disputed the median and is refunded, FRED is slashed.
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0));
+        assert_eq!(Balances::reserved_balance(&EVE), 0);
+        assert!(Balances::free_balance(&EVE) > 1_000 * BASE);
+        assert_eq!(
+            Balances::free_balance(&FRED),
+            1_000 * BASE - DisputeBond::get() - 3 * DisputeFactor::get()
+        );
+    });
+}
 
-        let eve_reserved = Balances::reserved_balance(&EVE);
-        assert_eq!(eve_reserved, DisputeBond::get() + 2 * DisputeFactor::get());
+// Reports `reported_value` on a scalar market, disputes it with `disputed_values` under `step` and
+// checks that disputing `rejected_value` next is out of step.
+fn check_scalar_dispute_steps(
+    step: ScalarDisputeStep,
+    reported_value: u128,
+    disputed_values: &[u128],
+    rejected_value: u128,
+) {
+    ExtBuilder::default().build().execute_with(|| {
+        MonotonicScalarDisputes::set(step);
+        assert_ok!(PredictionMarkets::create_scalar_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            10..=30,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Scalar(reported_value)
+        ));
+        for (disputant, value) in [CHARLIE, DAVE, EVE].iter().zip(disputed_values) {
+            assert_ok!(PredictionMarkets::dispute(
+                Origin::signed(*disputant),
+                0,
+                OutcomeReport::Scalar(*value)
+            ));
+        }
+        assert_noop!(
+            PredictionMarkets::dispute(
+                Origin::signed(FRED),
+                0,
+                OutcomeReport::Scalar(rejected_value)
+            ),
+            Error::<Runtime>::DisputeValueOutOfStep
+        );
+    });
+}
 
-        // check disputes length
-        let disputes = crate::Disputes::<Runtime>::get(&0);
-        assert_eq!(disputes.len(), 3);
+#[test]
+fn increasing_scalar_disputes_may_only_raise_the_value() {
+    check_scalar_dispute_steps(ScalarDisputeStep::Increasing, 12, &[15, 22, 30], 29);
+    check_scalar_dispute_steps(ScalarDisputeStep::Increasing, 20, &[], 19);
+}
 
-        // make sure the old mappings of market id per dispute block are erased
-        let market_ids_1 = MarketIdsPerDisputeBlock::<Runtime>::get(&102);
-        assert_eq!(market_ids_1.len(), 0);
+#[test]
+fn decreasing_scalar_disputes_may_only_lower_the_value() {
+    check_scalar_dispute_steps(ScalarDisputeStep::Decreasing, 28, &[25, 11, 10], 11);
+    check_scalar_dispute_steps(ScalarDisputeStep::Decreasing, 20, &[], 21);
+}
 
-        let market_ids_2 = MarketIdsPerDisputeBlock::<Runtime>::get(&103);
-        assert_eq!(market_ids_2.len(), 0);
+#[test]
+fn scalar_disputes_may_move_the_value_by_at_most_the_max_delta() {
+    check_scalar_dispute_steps(ScalarDisputeStep::MaxDelta(5), 20, &[25, 20, 15], 21);
+    check_scalar_dispute_steps(ScalarDisputeStep::MaxDelta(5), 20, &[16], 10);
+}
 
-        let market_ids_3 = MarketIdsPerDisputeBlock::<Runtime>::get(&104);
-        assert_eq!(market_ids_3.len(), 1);
+#[test]
+fn monotonic_scalar_disputes_resolve_to_the_last_step() {
+    ExtBuilder::default().build().execute_with(|| {
+        MonotonicScalarDisputes::set(ScalarDisputeStep::Increasing);
+        create_and_dispute_scalar_market(12, &[15, 22, 26, 30]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Scalar(30)));
+    });
+}
 
-        run_to_block(115);
+#[test]
+fn disputed_categorical_market_resolves_to_the_last_dispute_by_default() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Outcome 1 has the highest total bond, outcome 0 was disputed last.
+        create_and_dispute_categorical_market(3, 0, &[1, 2, 1, 0]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(0)));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(FRED), 0));
+        assert_eq!(Balances::reserved_balance(&FRED), 0);
+        assert!(Balances::free_balance(&FRED) > 1_000 * BASE);
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - DisputeBond::get());
+        assert_eq!(
+            Balances::free_balance(&EVE),
+            1_000 * BASE - DisputeBond::get() - 2 * DisputeFactor::get()
+        );
+    });
+}
 
-        let market_after = MarketCommons::market(&0).unwrap();
-        assert_eq!(market_after.status, MarketStatus::Resolved);
+#[test]
+fn disputed_categorical_market_resolves_to_the_highest_bonded_outcome_if_configured() {
+    ExtBuilder::default().build().execute_with(|| {
+        DisputeResolution::set(DisputeResolutionRule::HighestBondedOutcome);
+        // Outcome 1 has the highest total bond, outcome 0 was disputed last.
+        create_and_dispute_categorical_market(3, 0, &[1, 2, 1, 0]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
+        for disputant in [CHARLIE, EVE] {
+            assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(disputant), 0));
+            assert_eq!(Balances::reserved_balance(&disputant), 0);
+            assert!(Balances::free_balance(&disputant) > 1_000 * BASE);
+        }
+        assert_eq!(
+            Balances::free_balance(&FRED),
+            1_000 * BASE - DisputeBond::get() - 3 * DisputeFactor::get()
+        );
+    });
+}
 
-        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
+#[test]
+fn highest_bonded_outcome_ties_resolve_to_the_most_recently_disputed_outcome() {
+    ExtBuilder::default().build().execute_with(|| {
+        DisputeResolution::set(DisputeResolutionRule::HighestBondedOutcome);
+        // Every dispute bond is raised to the same amount, so outcomes 1 and 2 tie.
+        MinEffectiveDisputeBond::set(20 * BASE);
+        create_and_dispute_categorical_market(3, 0, &[1, 2, 1, 2, 0]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(2)));
+    });
+}
 
-        // make sure rewards are right
-        //
-        // slashed amounts
-        // ---------------------------
-        // - OracleBond: 50 * CENT
-        // - Dave's reserve: DisputeBond::get() + DisputeFactor::get()
-        // Total: 50 * CENT + DisputeBond::get() + DisputeFactor::get()
-        // Per each: 25 * CENT + (DisputeBond::get() + DisputeFactor::get()) / 2
+#[test]
+fn median_resolution_slashes_all_disputes_if_the_report_was_the_median() {
+    ExtBuilder::default().build().execute_with(|| {
+        ScalarResolution::set(ScalarResolutionPolicy::Median);
+        create_and_dispute_scalar_market(20, &[28, 12]);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Scalar(20)));
+        assert_eq!(Balances::reserved_balance(&ALICE), 0);
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE - DisputeBond::get());
+        assert_eq!(
+            Balances::free_balance(&DAVE),
+            1_000 * BASE - DisputeBond::get() - DisputeFactor::get()
+        );
+    });
+}
 
-        let dave_reserved = DisputeBond::get() + DisputeFactor::get();
+#[test]
+fn disputed_markets_hand_the_slash_split_of_the_slashed_bonds_to_slash() {
+    ExtBuilder::default().build().execute_with(|| {
+        SlashSplit::set(Perbill::from_percent(20));
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        for (disputant, outcome) in [(CHARLIE, 1), (DAVE, 0), (EVE, 1)] {
+            assert_ok!(PredictionMarkets::dispute(
+                Origin::signed(disputant),
+                0,
+                OutcomeReport::Categorical(outcome)
+            ));
+        }
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
+
+        // The oracle bond and DAVE's bond are slashed.
+        let slashed = OracleBond::get() + DisputeBond::get() + DisputeFactor::get();
+        let treasury_share = Perbill::from_percent(20).mul_floor(slashed);
+        let reward = (slashed - treasury_share) / 2;
+        assert_eq!(Treasury::balance(), treasury_share - ResolutionReward::get());
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(CHARLIE), 0));
+        assert_ok!(PredictionMarkets::claim_dispute_bond(Origin::signed(EVE), 0));
+        assert_eq!(Balances::free_balance(&CHARLIE), 1_000 * BASE + reward);
+        assert_eq!(Balances::free_balance(&EVE), 1_000 * BASE + reward);
+    });
+}
 
-        let charlie_balance = Balances::free_balance(&CHARLIE);
-        assert_eq!(charlie_balance, 1_000 * BASE + 25 * CENT + dave_reserved / 2);
-        let charlie_reserved_2 = Balances::reserved_balance(&CHARLIE);
-        assert_eq!(charlie_reserved_2, 0);
-        let eve_balance = Balances::free_balance(&EVE);
-        assert_eq!(eve_balance, 1_000 * BASE + 25 * CENT + dave_reserved / 2);
+#[test]
+fn disputed_markets_without_correct_reporters_hand_all_slashed_bonds_to_slash() {
+    ExtBuilder::default().build().execute_with(|| {
+        SlashSplit::set(Perbill::from_percent(20));
+        ScalarResolution::set(ScalarResolutionPolicy::Median);
+        create_and_dispute_scalar_market(20, &[28, 12]);
+        assert_eq!(
+            MarketCommons::market(&0).unwrap().resolved_outcome,
+            Some(OutcomeReport::Scalar(20))
+        );
+        assert_eq!(
+            Treasury::balance(),
+            2 * DisputeBond::get() + DisputeFactor::get() - ResolutionReward::get()
+        );
+    });
+}
 
-        let dave_balance = Balances::free_balance(&DAVE);
-        assert_eq!(dave_balance, 1_000 * BASE - dave_reserved);
+// Disputes a binary market that was reported as `0` with `disputes` and checks that the correct
+// reporters receive exactly the slashed bonds that aren't handed to `Slash`. The slash split is
+// chosen so that the rewards can't be split evenly.
+fn check_rewards_of_correct_reporters(disputes: &[(AccountIdTest, u16)]) {
+    SlashSplit::set(Perbill::from_parts(3));
+    simple_create_categorical_market::<Runtime>(
+        MarketCreation::Permissionless,
+        0..1,
+        ScoringRule::CPMM,
+        Default::default(),
+    );
+    run_to_block(2);
+    assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Categorical(0)));
+    let balances_before: Vec<_> =
+        disputes.iter().map(|(disputant, _)| Balances::free_balance(disputant)).collect();
+    for (disputant, outcome) in disputes {
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(*disputant),
+            0,
+            OutcomeReport::Categorical(*outcome)
+        ));
+    }
+    run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+    let resolved_outcome = disputes.last().unwrap().1;
+    assert_eq!(
+        MarketCommons::market(&0).unwrap().resolved_outcome,
+        Some(OutcomeReport::Categorical(resolved_outcome))
+    );
+
+    let mut slashed = if resolved_outcome == 0 { 0 } else { OracleBond::get() };
+    let mut rewards = 0;
+    for (i, ((disputant, outcome), before)) in disputes.iter().zip(balances_before).enumerate() {
+        let bond = default_dispute_bond::<Runtime>(i);
+        if *outcome == resolved_outcome {
+            rewards += Balances::free_balance(disputant) - before;
+        } else {
+            assert_eq!(Balances::free_balance(disputant), before - bond);
+            slashed += bond;
+        }
+    }
+    let slash_share = SlashSplit::get().mul_floor(slashed);
+    assert_eq!(rewards, slashed - slash_share);
+}
 
-        let alice_balance = Balances::free_balance(&ALICE);
-        assert_eq!(alice_balance, 1_000 * BASE - 50 * CENT);
+#[test]
+fn a_single_correct_reporter_receives_all_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        check_rewards_of_correct_reporters(&[(CHARLIE, 1)]);
+    });
+}
 
-        // bob kinda gets away scot-free since Alice is held responsible
-        // for her designated reporter
-        let bob_balance = Balances::free_balance(&BOB);
-        assert_eq!(bob_balance, 1_000 * BASE);
+#[test]
+fn two_correct_reporters_receive_all_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        check_rewards_of_correct_reporters(&[(CHARLIE, 1), (DAVE, 0), (EVE, 1)]);
+    });
+}
+
+#[test]
+fn many_correct_reporters_receive_all_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        check_rewards_of_correct_reporters(&[
+            (CHARLIE, 1),
+            (DAVE, 0),
+            (EVE, 1),
+            (FRED, 0),
+            (SUDO, 1),
+        ]);
+    });
+}
+
+#[test]
+fn market_resolve_does_not_hold_liquidity_withdraw() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..100),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            3,
+            MarketDisputeMechanism::SimpleDisputes,
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        deploy_swap_pool(MarketCommons::market(&0).unwrap(), 0).unwrap();
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(ALICE), 0, 1 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 3 * BASE));
+
+        run_to_block(100);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(2)
+        ));
+
+        run_to_block(150);
+        assert_ok!(Swaps::pool_exit(Origin::signed(BOB), 0, BASE * 100, vec![0, 0]));
+        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(BOB), 0));
+    })
+}
+
+#[test]
+fn on_idle_removes_empty_block_index_entries() {
+    ExtBuilder::default().build().execute_with(|| {
+        MarketIdsPerReportBlock::<Runtime>::insert(1, Vec::<MarketId>::new());
+        MarketIdsPerDisputeBlock::<Runtime>::insert(3, Vec::<MarketId>::new());
+        MarketIdsPerReportBlock::<Runtime>::insert(15, Vec::<MarketId>::new());
+        PredictionMarkets::on_idle(20, u64::MAX);
+        assert!(!MarketIdsPerReportBlock::<Runtime>::contains_key(1));
+        assert!(!MarketIdsPerDisputeBlock::<Runtime>::contains_key(3));
+        // Block 15 is still within the dispute period and must not be touched.
+        assert!(MarketIdsPerReportBlock::<Runtime>::contains_key(15));
+        assert_eq!(CleanupCursor::<Runtime>::get(), 11);
+    });
+}
+
+#[test]
+fn on_idle_does_not_exceed_its_weight_allowance() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Neither of the markets exist, so both entries are stale.
+        MarketIdsPerReportBlock::<Runtime>::insert(1, vec![7]);
+        MarketIdsPerReportBlock::<Runtime>::insert(2, vec![8]);
+        let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+        let max_market_weight = db_weight.reads(2)
+            + <Runtime as Config>::WeightInfo::internal_resolve_categorical_disputed(
+                0,
+                0,
+                <Runtime as Config>::MaxCategories::get().into(),
+                <Runtime as Config>::MaxDisputes::get(),
+            );
+        // Enough to handle the empty block 0 and block 1, but not block 2.
+        let allowance =
+            db_weight.reads_writes(1, 1) + 2 * db_weight.reads_writes(2, 2) + max_market_weight;
+        let used_weight = PredictionMarkets::on_idle(20, allowance);
+        assert!(used_weight <= allowance);
+        assert!(!MarketIdsPerReportBlock::<Runtime>::contains_key(1));
+        assert!(MarketIdsPerReportBlock::<Runtime>::contains_key(2));
+        assert_eq!(CleanupCursor::<Runtime>::get(), 2);
+
+        PredictionMarkets::on_idle(20, u64::MAX);
+        assert!(!MarketIdsPerReportBlock::<Runtime>::contains_key(2));
     });
 }
 
 #[test]
-fn it_allows_to_redeem_shares() {
+fn on_idle_resolves_overdue_markets() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
         simple_create_categorical_market::<Runtime>(
             MarketCreation::Permissionless,
             0..1,
             ScoringRule::CPMM,
+            Default::default(),
         );
-
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, CENT));
-        run_to_block(100);
-
+        run_to_block(2);
         assert_ok!(PredictionMarkets::report(
             Origin::signed(BOB),
             0,
             OutcomeReport::Categorical(1)
         ));
-        run_to_block(111);
+        // Skip the block in which the market would have been resolved.
+        System::set_block_number(20);
+        PredictionMarkets::on_idle(20, u64::MAX);
         let market = MarketCommons::market(&0).unwrap();
         assert_eq!(market.status, MarketStatus::Resolved);
-
-        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
-        let bal = Balances::free_balance(&CHARLIE);
-        assert_eq!(bal, 1_000 * BASE);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
+        assert!(!MarketIdsPerReportBlock::<Runtime>::contains_key(2));
     });
 }
 
 #[test]
-fn create_market_and_deploy_assets_is_identical_to_sequential_calls() {
-    let oracle = ALICE;
-    let period = MarketPeriod::Block(0..42);
-    let metadata = gen_metadata(42);
-    let creation = MarketCreation::Permissionless;
-    let category_count = 4;
-    let assets = MarketType::Categorical(category_count);
-    let extra_amount = 20 * BASE;
-    let keep_amount = 10 * BASE;
-    let min_liqudity = <Runtime as zrml_swaps::Config>::MinLiquidity::get();
-    let amount = min_liqudity + extra_amount;
-    let weights = vec![<Runtime as zrml_swaps::Config>::MinWeight::get(); 5];
-    let amount_base_asset = amount;
-    let amounts = vec![amount - extra_amount, amount, amount, amount];
-    let keep = vec![keep_amount, 0, 0, 0];
-    let pool_id = 0;
+fn resolve_rewards_the_first_account_to_resolve_an_overdue_market() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Invalid));
 
-    let first_state: RefCell<Vec<u8>> = RefCell::new(vec![]);
-    let second_state = RefCell::new(vec![]);
+        // Skip the block in which the market would have been resolved.
+        let dispute_period = <Runtime as Config>::DisputePeriod::get();
+        System::set_block_number(2 + dispute_period - 1);
+        assert_noop!(
+            PredictionMarkets::resolve(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::ResolutionNotDue
+        );
+        System::set_block_number(2 + dispute_period);
+        let balance_before = Balances::free_balance(&CHARLIE);
+        assert_ok!(PredictionMarkets::resolve(Origin::signed(CHARLIE), 0));
+        // The reward is funded with the slashed validity bond of the invalid market.
+        assert_eq!(Balances::free_balance(&CHARLIE), balance_before + ResolutionReward::get());
+        assert_eq!(Treasury::balance(), ValidityBond::get() - ResolutionReward::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Invalid));
+        assert!(!MarketIdsPerReportBlock::<Runtime>::get(2).contains(&0));
 
-    // Execute the combined convenience function
+        assert_noop!(
+            PredictionMarkets::resolve(Origin::signed(DAVE), 0),
+            Error::<Runtime>::MarketAlreadyResolved
+        );
+    });
+}
+
+#[test]
+fn disputed_market_without_disputes_resolves_to_the_report() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(PredictionMarkets::create_cpmm_market_and_deploy_assets(
-            Origin::signed(ALICE),
-            oracle,
-            period.clone(),
-            metadata.clone(),
-            creation.clone(),
-            assets,
-            MarketDisputeMechanism::SimpleDisputes,
-            amount_base_asset.clone(),
-            amounts.clone(),
-            weights.clone(),
-            keep.clone()
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
         ));
+        crate::Disputes::<Runtime>::remove(0);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
 
-        *first_state.borrow_mut() = storage_root();
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(1)));
     });
+}
 
-    // Execute every command included in the convencience function one-by-one
+#[test]
+fn a_market_that_fails_to_resolve_does_not_block_other_resolutions() {
     ExtBuilder::default().build().execute_with(|| {
+        // The authorized account never submits an outcome, so the resolution of market 0 fails.
         assert_ok!(PredictionMarkets::create_categorical_market(
             Origin::signed(ALICE),
-            oracle,
-            period.clone(),
-            metadata,
-            creation,
-            category_count,
-            MarketDisputeMechanism::SimpleDisputes,
-            ScoringRule::CPMM
-        ));
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(ALICE), 0, amount));
-        assert_ok!(PredictionMarkets::deploy_swap_pool_for_market(
-            Origin::signed(ALICE),
-            pool_id,
-            weights
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::Authorized(SUDO),
+            ScoringRule::CPMM,
+            Default::default()
         ));
-
-        for (idx, amount) in amounts.into_iter().enumerate() {
-            assert_ok!(Swaps::pool_join_with_exact_asset_amount(
-                Origin::signed(ALICE),
-                pool_id,
-                Asset::CategoricalOutcome(0, idx as u16),
-                amount - min_liqudity,
-                0
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        for market_id in [0, 1] {
+            assert_ok!(PredictionMarkets::report(
+                Origin::signed(BOB),
+                market_id,
+                OutcomeReport::Categorical(1)
             ));
         }
-
-        assert_ok!(Swaps::pool_join_with_exact_asset_amount(
-            Origin::signed(ALICE),
-            pool_id,
-            Asset::Ztg,
-            amount_base_asset - min_liqudity,
-            0
-        ));
-
-        assert_ok!(Swaps::swap_exact_amount_in(
-            Origin::signed(ALICE),
-            pool_id,
-            Asset::CategoricalOutcome(0, 0),
-            amount - min_liqudity - keep[0],
-            Asset::Ztg,
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
             0,
-            u128::MAX,
+            OutcomeReport::Categorical(0)
         ));
 
-        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 0), &ALICE), keep_amount);
-        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 1), &ALICE), 0);
-        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 2), &ALICE), 0);
-        assert_eq!(Tokens::free_balance(Asset::CategoricalOutcome(0, 3), &ALICE), 0);
-        *second_state.borrow_mut() = storage_root();
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
+        assert_eq!(MarketCommons::market(&1).unwrap().status, MarketStatus::Resolved);
+        let has_event = |event| System::events().iter().any(|record| record.event == event);
+        assert!(has_event(Event::PredictionMarkets(crate::Event::MarketResolutionFailed(0))));
+        assert!(!has_event(Event::PredictionMarkets(crate::Event::BadOnInitialize)));
     });
-
-    // Compare resulting state
-    assert_eq!(*first_state.borrow(), *second_state.borrow());
 }
 
 #[test]
-fn process_subsidy_collecting_market_creates_or_destroys_markets_properly() {
+fn a_market_that_repeatedly_fails_to_resolve_is_stalled() {
     ExtBuilder::default().build().execute_with(|| {
-        // Create permissionless categorical market using Rikiddo.
-        // One will have enough subsidy, one insufficient and the last not ready for activation.
-        let min_sub_period = <Runtime as crate::Config>::MinSubsidyPeriod::get();
-        let max_sub_period = <Runtime as crate::Config>::MaxSubsidyPeriod::get();
+        MaxResolutionFailures::set(2);
+        // The authorized account doesn't submit an outcome in time, so the resolution fails.
+        assert_ok!(PredictionMarkets::create_categorical_market(
+            Origin::signed(ALICE),
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::Authorized(SUDO),
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
 
-        for _ in 0..2 {
-            simple_create_categorical_market::<Runtime>(
-                MarketCreation::Permissionless,
-                min_sub_period..max_sub_period,
-                ScoringRule::RikiddoSigmoidFeeMarketEma,
-            );
-        }
+        let resolution_block = 2 + <Runtime as Config>::DisputePeriod::get();
+        run_to_block(resolution_block);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
+        assert_eq!(ResolutionFailures::<Runtime>::get(0), 1);
 
-        simple_create_categorical_market::<Runtime>(
-            MarketCreation::Permissionless,
-            min_sub_period + 42..max_sub_period,
-            ScoringRule::RikiddoSigmoidFeeMarketEma,
-        );
+        PredictionMarkets::on_idle(resolution_block, u64::MAX);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::ResolutionStalled);
+        assert_eq!(ResolutionFailures::<Runtime>::get(0), 0);
+        assert!(MarketIdsPerDisputeBlock::<Runtime>::get(2).is_empty());
+        let has_event = |event| System::events().iter().any(|record| record.event == event);
+        assert!(has_event(Event::PredictionMarkets(crate::Event::MarketResolutionStalled(0))));
 
-        let market_enough_subsidy = 0;
-        let market_insufficient_subsidy = 1;
-        let market_not_ready = 2;
-        let min_subsidy = <Runtime as zrml_swaps::Config>::MinSubsidy::get();
+        run_to_block(resolution_block + 5);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::ResolutionStalled);
 
-        // Give alice enough funds and subsidize one market
-        assert_ok!(Tokens::deposit(Asset::Ztg, &ALICE, min_subsidy));
-        assert_ok!(Swaps::pool_join_subsidy(
+        assert_ok!(Authorized::authorize_market_outcome(
+            Origin::signed(SUDO),
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::admin_move_market_to_resolved(Origin::signed(SUDO), 0));
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(0)));
+    });
+}
+
+#[test]
+fn a_stalled_market_cannot_be_disputed() {
+    ExtBuilder::default().build().execute_with(|| {
+        MaxResolutionFailures::set(1);
+        assert_ok!(PredictionMarkets::create_categorical_market(
             Origin::signed(ALICE),
-            market_enough_subsidy,
-            min_subsidy
+            BOB,
+            MarketPeriod::Block(0..1),
+            gen_metadata(2),
+            MarketCreation::Permissionless,
+            2,
+            MarketDisputeMechanism::Authorized(SUDO),
+            ScoringRule::CPMM,
+            Default::default()
+        ));
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(0)
         ));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::ResolutionStalled);
 
-        // Run to block where 2 markets are ready and process all markets.
-        run_to_block(min_sub_period);
-        let subsidy_queue = crate::MarketsCollectingSubsidy::<Runtime>::get();
-        assert!(subsidy_queue.len() == 1);
-        assert!(subsidy_queue[0].market_id == market_not_ready);
-        assert!(
-            MarketCommons::market(&market_enough_subsidy).unwrap().status == MarketStatus::Active
-        );
-        assert!(
-            MarketCommons::market(&market_insufficient_subsidy).unwrap().status
-                == MarketStatus::InsufficientSubsidy
+        assert!(!PredictionMarkets::can_dispute(&0));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(EVE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MarketIsNotReportedOrDisputed
         );
     });
 }
 
 #[test]
-fn start_subsidy_creates_pool_and_starts_subsidy() {
+fn global_dispute_requires_max_disputes() {
     ExtBuilder::default().build().execute_with(|| {
-        // Create advised categorical market using Rikiddo.
         simple_create_categorical_market::<Runtime>(
-            MarketCreation::Advised,
-            1337..1337,
-            ScoringRule::RikiddoSigmoidFeeMarketEma,
-        );
-        let market_id = 0;
-        let mut market = MarketCommons::market(&market_id).unwrap();
-
-        // Ensure and set correct market status.
-        assert_err!(
-            PredictionMarkets::start_subsidy(&market, market_id),
-            crate::Error::<Runtime>::MarketIsNotCollectingSubsidy
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        run_to_block(2);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
+            OutcomeReport::Categorical(1)
+        ));
+        assert_noop!(
+            PredictionMarkets::global_dispute(Origin::signed(CHARLIE), 0),
+            Error::<Runtime>::MaxDisputesNotReached
         );
-        assert_ok!(MarketCommons::mutate_market(&market_id, |market_inner| {
-            market_inner.status = MarketStatus::CollectingSubsidy;
-            market = market_inner.clone();
-            Ok(())
-        }));
-
-        // Pool was created and market was registered for state transition into active.
-        assert_ok!(PredictionMarkets::start_subsidy(&market, market_id));
-        assert_ok!(MarketCommons::market_pool(&market_id));
-        let mut inserted = false;
-
-        for market in crate::MarketsCollectingSubsidy::<Runtime>::get() {
-            if market.market_id == market_id {
-                inserted = true;
-            }
-        }
-
-        assert!(inserted);
     });
 }
 
 #[test]
-fn the_entire_market_lifecycle_works_with_timestamps() {
+fn disputes_stop_at_the_max_disputes_of_the_market() {
     ExtBuilder::default().build().execute_with(|| {
-        // Creates a permissionless market.
+        let max_disputes = 2;
+        assert!(max_disputes < <Runtime as Config>::MaxDisputes::get());
         assert_ok!(PredictionMarkets::create_categorical_market(
             Origin::signed(ALICE),
             BOB,
-            MarketPeriod::Timestamp(0..100_000_000),
+            MarketPeriod::Block(0..1),
             gen_metadata(2),
             MarketCreation::Permissionless,
             2,
             MarketDisputeMechanism::SimpleDisputes,
-            ScoringRule::CPMM
+            ScoringRule::CPMM,
+            MarketCreationOptions { max_disputes: Some(max_disputes), ..Default::default() },
         ));
-
-        // is ok
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT));
-
-        // set the timestamp
-        Timestamp::set_timestamp(123_456_789);
-
-        assert_noop!(
-            PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, CENT),
-            Error::<Runtime>::MarketIsNotActive,
-        );
-
+        assert_eq!(MarketCommons::market(&0).unwrap().max_disputes, Some(max_disputes));
+        run_to_block(2);
         assert_ok!(PredictionMarkets::report(
             Origin::signed(BOB),
             0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            0,
             OutcomeReport::Categorical(1)
         ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(DAVE),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_noop!(
+            PredictionMarkets::dispute(Origin::signed(EVE), 0, OutcomeReport::Categorical(1)),
+            Error::<Runtime>::MaxDisputesReached
+        );
+        // The per-market limit also allows to start a global dispute.
+        assert_ok!(PredictionMarkets::global_dispute(Origin::signed(CHARLIE), 0));
     });
 }
 
 #[test]
-fn full_scalar_market_lifecycle() {
+fn it_does_not_create_markets_with_an_invalid_max_disputes() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(PredictionMarkets::create_scalar_market(
-            Origin::signed(ALICE),
-            BOB,
-            MarketPeriod::Timestamp(0..100_000_000),
-            gen_metadata(3),
-            MarketCreation::Permissionless,
-            10..=30,
-            MarketDisputeMechanism::SimpleDisputes,
-            ScoringRule::CPMM
+        let too_many = <Runtime as Config>::MaxDisputes::get() + 1;
+        for max_disputes in [0, too_many] {
+            assert_noop!(
+                PredictionMarkets::create_categorical_market(
+                    Origin::signed(ALICE),
+                    BOB,
+                    MarketPeriod::Block(0..1),
+                    gen_metadata(2),
+                    MarketCreation::Permissionless,
+                    2,
+                    MarketDisputeMechanism::SimpleDisputes,
+                    ScoringRule::CPMM,
+                    MarketCreationOptions {
+                        max_disputes: Some(max_disputes),
+                        ..Default::default()
+                    },
+                ),
+                Error::<Runtime>::InvalidMaxDisputes
+            );
+            assert_noop!(
+                PredictionMarkets::create_scalar_market(
+                    Origin::signed(ALICE),
+                    BOB,
+                    MarketPeriod::Block(0..1),
+                    gen_metadata(2),
+                    MarketCreation::Permissionless,
+                    10..=30,
+                    MarketDisputeMechanism::SimpleDisputes,
+                    ScoringRule::CPMM,
+                    MarketCreationOptions {
+                        max_disputes: Some(max_disputes),
+                        ..Default::default()
+                    },
+                ),
+                Error::<Runtime>::InvalidMaxDisputes
+            );
+        }
+    });
+}
+
+#[test]
+fn global_dispute_resolves_to_the_outcome_with_the_highest_stake() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        let end = 2 + <Runtime as Config>::GlobalDisputePeriod::get();
+        assert_eq!(MarketIdsPerGlobalDisputeEnd::<Runtime>::get(end), vec![0]);
+
+        // The last dispute was `Categorical(0)`, but the votes overrule it.
+        let outcome = OutcomeReport::Categorical(1);
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(FRED),
+            0,
+            outcome.clone(),
+            30 * BASE
+        ));
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(BOB),
+            0,
+            outcome.clone(),
+            10 * BASE
+        ));
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(SUDO),
+            0,
+            OutcomeReport::Categorical(0),
+            20 * BASE
         ));
+        assert_eq!(Balances::reserved_balance(&FRED), 30 * BASE);
 
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 100 * BASE,));
+        // The market isn't resolved at the end of the regular dispute period.
+        run_to_block(end - 1);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Disputed);
 
-        // check balances
-        let assets = PredictionMarkets::outcome_assets(0, &MarketCommons::market(&0).unwrap());
-        assert_eq!(assets.len(), 2);
-        for asset in assets.iter() {
-            let bal = Tokens::free_balance(*asset, &CHARLIE);
-            assert_eq!(bal, 100 * BASE);
+        run_to_block(end);
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(outcome));
+
+        // The losing stake is split proportionally between the winning voters.
+        assert_eq!(Balances::free_balance(&FRED), 1_015 * BASE);
+        assert_eq!(Balances::free_balance(&BOB), 1_005 * BASE);
+        assert_eq!(Balances::free_balance(&SUDO), 980 * BASE);
+        assert_eq!(Balances::reserved_balance(&FRED), 0);
+        assert_eq!(Balances::reserved_balance(&BOB), 0);
+        assert_eq!(Balances::reserved_balance(&SUDO), 0);
+        assert_eq!(GlobalDisputeVotes::<Runtime>::iter_prefix(0).count(), 0);
+    });
+}
+
+#[test]
+fn global_dispute_hands_rounding_leftovers_to_the_treasury() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        let end = 2 + <Runtime as Config>::GlobalDisputePeriod::get();
+        for (voter, outcome, stake) in
+            vec![(FRED, 1, 10 * BASE), (BOB, 1, 10 * BASE), (SUDO, 1, 10 * BASE), (ALICE, 0, BASE)]
+        {
+            assert_ok!(PredictionMarkets::vote_on_global_dispute(
+                Origin::signed(voter),
+                0,
+                OutcomeReport::Categorical(outcome),
+                stake
+            ));
         }
 
-        Timestamp::set_timestamp(123_456_789);
-        run_to_block(100);
+        run_to_block(end);
+        assert_eq!(
+            MarketCommons::market(&0).unwrap().resolved_outcome,
+            Some(OutcomeReport::Categorical(1))
+        );
 
-        // report
-        assert_ok!(PredictionMarkets::report(Origin::signed(BOB), 0, OutcomeReport::Scalar(100)));
+        // The losing stake can't be split evenly between the three winning voters.
+        let rewards: u128 = [FRED, BOB, SUDO]
+            .iter()
+            .map(|voter| Balances::free_balance(voter) - 1_000 * BASE)
+            .sum();
+        assert!(Treasury::balance() > 0);
+        assert_eq!(rewards + Treasury::balance(), BASE);
+    });
+}
 
-        let market_after_report = MarketCommons::market(&0).unwrap();
-        assert_eq!(market_after_report.report.is_some(), true);
-        let report = market_after_report.report.unwrap();
-        assert_eq!(report.at, 100);
-        assert_eq!(report.by, BOB);
-        assert_eq!(report.outcome, OutcomeReport::Scalar(100));
+#[test]
+fn it_does_not_allow_trading_complete_sets_during_a_global_dispute() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        assert!(PredictionMarkets::is_global_disputed(&0));
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(FRED), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
+        assert_noop!(
+            PredictionMarkets::sell_complete_set(Origin::signed(FRED), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
 
-        // dispute
-        assert_ok!(PredictionMarkets::dispute(Origin::signed(DAVE), 0, OutcomeReport::Scalar(20)));
-        let disputes = crate::Disputes::<Runtime>::get(&0);
-        assert_eq!(disputes.len(), 1);
+        run_to_block(2 + <Runtime as Config>::GlobalDisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert!(!PredictionMarkets::is_global_disputed(&0));
+        assert_noop!(
+            PredictionMarkets::buy_complete_set(Origin::signed(FRED), 0, CENT),
+            Error::<Runtime>::MarketIsNotActive
+        );
+    });
+}
 
-        run_to_block(150);
+#[test]
+fn global_dispute_ties_resolve_to_the_lowest_outcome() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(FRED),
+            0,
+            OutcomeReport::Categorical(1),
+            10 * BASE
+        ));
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(SUDO),
+            0,
+            OutcomeReport::Categorical(0),
+            10 * BASE
+        ));
 
-        let market_after_resolve = MarketCommons::market(&0).unwrap();
-        assert_eq!(market_after_resolve.status, MarketStatus::Resolved);
+        run_to_block(2 + <Runtime as Config>::GlobalDisputePeriod::get());
+        let market = MarketCommons::market(&0).unwrap();
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(market.resolved_outcome, Some(OutcomeReport::Categorical(0)));
+        assert_eq!(Balances::free_balance(&FRED), 990 * BASE);
+        assert_eq!(Balances::free_balance(&SUDO), 1_010 * BASE);
+    });
+}
 
-        // give EVE some shares
-        assert_ok!(Tokens::transfer(
-            Origin::signed(CHARLIE),
-            EVE,
-            Asset::ScalarOutcome(0, ScalarPosition::Short),
-            100 * BASE
+#[test]
+fn the_first_global_dispute_vote_must_meet_the_initial_stake() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        let initial_stake = MinGlobalDisputeInitialStake::get();
+        assert_noop!(
+            PredictionMarkets::vote_on_global_dispute(
+                Origin::signed(FRED),
+                0,
+                OutcomeReport::Categorical(1),
+                initial_stake - 1
+            ),
+            Error::<Runtime>::GlobalDisputeStakeTooLow
+        );
+        assert!(GlobalDisputeVotes::<Runtime>::iter_prefix(&0).next().is_none());
+    });
+}
+
+#[test]
+fn a_sufficient_initial_stake_opens_global_dispute_voting() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_and_globally_dispute_categorical_market();
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(FRED),
+            0,
+            OutcomeReport::Categorical(1),
+            MinGlobalDisputeInitialStake::get()
         ));
 
+        // Subsequent voters only need to meet `MinGlobalDisputeStake`.
+        let stake = MinGlobalDisputeStake::get();
+        assert_noop!(
+            PredictionMarkets::vote_on_global_dispute(
+                Origin::signed(SUDO),
+                0,
+                OutcomeReport::Categorical(0),
+                stake - 1
+            ),
+            Error::<Runtime>::GlobalDisputeStakeTooLow
+        );
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(SUDO),
+            0,
+            OutcomeReport::Categorical(0),
+            stake
+        ));
         assert_eq!(
-            Tokens::free_balance(Asset::ScalarOutcome(0, ScalarPosition::Short), &CHARLIE),
-            0
+            GlobalDisputeVotes::<Runtime>::get(&0, &SUDO),
+            Some((OutcomeReport::Categorical(0), stake))
         );
+    });
+}
 
-        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(CHARLIE), 0));
-        for asset in assets.iter() {
-            let bal = Tokens::free_balance(*asset, &CHARLIE);
-            assert_eq!(bal, 0);
+#[test]
+fn pending_resolution_markets_lists_reported_and_disputed_markets_until_resolution() {
+    ExtBuilder::default().build().execute_with(|| {
+        for _ in 0..4 {
+            simple_create_categorical_market::<Runtime>(
+                MarketCreation::Permissionless,
+                0..1,
+                ScoringRule::CPMM,
+                Default::default(),
+            );
         }
+        run_to_block(2);
+        assert!(PredictionMarkets::pending_resolution_markets().is_empty());
 
-        // check payouts is right for each CHARLIE and EVE
-        let ztg_bal_charlie = Balances::free_balance(&CHARLIE);
-        let ztg_bal_eve = Balances::free_balance(&EVE);
-        assert_eq!(ztg_bal_charlie, 950 * BASE);
-        assert_eq!(ztg_bal_eve, 1000 * BASE);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            0,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            1,
+            OutcomeReport::Categorical(0)
+        ));
+        run_to_block(3);
+        assert_ok!(PredictionMarkets::report(
+            Origin::signed(BOB),
+            2,
+            OutcomeReport::Categorical(0)
+        ));
+        assert_ok!(PredictionMarkets::dispute(
+            Origin::signed(CHARLIE),
+            1,
+            OutcomeReport::Categorical(1)
+        ));
+        // Market 3 is closed, but not reported.
+        assert_eq!(PredictionMarkets::pending_resolution_markets(), vec![0, 1, 2]);
 
-        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(EVE), 0));
-        let ztg_bal_eve_after = Balances::free_balance(&EVE);
-        assert_eq!(ztg_bal_eve_after, 1050 * BASE);
-    })
+        let dispute_period = <Runtime as Config>::DisputePeriod::get();
+        run_to_block(2 + dispute_period);
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert_eq!(PredictionMarkets::pending_resolution_markets(), vec![1, 2]);
+
+        run_to_block(3 + dispute_period);
+        assert!(PredictionMarkets::pending_resolution_markets().is_empty());
+    });
 }
 
 #[test]
-fn market_resolve_does_not_hold_liquidity_withdraw() {
+fn pending_resolution_markets_includes_globally_disputed_markets() {
     ExtBuilder::default().build().execute_with(|| {
-        assert_ok!(PredictionMarkets::create_categorical_market(
-            Origin::signed(ALICE),
-            BOB,
-            MarketPeriod::Block(0..100),
-            gen_metadata(2),
-            MarketCreation::Permissionless,
-            3,
-            MarketDisputeMechanism::SimpleDisputes,
-            ScoringRule::CPMM
+        create_and_globally_dispute_categorical_market();
+        assert_eq!(PredictionMarkets::pending_resolution_markets(), vec![0]);
+
+        assert_ok!(PredictionMarkets::vote_on_global_dispute(
+            Origin::signed(FRED),
+            0,
+            OutcomeReport::Categorical(1),
+            MinGlobalDisputeInitialStake::get()
         ));
-        deploy_swap_pool(MarketCommons::market(&0).unwrap(), 0).unwrap();
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(ALICE), 0, 1 * BASE));
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(BOB), 0, 2 * BASE));
-        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(CHARLIE), 0, 3 * BASE));
+        run_to_block(2 + <Runtime as Config>::DisputePeriod::get());
+        assert_eq!(PredictionMarkets::pending_resolution_markets(), vec![0]);
 
-        run_to_block(100);
-        assert_ok!(PredictionMarkets::report(
-            Origin::signed(BOB),
+        run_to_block(2 + <Runtime as Config>::GlobalDisputePeriod::get());
+        assert_eq!(MarketCommons::market(&0).unwrap().status, MarketStatus::Resolved);
+        assert!(PredictionMarkets::pending_resolution_markets().is_empty());
+    });
+}
+
+#[test]
+fn admin_migrate_pool_requires_a_pool_with_the_market_assets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(deploy_swap_pool(MarketCommons::market(&0).unwrap(), 0));
+
+        // Pool 1 belongs to market 1 and pool 2 to market 0.
+        for market_id in [1, 0] {
+            let market = MarketCommons::market(&market_id).unwrap();
+            let mut assets = PredictionMarkets::outcome_assets(market_id, &market);
+            assets.push(Asset::Ztg);
+            let weights = assets.iter().map(|_| BASE).collect();
+            assert_ok!(PredictionMarkets::buy_complete_set(
+                Origin::signed(FRED),
+                market_id,
+                100 * BASE
+            ));
+            assert_ok!(Swaps::create_pool(
+                FRED,
+                assets,
+                Some(Asset::Ztg),
+                market_id,
+                ScoringRule::CPMM,
+                Some(0),
+                Some(weights),
+            ));
+        }
+
+        assert_noop!(
+            PredictionMarkets::admin_migrate_pool(Origin::signed(SUDO), 0, 3),
+            Error::<Runtime>::PoolDoesNotExist
+        );
+        assert_noop!(
+            PredictionMarkets::admin_migrate_pool(Origin::signed(SUDO), 0, 1),
+            Error::<Runtime>::PoolDoesNotContainAsset
+        );
+        assert_noop!(
+            PredictionMarkets::admin_migrate_pool(Origin::signed(FRED), 0, 2),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PredictionMarkets::admin_migrate_pool(Origin::signed(SUDO), 0, 2));
+        assert_eq!(MarketCommons::market_pool(&0), Ok(2));
+    });
+}
+
+#[test]
+fn admin_migrate_pool_rejects_a_pool_with_additional_assets() {
+    ExtBuilder::default().build().execute_with(|| {
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        simple_create_categorical_market::<Runtime>(
+            MarketCreation::Permissionless,
+            0..1,
+            ScoringRule::CPMM,
+            Default::default(),
+        );
+        assert_ok!(deploy_swap_pool(MarketCommons::market(&0).unwrap(), 0));
+
+        // Pool 1 contains the assets of market 0 and an outcome asset of market 1.
+        let market = MarketCommons::market(&0).unwrap();
+        let mut assets = PredictionMarkets::outcome_assets(0, &market);
+        assets.push(Asset::CategoricalOutcome(1, 0));
+        assets.push(Asset::Ztg);
+        let weights = assets.iter().map(|_| BASE).collect();
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(FRED), 0, 100 * BASE));
+        assert_ok!(PredictionMarkets::buy_complete_set(Origin::signed(FRED), 1, 100 * BASE));
+        assert_ok!(Swaps::create_pool(
+            FRED,
+            assets,
+            Some(Asset::Ztg),
             0,
-            OutcomeReport::Categorical(2)
+            ScoringRule::CPMM,
+            Some(0),
+            Some(weights),
         ));
 
-        run_to_block(150);
-        assert_ok!(Swaps::pool_exit(Origin::signed(BOB), 0, BASE * 100, vec![0, 0]));
-        assert_ok!(PredictionMarkets::redeem_shares(Origin::signed(BOB), 0));
-    })
+        assert_noop!(
+            PredictionMarkets::admin_migrate_pool(Origin::signed(SUDO), 0, 1),
+            Error::<Runtime>::PoolAssetCountMismatch
+        );
+        assert_eq!(MarketCommons::market_pool(&0), Ok(0));
+    });
 }
 
 fn deploy_swap_pool(market: Market<u128, u64, u64>, market_id: u128) -> DispatchResult {
@@ -29,15 +29,20 @@ use frame_support::{traits::Get, weights::Weight};
 pub trait WeightInfoZeitgeist {
     fn admin_destroy_disputed_market(a: u32, b: u32, c: u32) -> Weight;
     fn admin_destroy_reported_market(a: u32, b: u32, c: u32) -> Weight;
+    fn admin_extend_review() -> Weight;
+    fn admin_migrate_pool(a: u32) -> Weight;
     fn admin_move_market_to_closed() -> Weight;
     fn admin_move_market_to_resolved_overhead() -> Weight;
     fn approve_market() -> Weight;
+    fn attach_market_evidence(a: u32) -> Weight;
     fn buy_complete_set(a: u32) -> Weight;
     fn cancel_pending_market() -> Weight;
+    fn claim_dispute_bond(a: u32) -> Weight;
     fn create_categorical_market() -> Weight;
     fn create_scalar_market() -> Weight;
     fn deploy_swap_pool_for_market(a: u32) -> Weight;
     fn dispute(_a: u32) -> Weight;
+    fn global_dispute() -> Weight;
     fn internal_resolve_categorical_reported(a: u32, b: u32, c: u32) -> Weight;
     fn internal_resolve_categorical_disputed(a: u32, b: u32, c: u32, _d: u32) -> Weight;
     fn internal_resolve_scalar_reported() -> Weight;
@@ -50,6 +55,7 @@ pub trait WeightInfoZeitgeist {
     fn report() -> Weight;
     fn sell_complete_set(a: u32) -> Weight;
     fn start_subsidy(a: u32) -> Weight;
+    fn vote_on_global_dispute() -> Weight;
 }
 
 /// Weight functions for zrml_prediction_markets (automatically generated)
@@ -83,6 +89,18 @@ impl<T: frame_system::Config> WeightInfoZeitgeist for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
     }
+    fn admin_extend_review() -> Weight {
+        (36_217_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn admin_migrate_pool(a: u32) -> Weight {
+        (44_380_000 as Weight)
+            // Standard Error: 1_000
+            .saturating_add((121_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn admin_move_market_to_closed() -> Weight {
         (23_930_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
@@ -98,6 +116,13 @@ impl<T: frame_system::Config> WeightInfoZeitgeist for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn attach_market_evidence(a: u32) -> Weight {
+        (38_122_000 as Weight)
+            // Standard Error: 3_000
+            .saturating_add((274_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn buy_complete_set(a: u32) -> Weight {
         (169_548_000 as Weight)
             // Standard Error: 76_000
@@ -112,6 +137,13 @@ impl<T: frame_system::Config> WeightInfoZeitgeist for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn claim_dispute_bond(a: u32) -> Weight {
+        (41_507_000 as Weight)
+            // Standard Error: 2_000
+            .saturating_add((391_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn create_categorical_market() -> Weight {
         (108_789_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -134,6 +166,11 @@ impl<T: frame_system::Config> WeightInfoZeitgeist for WeightInfo<T> {
     fn dispute(_a: u32) -> Weight {
         (21_465_000 as Weight).saturating_add(T::DbWeight::get().reads(2 as Weight))
     }
+    fn global_dispute() -> Weight {
+        (48_311_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn internal_resolve_categorical_reported(a: u32, b: u32, c: u32) -> Weight {
         (21_562_000 as Weight)
             // Standard Error: 1_000
@@ -208,4 +245,9 @@ impl<T: frame_system::Config> WeightInfoZeitgeist for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
+    fn vote_on_global_dispute() -> Weight {
+        (64_705_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
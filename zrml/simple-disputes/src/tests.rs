@@ -8,30 +8,36 @@ use frame_support::assert_noop;
 use zeitgeist_primitives::{
     traits::DisputeApi,
     types::{
-        Market, MarketCreation, MarketDispute, MarketDisputeMechanism, MarketPeriod, MarketStatus,
-        MarketType, OutcomeReport, Report, ScoringRule,
+        Market, MarketCreation, MarketDispute, MarketDisputeMechanism, MarketKind, MarketPeriod,
+        MarketStatus, MarketType, OutcomeReport, Report, ScoringRule,
     },
 };
 
-const DEFAULT_MARKET: Market<u128, u64, u64> = Market {
-    creation: MarketCreation::Permissionless,
-    creator_fee: 0,
-    creator: 0,
-    market_type: MarketType::Scalar(0..=100),
-    mdm: MarketDisputeMechanism::SimpleDisputes,
-    metadata: vec![],
-    oracle: 0,
-    period: MarketPeriod::Block(0..100),
-    report: None,
-    resolved_outcome: None,
-    scoring_rule: ScoringRule::CPMM,
-    status: MarketStatus::Disputed,
-};
+fn default_market() -> Market<u128, u64, u64> {
+    Market {
+        creation: MarketCreation::Permissionless,
+        creator_fee: 0,
+        creator: 0,
+        created_at: 0,
+        created_at_timestamp: 0,
+        kind: MarketKind::Scalar,
+        market_type: MarketType::Scalar(0..=100),
+        max_disputes: None,
+        mdm: MarketDisputeMechanism::SimpleDisputes,
+        metadata: Default::default(),
+        oracle: 0,
+        period: MarketPeriod::Block(0..100),
+        report: None,
+        resolved_outcome: None,
+        scoring_rule: ScoringRule::CPMM,
+        status: MarketStatus::Disputed,
+    }
+}
 
 #[test]
 fn on_dispute_denies_non_simple_disputes_markets() {
     ExtBuilder.build().execute_with(|| {
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.mdm = MarketDisputeMechanism::Court;
         assert_noop!(
             SimpleDisputes::on_dispute(&[], &0, &market),
@@ -43,7 +49,7 @@ fn on_dispute_denies_non_simple_disputes_markets() {
 #[test]
 fn on_resolution_denies_non_simple_disputes_markets() {
     ExtBuilder.build().execute_with(|| {
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.mdm = MarketDisputeMechanism::Court;
         assert_noop!(
             SimpleDisputes::on_resolution(&[], &0, &market),
@@ -56,9 +62,9 @@ fn on_resolution_denies_non_simple_disputes_markets() {
 fn on_resolution_sets_reported_outcome_of_reported_markets_as_the_canonical_outcome() {
     ExtBuilder.build().execute_with(|| {
         let outcome = OutcomeReport::Scalar(3);
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.status = MarketStatus::Reported;
-        market.report = Some(Report { at: 0, by: 0, outcome: outcome.clone() });
+        market.report = Some(Report { at: 0, by: 0, outcome: outcome.clone(), by_oracle: true });
         assert_eq!(outcome, SimpleDisputes::on_resolution(&[], &0, &market).unwrap())
     });
 }
@@ -66,7 +72,7 @@ fn on_resolution_sets_reported_outcome_of_reported_markets_as_the_canonical_outc
 #[test]
 fn on_resolution_sets_the_last_dispute_of_disputed_markets_as_the_canonical_outcome() {
     ExtBuilder.build().execute_with(|| {
-        let mut market = DEFAULT_MARKET;
+        let mut market = default_market();
         market.status = MarketStatus::Disputed;
         let disputes = [
             MarketDispute { at: 0, by: 0, outcome: OutcomeReport::Scalar(0) },
@@ -1578,6 +1578,14 @@ mod pallet {
             Ok(Self::pool_by_id(pool_id)?)
         }
 
+        fn pool_contains_asset(pool_id: PoolId, asset: &Asset<Self::MarketId>) -> bool {
+            Self::pool_by_id(pool_id).map(|pool| pool.assets.contains(asset)).unwrap_or(false)
+        }
+
+        fn pool_exists(pool_id: PoolId) -> bool {
+            Self::pools(pool_id).is_some()
+        }
+
         /// Pool will be marked as `PoolStatus::Stale`. If market is categorical, removes everything
         /// that is not ZTG or winning assets from the selected pool. Additionally, it distributes
         /// the rewards to all pool share holders.
@@ -1629,6 +1637,15 @@ mod pallet {
                     total_assets = pool.assets.len();
                 }
 
+                // An invalid market has no winning asset, so there is nothing to retain or
+                // distribute.
+                if *outcome_report == OutcomeReport::Invalid
+                    && pool.scoring_rule == ScoringRule::CPMM
+                {
+                    pool.pool_status = PoolStatus::Stale;
+                    return Ok(());
+                }
+
                 let winning_asset_unwrapped = winning_asset?;
 
                 if pool.scoring_rule == ScoringRule::RikiddoSigmoidFeeMarketEma {
@@ -528,6 +528,23 @@ fn pool_amount_must_not_be_zero() {
     });
 }
 
+#[test]
+fn pool_exists_and_pool_contains_asset_reflect_the_pools() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert!(!Swaps::pool_exists(0));
+        assert!(!Swaps::pool_contains_asset(0, &ASSET_A));
+
+        create_initial_pool(ScoringRule::CPMM, true);
+        assert!(Swaps::pool_exists(0));
+        assert!(!Swaps::pool_exists(1));
+        for asset in ASSETS.iter() {
+            assert!(Swaps::pool_contains_asset(0, asset));
+        }
+        assert!(!Swaps::pool_contains_asset(0, &ASSET_E));
+        assert!(!Swaps::pool_contains_asset(1, &ASSET_A));
+    });
+}
+
 #[test]
 fn pool_exit_decreases_correct_pool_parameters() {
     ExtBuilder::default().build().execute_with(|| {